@@ -1,14 +1,37 @@
-mod better_stack;
+pub mod allocator;
+pub mod arena_list;
+pub mod better_stack;
+pub mod circular_list;
 pub mod doubly_linked_list;
-mod persistent_stack;
-mod stack;
-mod unsafe_queue;
+pub mod intrusive_list;
+pub mod persistent_queue;
+pub mod persistent_stack;
+pub mod skip_list;
+pub mod stack;
+pub mod sync;
+mod traits;
+pub mod unrolled_linked_list;
+pub mod unsafe_queue;
+pub mod xor_linked_list;
+
+pub use traits::{Deque, PersistentStack, Queue, Stack};
 
 #[allow(dead_code)]
 fn assert_properties() {
     fn is_send<T: Send>() {}
     fn is_sync<T: Sync>() {}
 
+    is_send::<stack::List<i32>>();
+    is_sync::<stack::List<i32>>();
+
+    is_send::<better_stack::List<i32>>();
+    is_sync::<better_stack::List<i32>>();
+
+    is_send::<unsafe_queue::List<i32>>();
+    is_sync::<unsafe_queue::List<i32>>();
+    is_send::<unsafe_queue::IntoIter<i32>>();
+    is_sync::<unsafe_queue::IntoIter<i32>>();
+
     is_send::<doubly_linked_list::LinkedList<i32>>();
     is_sync::<doubly_linked_list::LinkedList<i32>>();
 
@@ -21,6 +44,78 @@ fn assert_properties() {
     is_send::<doubly_linked_list::IterMut<i32>>();
     is_sync::<doubly_linked_list::IterMut<i32>>();
 
+    is_send::<doubly_linked_list::Cursor<i32>>();
+    is_sync::<doubly_linked_list::Cursor<i32>>();
+
+    is_send::<doubly_linked_list::NodeHandle<i32>>();
+    is_sync::<doubly_linked_list::NodeHandle<i32>>();
+
+    is_send::<unrolled_linked_list::UnrolledList<i32, 8>>();
+    is_sync::<unrolled_linked_list::UnrolledList<i32, 8>>();
+
+    is_send::<unrolled_linked_list::Iter<i32, 8>>();
+    is_sync::<unrolled_linked_list::Iter<i32, 8>>();
+
+    is_send::<unrolled_linked_list::IterMut<i32, 8>>();
+    is_sync::<unrolled_linked_list::IterMut<i32, 8>>();
+
+    is_send::<unrolled_linked_list::Cursor<i32, 8>>();
+    is_sync::<unrolled_linked_list::Cursor<i32, 8>>();
+
+    is_send::<arena_list::ArenaList<i32>>();
+    is_sync::<arena_list::ArenaList<i32>>();
+
+    is_send::<arena_list::IterMut<i32>>();
+    is_sync::<arena_list::IterMut<i32>>();
+
+    is_send::<circular_list::CircularList<i32>>();
+    is_sync::<circular_list::CircularList<i32>>();
+
+    is_send::<circular_list::Iter<i32>>();
+    is_sync::<circular_list::Iter<i32>>();
+
+    is_send::<circular_list::IterMut<i32>>();
+    is_sync::<circular_list::IterMut<i32>>();
+
+    is_send::<xor_linked_list::XorLinkedList<i32>>();
+    is_sync::<xor_linked_list::XorLinkedList<i32>>();
+
+    is_send::<xor_linked_list::Iter<i32>>();
+    is_sync::<xor_linked_list::Iter<i32>>();
+
+    is_send::<xor_linked_list::IterMut<i32>>();
+    is_sync::<xor_linked_list::IterMut<i32>>();
+
+    is_send::<skip_list::SkipList<i32, i32>>();
+    is_sync::<skip_list::SkipList<i32, i32>>();
+
+    is_send::<skip_list::Range<i32, i32>>();
+    is_sync::<skip_list::Range<i32, i32>>();
+
+    is_send::<skip_list::RangeMut<i32, i32>>();
+    is_sync::<skip_list::RangeMut<i32, i32>>();
+
+    is_send::<skip_list::SkipSet<i32>>();
+    is_sync::<skip_list::SkipSet<i32>>();
+
+    is_send::<persistent_stack::SyncList<i32>>();
+    is_sync::<persistent_stack::SyncList<i32>>();
+
+    is_send::<persistent_stack::SyncZipper<i32>>();
+    is_sync::<persistent_stack::SyncZipper<i32>>();
+
+    is_send::<persistent_queue::SyncList<i32>>();
+    is_sync::<persistent_queue::SyncList<i32>>();
+
+    is_send::<sync::BlockingQueue<i32>>();
+    is_sync::<sync::BlockingQueue<i32>>();
+
+    is_send::<sync::LockFreeQueue<i32>>();
+    is_sync::<sync::LockFreeQueue<i32>>();
+
+    is_send::<sync::LockFreeStack<i32>>();
+    is_sync::<sync::LockFreeStack<i32>>();
+
     fn linked_list_covariant<'a, T>(
         x: doubly_linked_list::LinkedList<&'static T>,
     ) -> doubly_linked_list::LinkedList<&'a T> {
@@ -33,6 +128,12 @@ fn assert_properties() {
         x
     }
 
+    fn cursor_covariant<'i, 'a, T>(
+        x: doubly_linked_list::Cursor<'a, &'static T>,
+    ) -> doubly_linked_list::Cursor<'i, &'a T> {
+        x
+    }
+
     fn into_iter_covariant<'a, T>(
         x: doubly_linked_list::IntoIter<&'static T>,
     ) -> doubly_linked_list::IntoIter<&'a T> {
@@ -116,9 +217,139 @@ mod test_stack {
         // assert
         assert_eq!(value, None);
     }
+
+    #[test]
+    fn test_len() {
+        // arrange
+        let mut list = stack::List::new();
+
+        // act & assert
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        // act & assert
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+
+        // act & assert
+        list.pop();
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn test_peek() {
+        // arrange
+        let mut list = stack::List::new();
+        assert_eq!(list.peek(), None);
+        list.push(1);
+        list.push(2);
+
+        // act & assert
+        assert_eq!(list.peek(), Some(&2));
+
+        // act
+        if let Some(value) = list.peek_mut() {
+            *value = 5;
+        }
+
+        // assert
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.peek(), Some(&1));
+    }
+
+    #[test]
+    fn test_iter() {
+        // arrange
+        let mut list = stack::List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        // act
+        let mut iter = list.iter();
+
+        // assert
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        // arrange
+        let mut list: stack::List<i32> = (1..4).collect();
+
+        // act
+        for value in &mut list {
+            *value *= 10;
+        }
+
+        // assert
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![30, 20, 10]);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        // arrange
+        let list: stack::List<i32> = (1..4).collect();
+
+        // act
+        let values: Vec<_> = list.into_iter().collect();
+
+        // assert
+        assert_eq!(values, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        // arrange
+        let mut list: stack::List<i32> = (1..3).collect();
+
+        // act
+        list.extend([3, 4]);
+
+        // assert
+        // the last element of the iterator ends up on top
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_clone_eq() {
+        // arrange
+        let list: stack::List<i32> = (1..4).collect();
+
+        // act
+        let mut other_list = list.clone();
+
+        // assert
+        assert_eq!(list, other_list);
+        assert_eq!(other_list.pop(), Some(3));
+        assert_ne!(list, other_list);
+        assert_eq!(stack::List::<i32>::default(), stack::List::new());
+    }
+
+    #[test]
+    fn test_debug_hash() {
+        // arrange
+        let list: stack::List<i32> = (1..4).collect();
+        let mut set = std::collections::HashSet::new();
+
+        // act & assert
+        assert_eq!(format!("{:?}", list), "[3, 2, 1]");
+        assert!(set.insert(list.clone()));
+        assert!(!set.insert(list));
+    }
 }
 
 #[cfg(test)]
+// the tests spell out both forms on purpose
+#[allow(clippy::option_map_unit_fn)]
 mod test_better_stack {
     use super::*;
 
@@ -322,6 +553,87 @@ mod test_better_stack {
         // assert
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_len() {
+        // arrange
+        let mut list = better_stack::List::new();
+
+        // act & assert
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        // act & assert
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+
+        // act & assert
+        list.pop();
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn test_into_iter_refs() {
+        // arrange
+        let mut list: better_stack::List<i32> = (1..4).collect();
+
+        // act
+        for value in &mut list {
+            *value *= 10;
+        }
+        let mut values = Vec::new();
+        for value in &list {
+            values.push(*value);
+        }
+
+        // assert
+        assert_eq!(values, vec![30, 20, 10]);
+        assert_eq!(list.into_iter().size_hint(), (3, Some(3)));
+    }
+
+    #[test]
+    fn test_from_iter() {
+        // arrange
+        let mut list: better_stack::List<i32> = (1..3).collect();
+
+        // act
+        list.extend([3, 4]);
+
+        // assert
+        // the last element of the iterator ends up on top
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_clone_eq() {
+        // arrange
+        let list: better_stack::List<i32> = (1..4).collect();
+
+        // act
+        let mut other_list = list.clone();
+
+        // assert
+        assert_eq!(list, other_list);
+        assert_eq!(other_list.pop(), Some(3));
+        assert_ne!(list, other_list);
+        assert_eq!(better_stack::List::<i32>::default(), better_stack::List::new());
+    }
+
+    #[test]
+    fn test_debug_hash() {
+        // arrange
+        let list: better_stack::List<i32> = (1..4).collect();
+        let mut set = std::collections::HashSet::new();
+
+        // act & assert
+        assert_eq!(format!("{:?}", list), "[3, 2, 1]");
+        assert!(set.insert(list.clone()));
+        assert!(!set.insert(list));
+    }
 }
 
 #[cfg(test)]
@@ -454,692 +766,931 @@ mod test_persistent_stack {
         assert_eq!(iter.next(), None);
         assert_eq!(iter.next(), None);
     }
-}
-
-#[cfg(test)]
-mod test_unsafe_queue {
-    use super::*;
 
     #[test]
-    fn test_push() {
+    fn test_len() {
         // arrange
-        let mut list = unsafe_queue::List::new();
-
-        // act
-        list.push(1);
-        list.push(2);
-        list.push(3);
-
-        // assert
-        assert_eq!(list.pop(), Some(1));
-        assert_eq!(list.pop(), Some(2));
+        let list = persistent_stack::List::new();
 
-        // push some more elements
         // act
-        list.push(4);
-        list.push(5);
+        let other_list = list.prepend(1).prepend(2);
 
         // assert
-        assert_eq!(list.pop(), Some(3));
-        assert_eq!(list.pop(), Some(4));
-        assert_eq!(list.pop(), Some(5));
-        assert_eq!(list.pop(), None);
+        // every version knows its own length
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+        assert_eq!(other_list.len(), 2);
+        assert_eq!(other_list.tail().len(), 1);
+        assert_eq!(list.tail().len(), 0);
     }
 
     #[test]
-    fn test_pop() {
+    fn test_peek() {
         // arrange
-        let mut list = unsafe_queue::List::new();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-
-        // act
-        let value = list.pop();
+        let list = persistent_stack::List::new().prepend(1).prepend(2);
 
-        // assert
-        assert_eq!(value.is_some(), true);
-        assert_eq!(value, Some(1));
-        assert_eq!(list.pop(), Some(2));
-        assert_eq!(list.pop(), Some(3));
-        assert_eq!(list.pop(), None);
+        // act & assert
+        assert_eq!(list.peek(), Some(&2));
+        assert_eq!(list.peek(), list.head());
+        assert_eq!(persistent_stack::List::<i32>::new().peek(), None);
     }
 
     #[test]
-    fn test_pop_empty() {
+    fn test_into_iter() {
         // arrange
-        let mut list = unsafe_queue::List::<i32>::new();
+        let list = persistent_stack::List::new().prepend(1).prepend(2);
+        let shared = list.prepend(3);
 
         // act
-        let value = list.pop();
+        // the first node is ours, the others are shared with `list`
+        let values: Vec<_> = shared.into_iter().collect();
 
         // assert
-        assert_eq!(value.is_none(), true);
-        assert_eq!(value, None);
+        assert_eq!(values, vec![3, 2, 1]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 1]);
     }
 
     #[test]
-    fn test_peek() {
+    fn test_into_iter_refs() {
         // arrange
-        let mut list = unsafe_queue::List::new();
-        list.push(1);
-        list.push(2);
-        list.push(3);
+        let list: persistent_stack::List<i32> = (1..4).collect();
+        let mut values = Vec::new();
 
         // act
-        let value = list.peek();
+        for value in &list {
+            values.push(*value);
+        }
 
         // assert
-        assert_eq!(value, Some(&{ 1 }));
-        assert_eq!(list.pop(), Some(1));
+        assert_eq!(values, vec![1, 2, 3]);
+    }
 
-        // another peek after pop
-        // act
-        let value = list.peek();
+    #[test]
+    fn test_from_iter() {
+        // arrange & act
+        let list: persistent_stack::List<i32> = (1..4).collect();
 
         // assert
-        assert_eq!(value, Some(&{ 2 }));
-        assert_eq!(list.pop(), Some(2));
-        assert_eq!(list.pop(), Some(3));
+        // the order of the iterator is kept
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.head(), Some(&1));
+        assert_eq!(list.tail().head(), Some(&2));
     }
 
     #[test]
-    fn test_peek_empty() {
+    fn test_clone_eq() {
         // arrange
-        let list = unsafe_queue::List::<i32>::new();
+        let list: persistent_stack::List<i32> = (1..4).collect();
 
         // act
-        let value = list.peek();
+        let other_list = list.clone();
 
         // assert
-        assert_eq!(value, None);
+        assert_eq!(list, other_list);
+        assert_ne!(list, other_list.tail());
+        assert_eq!(list.tail(), other_list.tail());
+        assert_eq!(persistent_stack::List::<i32>::default(), persistent_stack::List::new());
     }
 
     #[test]
-    fn test_peek_mut() {
+    fn test_debug_hash() {
         // arrange
-        let mut list = unsafe_queue::List::new();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-
-        // act
-        if let Some(value) = list.peek_mut() {
-            *value = 5;
-        }
-        // same as above (not sure which I like more/better):
-        list.peek_mut().map(|value| {
-            *value = 5;
-        });
+        let list: persistent_stack::List<i32> = (1..4).collect();
+        let mut set = std::collections::HashSet::new();
 
-        // assert
-        assert_eq!(list.peek_mut(), Some(&mut 5));
-        assert_eq!(list.peek(), Some(&{ 5 }));
-        assert_eq!(list.pop(), Some(5));
-        assert_eq!(list.pop(), Some(2));
-        assert_eq!(list.pop(), Some(3));
-        assert_eq!(list.pop(), None);
+        // act & assert
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+        assert!(set.insert(list.clone()));
+        assert!(!set.insert(list));
     }
 
     #[test]
-    fn test_into_iter() {
+    fn test_sync_shared_between_threads() {
         // arrange
-        let mut list = unsafe_queue::List::new();
-        list.push(1);
-        list.push(2);
-        list.push(3);
+        let list: persistent_stack::SyncList<i32> = (1..4).collect();
 
         // act
-        let mut iter = list.into_iter();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let list = list.clone();
+                std::thread::spawn(move || {
+                    let list = list.prepend(i);
+                    (list.head().copied(), list.iter().sum::<i32>())
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
 
         // assert
-        assert_eq!(iter.next(), Some(1));
-        assert_eq!(iter.next(), Some(2));
-        assert_eq!(iter.next(), Some(3));
-        assert_eq!(iter.next(), None);
+        for (i, result) in results.into_iter().enumerate() {
+            assert_eq!(result, (Some(i as i32), i as i32 + 6));
+        }
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
     }
 
     #[test]
-    fn test_iter() {
+    fn test_sync_drop_shared_long() {
         // arrange
-        let mut list = unsafe_queue::List::new();
-        list.push(1);
-        list.push(2);
-        list.push(3);
+        let shared: persistent_stack::SyncList<i32> = (0..100_000).collect();
 
         // act
-        let mut iter = list.iter();
+        // every thread drops its own long version, which stops at the shared
+        // nodes without recursing
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let mut list = shared.clone();
+                for i in 0..100_000 {
+                    list = list.prepend(i);
+                }
+                std::thread::spawn(move || list.len())
+            })
+            .collect();
+        drop(shared);
 
         // assert
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.next(), Some(&3));
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 200_000);
+        }
     }
 
     #[test]
-    fn test_iter_empty() {
+    fn test_nth_drop() {
         // arrange
-        let list = unsafe_queue::List::<i32>::new();
+        let list: persistent_stack::List<i32> = (1..6).collect();
 
         // act
-        let mut iter = list.iter();
+        let dropped = list.drop(2);
 
         // assert
-        assert_eq!(iter.next(), None);
+        assert_eq!(list.nth(0), Some(&1));
+        assert_eq!(list.nth(4), Some(&5));
+        assert_eq!(list.nth(5), None);
+        assert_eq!(dropped.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(dropped.len(), 3);
+        assert!(dropped.ptr_eq(&list.tail().tail()));
+        assert!(list.drop(7).is_empty());
+        assert!(list.drop(0).ptr_eq(&list));
     }
 
     #[test]
-    fn test_iter_mut() {
+    fn test_take() {
         // arrange
-        let mut list = unsafe_queue::List::new();
-        list.push(1);
-        list.push(2);
-        list.push(3);
+        let list: persistent_stack::List<i32> = (1..6).collect();
 
         // act
-        let mut iter = list.iter_mut();
+        let taken = list.take(3);
 
         // assert
-        assert_eq!(iter.next(), Some(&mut 1));
-        assert_eq!(iter.next(), Some(&mut 2));
-        assert_eq!(iter.next(), Some(&mut 3));
+        assert_eq!(taken.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(taken.len(), 3);
+        assert!(list.take(5).ptr_eq(&list));
+        assert!(list.take(0).is_empty());
     }
 
     #[test]
-    fn test_iter_mut_empty() {
+    fn test_append() {
         // arrange
-        let mut list = unsafe_queue::List::<i32>::new();
+        let list: persistent_stack::List<i32> = (1..3).collect();
+        let other: persistent_stack::List<i32> = (3..5).collect();
 
         // act
-        let mut iter = list.iter_mut();
+        let appended = list.append(&other);
 
         // assert
-        assert_eq!(iter.next(), None);
+        assert_eq!(appended.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(appended.len(), 4);
+        assert!(appended.drop(2).ptr_eq(&other));
+        assert!(persistent_stack::List::new().append(&other).ptr_eq(&other));
+        assert_eq!(list.append(&persistent_stack::List::new()), list);
     }
-}
-
-#[cfg(test)]
-mod test_doubly_linked_list {
-    use super::*;
 
     #[test]
-    fn test_pop_front_empty() {
+    fn test_reverse_map() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+        let list: persistent_stack::List<i32> = (1..4).collect();
 
         // act
-        let value = list.pop_front();
+        let reversed = list.reverse();
+        let mapped = list.map(|elem| elem.to_string());
 
         // assert
-        assert!(value.is_none());
+        assert_eq!(reversed.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(mapped.iter().cloned().collect::<Vec<_>>(), vec!["1", "2", "3"]);
+        assert_eq!(mapped.len(), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
     }
 
     #[test]
-    fn test_pop_front() {
+    fn test_filter_shares_suffix() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
-        list.push_front(1);
-        list.push_front(2);
-        list.push_front(3);
+        let list: persistent_stack::List<i32> = vec![1, 2, 3, 4, 6, 8].into_iter().collect();
 
         // act
-        let value = list.pop_front();
+        let even = list.filter(|elem| elem % 2 == 0);
+        let all = list.filter(|_| true);
+        let none = list.filter(|_| false);
 
         // assert
-        assert_eq!(value.is_some(), true);
-        assert_eq!(value, Some(3));
+        assert_eq!(even.iter().copied().collect::<Vec<_>>(), vec![2, 4, 6, 8]);
+        assert_eq!(even.len(), 4);
+        // everything after the last removed element (3) is shared
+        assert!(even.drop(1).ptr_eq(&list.drop(3)));
+        assert!(all.ptr_eq(&list));
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_ptr_eq() {
+        // arrange
+        let list: persistent_stack::List<i32> = (1..4).collect();
+        let equal: persistent_stack::List<i32> = (1..4).collect();
 
-        // and the other pop's
         // act & assert
-        assert_eq!(list.pop_front(), Some(2));
-        assert_eq!(list.pop_front(), Some(1));
-        assert_eq!(list.pop_front(), None);
+        assert!(list.ptr_eq(&list.clone()));
+        assert_eq!(list, equal);
+        assert!(!list.ptr_eq(&equal));
+        assert!(!list.ptr_eq(&list.tail()));
+        assert!(list.prepend(0).tail().ptr_eq(&list));
+        assert!(persistent_stack::List::<i32>::new().ptr_eq(&persistent_stack::List::new()));
     }
 
     #[test]
-    fn test_push_front() {
+    fn test_zipper_move() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
-
-        // act
-        list.push_front(1);
+        let list: persistent_stack::List<i32> = (1..4).collect();
+        let zipper = persistent_stack::Zipper::from_list(&list);
 
-        // assert
-        assert_eq!(list.len(), 1);
-        assert_eq!(list.pop_front(), Some(1));
-        assert!(list.pop_front().is_none());
+        // act & assert
+        assert!(zipper.is_at_start());
+        assert_eq!(zipper.current(), Some(&1));
+        assert!(zipper.move_left().is_none());
+        let zipper = zipper.move_right().unwrap();
+        assert_eq!((zipper.index(), zipper.current()), (1, Some(&2)));
+        assert_eq!(zipper.peek_left(), Some(&1));
+        let zipper = zipper.move_right().unwrap().move_right().unwrap();
+        assert!(zipper.is_at_end());
+        assert_eq!((zipper.index(), zipper.current()), (3, None));
+        assert!(zipper.move_right().is_none());
+        let zipper = zipper.move_left().unwrap();
+        assert_eq!((zipper.index(), zipper.current()), (2, Some(&3)));
+        assert_eq!(zipper.len(), 3);
+        assert_eq!(zipper.to_list(), list);
     }
 
     #[test]
-    fn test_len_empty() {
+    fn test_zipper_edit() {
         // arrange
-        let list = doubly_linked_list::LinkedList::<i32>::new();
+        let list: persistent_stack::List<i32> = (1..4).collect();
+        let zipper = persistent_stack::Zipper::from(list.clone()).move_right().unwrap();
 
         // act
-        let len = list.len();
+        let inserted = zipper.insert(10);
+        let deleted = zipper.delete().unwrap();
+        let replaced = zipper.replace(20).unwrap();
+        let at_end = zipper.move_right().unwrap().move_right().unwrap();
 
         // assert
-        assert_eq!(len, 0);
+        assert_eq!(inserted.current(), Some(&10));
+        assert_eq!(inserted.to_list().iter().copied().collect::<Vec<_>>(), vec![1, 10, 2, 3]);
+        assert_eq!(deleted.current(), Some(&3));
+        assert_eq!(deleted.to_list().iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(replaced.current(), Some(&20));
+        assert_eq!(replaced.to_list().iter().copied().collect::<Vec<_>>(), vec![1, 20, 3]);
+        assert!(at_end.delete().is_none());
+        assert!(at_end.replace(0).is_none());
+        assert_eq!(at_end.insert(4).to_list().iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        // the old versions are untouched
+        assert_eq!(zipper.to_list(), list);
+        assert_eq!(format!("{:?}", zipper), "Zipper { left: [1], right: [2, 3] }");
     }
 
     #[test]
-    fn test_len() {
+    fn test_zipper_undo() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
-        list.push_front(1);
-        list.push_front(1);
-        list.push_front(1);
+        let mut history = vec![persistent_stack::Zipper::new()];
 
         // act
-        let len = list.len();
+        for i in 0..5 {
+            let zipper = history.last().unwrap().insert(i).move_right().unwrap();
+            history.push(zipper);
+        }
+        history.pop();
+        history.pop();
 
         // assert
-        assert_eq!(len, 3);
+        let zipper = history.last().unwrap();
+        assert_eq!(zipper.to_list().iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(history[0].is_empty());
+    }
+}
 
-        // and another one
+#[cfg(test)]
+mod test_persistent_queue {
+    use super::*;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_push_pop() {
         // arrange
-        list.pop_front();
+        let queue = persistent_queue::List::new();
 
         // act
-        let len = list.len();
+        let queue = queue.push_back(1).push_back(2).push_back(3);
 
         // assert
-        assert_eq!(len, 2);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.peek(), Some(&1));
+        let queue = queue.pop_front();
+        assert_eq!(queue.peek(), Some(&2));
+        let queue = queue.push_back(4);
+        let queue = queue.pop_front();
+        assert_eq!(queue.peek(), Some(&3));
+        let queue = queue.pop_front();
+        assert_eq!(queue.peek(), Some(&4));
+        let queue = queue.pop_front();
+        assert_eq!(queue.peek(), None);
+        assert!(queue.is_empty());
+
+        // an empty queue stays empty
+        let queue = queue.pop_front();
+        assert_eq!(queue.peek(), None);
+        assert_eq!(queue.len(), 0);
+    }
 
-        // and another one
+    #[test]
+    fn test_versions() {
         // arrange
-        list.pop_front();
+        let base: persistent_queue::List<i32> = (0..5).collect();
 
         // act
-        let len = list.len();
+        let popped = base.pop_front().pop_front();
+        let pushed = base.push_back(5);
+        let both = popped.push_back(6);
 
         // assert
-        assert_eq!(len, 1);
+        assert_eq!(base.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(popped.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(pushed.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(both.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 6]);
+    }
 
-        // and the last one
+    #[test]
+    fn test_iter_during_rotation() {
         // arrange
-        list.pop_front();
+        let mut queue = persistent_queue::List::new();
+        let mut model = VecDeque::new();
 
-        // act
-        let len = list.len();
+        // act & assert
+        // alternating pushes and pops hit every state of a rotation
+        for i in 0..200 {
+            queue = queue.push_back(i);
+            model.push_back(i);
+            if i % 3 == 0 {
+                queue = queue.pop_front();
+                model.pop_front();
+            }
+            assert_eq!(queue.len(), model.len());
+            assert_eq!(queue.iter().len(), model.len());
+            assert!(queue.iter().eq(model.iter()));
+            assert_eq!(queue.peek(), model.front());
+        }
+    }
 
-        // assert
-        assert_eq!(len, 0);
+    #[test]
+    fn test_reuse_old_versions() {
+        // arrange
+        let mut versions = vec![(persistent_queue::List::new(), VecDeque::new())];
+        // small linear congruential generator, such that the test is
+        // reproducible
+        let mut seed: u64 = 42;
+        let mut random = move |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
 
-        // just for good measure; another one
+        // act & assert
+        for i in 0..2_000 {
+            let (queue, model): &(persistent_queue::List<i32>, VecDeque<i32>) =
+                &versions[random(versions.len())];
+            let (queue, model) = if random(3) == 0 {
+                let mut model = model.clone();
+                model.pop_front();
+                (queue.pop_front(), model)
+            } else {
+                let mut model = model.clone();
+                model.push_back(i);
+                (queue.push_back(i), model)
+            };
+            assert_eq!(queue.len(), model.len());
+            assert_eq!(queue.peek(), model.front());
+            assert!(queue.iter().eq(model.iter()));
+            versions.push((queue, model));
+        }
+    }
+
+    #[test]
+    fn test_drop_long() {
         // arrange
-        list.pop_back();
+        let mut queue = persistent_queue::List::new();
 
         // act
-        let len = list.len();
+        for i in 0..100_000 {
+            queue = queue.push_back(i);
+        }
+        let half = queue.clone();
+        for _ in 0..50_000 {
+            queue = queue.pop_front();
+        }
 
         // assert
-        assert_eq!(len, 0);
+        assert_eq!(queue.peek(), Some(&50_000));
+        assert_eq!(half.peek(), Some(&0));
+        drop(half);
+        drop(queue);
     }
 
     #[test]
-    fn test_pop_back_empty() {
+    fn test_traits() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+        let queue: persistent_queue::List<i32> = (1..4).collect();
+        let other = persistent_queue::List::new()
+            .push_back(0)
+            .push_back(1)
+            .pop_front()
+            .push_back(2)
+            .push_back(3);
+        let mut set = std::collections::HashSet::new();
 
-        // act
-        let value = list.pop_back();
-
-        // assert
-        assert!(value.is_none());
+        // act & assert
+        assert_eq!(queue, other);
+        assert_ne!(queue, other.pop_front());
+        assert_eq!(persistent_queue::List::<i32>::default(), persistent_queue::List::new());
+        assert_eq!(format!("{:?}", queue), "[1, 2, 3]");
+        assert!(set.insert(queue.clone()));
+        assert!(!set.insert(other));
     }
 
     #[test]
-    fn test_pop_back() {
+    fn test_sync_shared_between_threads() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
-        list.push_front(1);
-        list.push_front(2);
-        list.push_front(3);
+        let queue: persistent_queue::SyncList<i32> = (1..4).collect();
 
         // act
-        let value = list.pop_back();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let queue = queue.clone();
+                std::thread::spawn(move || {
+                    let queue = queue.pop_front().push_back(i);
+                    queue.iter().copied().collect::<Vec<_>>()
+                })
+            })
+            .collect();
 
         // assert
-        assert_eq!(value.is_some(), true);
-        assert_eq!(value, Some(1));
-
-        // and the other pop's
-        // act & assert
-        assert_eq!(list.pop_back(), Some(2));
-        assert_eq!(list.pop_back(), Some(3));
-        assert_eq!(list.pop_back(), None);
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap(), vec![2, 3, i as i32]);
+        }
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
     }
+}
+
+#[cfg(test)]
+// the tests spell out both forms on purpose
+#[allow(clippy::bool_assert_comparison, clippy::option_map_unit_fn)]
+mod test_unsafe_queue {
+    use super::*;
 
     #[test]
-    fn test_push_back() {
+    fn test_push() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
+        let mut list = unsafe_queue::List::new();
 
         // act
-        list.push_back(1);
+        list.push(1);
+        list.push(2);
+        list.push(3);
 
         // assert
-        assert_eq!(list.len(), 1);
-        assert_eq!(list.pop_front(), Some(1));
-        assert!(list.pop_front().is_none());
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), Some(2));
 
-        // another push back with checking for correct order!
+        // push some more elements
         // act
-        list.push_back(1);
-        list.push_back(2);
+        list.push(4);
+        list.push(5);
 
         // assert
-        assert_eq!(list.len(), 2);
-        assert_eq!(list.front(), Some(&1));
-        assert_eq!(list.back(), Some(&2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.pop(), None);
     }
 
     #[test]
-    fn test_front_empty() {
+    fn test_pop() {
         // arrange
-        let list = doubly_linked_list::LinkedList::<i32>::new();
+        let mut list = unsafe_queue::List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
 
         // act
-        let value = list.front();
+        let value = list.pop();
 
         // assert
-        assert!(value.is_none());
+        assert_eq!(value.is_some(), true);
+        assert_eq!(value, Some(1));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), None);
     }
 
     #[test]
-    fn test_front() {
+    fn test_pop_empty() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::<i32>::new();
-        list.push_front(1);
-        list.push_back(3);
+        let mut list = unsafe_queue::List::<i32>::new();
 
         // act
-        let value = list.front();
+        let value = list.pop();
 
         // assert
-        assert!(value.is_some());
-        assert_eq!(value, Some(&1));
+        assert_eq!(value.is_none(), true);
+        assert_eq!(value, None);
     }
 
     #[test]
-    fn test_front_mut_empty() {
+    fn test_peek() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+        let mut list = unsafe_queue::List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
 
         // act
-        let value = list.front_mut();
+        let value = list.peek();
 
         // assert
-        assert!(value.is_none());
+        assert_eq!(value, Some(&{ 1 }));
+        assert_eq!(list.pop(), Some(1));
+
+        // another peek after pop
+        // act
+        let value = list.peek();
+
+        // assert
+        assert_eq!(value, Some(&{ 2 }));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
     }
 
     #[test]
-    fn test_front_mut() {
+    fn test_peek_empty() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::<i32>::new();
-        list.push_front(1);
-        list.push_back(3);
+        let list = unsafe_queue::List::<i32>::new();
 
         // act
-        if let Some(value) = list.front_mut() {
+        let value = list.peek();
+
+        // assert
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_peek_mut() {
+        // arrange
+        let mut list = unsafe_queue::List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        // act
+        if let Some(value) = list.peek_mut() {
             *value = 5;
         }
         // same as above (not sure which I like more/better):
-        list.front_mut().map(|value| {
+        list.peek_mut().map(|value| {
             *value = 5;
         });
 
         // assert
-        assert_eq!(list.len(), 2);
-        assert_eq!(list.back(), Some(&3));
-        assert_eq!(list.front(), Some(&5));
+        assert_eq!(list.peek_mut(), Some(&mut 5));
+        assert_eq!(list.peek(), Some(&{ 5 }));
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), None);
     }
 
     #[test]
-    fn test_back_empty() {
+    fn test_into_iter() {
         // arrange
-        let list = doubly_linked_list::LinkedList::<i32>::new();
+        let mut list = unsafe_queue::List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
 
         // act
-        let value = list.back();
+        let mut iter = list.into_iter();
 
         // assert
-        assert!(value.is_none());
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), None);
     }
 
     #[test]
-    fn test_back() {
+    fn test_iter() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::<i32>::new();
-        list.push_front(1);
-        list.push_back(3);
+        let mut list = unsafe_queue::List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
 
         // act
-        let value = list.back();
+        let mut iter = list.iter();
 
         // assert
-        assert!(value.is_some());
-        assert_eq!(value, Some(&3));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+    }
 
-        // after poping an element
+    #[test]
+    fn test_iter_empty() {
         // arrange
-        list.pop_back();
+        let list = unsafe_queue::List::<i32>::new();
 
         // act
-        let value = list.back();
+        let mut iter = list.iter();
 
         // assert
-        assert_eq!(list.len(), 1);
-        assert!(value.is_some());
-        assert_eq!(value, Some(&1));
+        assert_eq!(iter.next(), None);
+    }
 
-        // and the last one
+    #[test]
+    fn test_iter_mut() {
         // arrange
-        list.pop_front();
+        let mut list = unsafe_queue::List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
 
         // act
-        let value = list.back();
+        let mut iter = list.iter_mut();
 
         // assert
-        assert_eq!(list.len(), 0);
-        assert!(value.is_none());
+        assert_eq!(iter.next(), Some(&mut 1));
+        assert_eq!(iter.next(), Some(&mut 2));
+        assert_eq!(iter.next(), Some(&mut 3));
     }
 
     #[test]
-    fn test_back_mut_empty() {
+    fn test_iter_mut_empty() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+        let mut list = unsafe_queue::List::<i32>::new();
 
         // act
-        let value = list.back_mut();
+        let mut iter = list.iter_mut();
 
         // assert
-        assert!(value.is_none());
+        assert_eq!(iter.next(), None);
     }
 
     #[test]
-    fn test_back_mut() {
+    fn test_len() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::<i32>::new();
-        list.push_front(1);
-        list.push_back(3);
+        let mut list = unsafe_queue::List::new();
 
-        // act
-        if let Some(value) = list.back_mut() {
-            *value = 5;
-        }
-        // or
-        list.back_mut().map(|value| {
-            *value = 5;
-        });
+        // act & assert
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
 
-        // assert
+        // act & assert
+        list.push(1);
+        list.push(2);
         assert_eq!(list.len(), 2);
-        assert_eq!(list.front(), Some(&1));
-        assert_eq!(list.back(), Some(&5));
+        assert!(!list.is_empty());
+
+        // act & assert
+        list.pop();
+        list.pop();
+        list.pop();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
     }
 
     #[test]
-    fn test_clear_empty() {
+    fn test_into_iter_refs() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+        let mut list: unsafe_queue::List<i32> = (1..4).collect();
 
         // act
-        list.clear();
+        for value in &mut list {
+            *value *= 10;
+        }
+        let mut values = Vec::new();
+        for value in &list {
+            values.push(*value);
+        }
 
         // assert
-        assert_eq!(list.len(), 0);
-        assert!(list.front().is_none());
+        assert_eq!(values, vec![10, 20, 30]);
+        assert_eq!(list.into_iter().size_hint(), (3, Some(3)));
     }
 
     #[test]
-    fn test_clear() {
+    fn test_from_iter() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
-        list.push_back(1);
-        list.push_back(1);
-        list.push_back(1);
-        // make sure that the length changed
-        assert_eq!(list.len(), 3);
+        let mut list: unsafe_queue::List<i32> = (1..3).collect();
 
         // act
-        list.clear();
+        list.extend([3, 4]);
 
         // assert
-        assert_eq!(list.len(), 0);
-        assert!(list.back().is_none());
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
     }
 
     #[test]
-    fn test_is_empty() {
+    fn test_clone_eq() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
-        list.push_front(1);
+        let list: unsafe_queue::List<i32> = (1..4).collect();
 
         // act
-        let value = list.is_empty();
+        let mut other_list = list.clone();
 
         // assert
-        assert!(!value);
+        assert_eq!(list, other_list);
+        assert_eq!(other_list.pop(), Some(1));
+        assert_ne!(list, other_list);
+        assert_eq!(unsafe_queue::List::<i32>::default(), unsafe_queue::List::new());
+    }
 
-        // now clear the list
+    #[test]
+    fn test_debug_hash() {
         // arrange
-        list.clear();
+        let list: unsafe_queue::List<i32> = (1..4).collect();
+        let mut set = std::collections::HashSet::new();
 
-        // act
-        let value = list.is_empty();
+        // act & assert
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+        assert!(set.insert(list.clone()));
+        assert!(!set.insert(list));
+    }
 
-        // assert
-        assert!(value);
+    #[test]
+    fn test_bounded() {
+        // arrange
+        let mut list = unsafe_queue::List::bounded(2);
+
+        // act & assert
+        assert_eq!(list.capacity(), Some(2));
+        assert_eq!(list.try_push(1).map_err(|err| err.into_inner()), Ok(()));
+        assert!(!list.is_full());
+        assert_eq!(list.try_push(2).map_err(|err| err.into_inner()), Ok(()));
+        assert!(list.is_full());
+        assert_eq!(list.try_push(3).map_err(|err| err.into_inner()), Err(3));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.try_push(3).map_err(|err| err.into_inner()), Ok(()));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(list.clone().capacity(), Some(2));
+
+        // unbounded queues are never full
+        let mut list = unsafe_queue::List::new();
+        assert_eq!(list.capacity(), None);
+        for i in 0..100 {
+            assert_eq!(list.try_push(i).map_err(|err| err.into_inner()), Ok(()));
+        }
+        assert!(!list.is_full());
     }
 
     #[test]
-    fn test_into_iter() {
+    #[should_panic]
+    fn test_bounded_push_full() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
-        list.push_front(1);
-        list.push_front(2);
-        list.push_front(3);
+        let mut list = unsafe_queue::List::bounded(1);
+        list.push(1);
 
         // act
-        let mut iter = list.into_iter();
-
-        // assert
-        assert_eq!(iter.size_hint(), (3, Some(3)));
-        assert_eq!(iter.next(), Some(3));
-        assert_eq!(iter.next_back(), Some(1));
-        assert_eq!(iter.next(), Some(2));
-        assert_eq!(iter.next(), None);
+        list.push(2);
     }
 
     #[test]
-    fn test_iter() {
+    fn test_peek_back() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
-        list.push_back(1);
-        list.push_back(2);
-        list.push_back(3);
+        let mut list = unsafe_queue::List::new();
+        assert_eq!(list.peek_back(), None);
+        list.push(1);
+        list.push(2);
 
         // act
-        let mut iter = list.iter();
+        if let Some(elem) = list.peek_back_mut() {
+            *elem = 20;
+        }
 
         // assert
-        assert_eq!(iter.size_hint(), (3, Some(3)));
-        assert_eq!(iter.next_back(), Some(&3));
-        assert_eq!(iter.next(), Some(&1));
-        assert_eq!(iter.size_hint(), (1, Some(1)));
-        assert_eq!(iter.next(), Some(&2));
-        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(list.peek(), Some(&1));
+        assert_eq!(list.peek_back(), Some(&20));
+        list.pop();
+        assert_eq!(list.peek_back(), Some(&20));
+        list.pop();
+        assert_eq!(list.peek_back(), None);
     }
 
     #[test]
-    fn test_iter_empty() {
+    fn test_append() {
         // arrange
-        let list = doubly_linked_list::LinkedList::<i32>::new();
+        let mut list: unsafe_queue::List<i32> = (1..3).collect();
+        let mut other: unsafe_queue::List<i32> = (3..5).collect();
+        let mut empty = unsafe_queue::List::new();
 
         // act
-        let mut iter = list.iter();
+        list.append(&mut other);
+        list.append(&mut empty);
+        empty.append(&mut list);
 
         // assert
-        assert_eq!(iter.size_hint(), (0, Some(0)));
-        assert_eq!(iter.next(), None);
+        assert!(list.is_empty() && other.is_empty());
+        assert_eq!(list.peek_back(), None);
+        assert_eq!(empty.len(), 4);
+        assert_eq!(empty.peek_back(), Some(&4));
+        empty.push(5);
+        assert_eq!(empty.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        // the emptied lists are still usable
+        list.push(6);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![6]);
     }
 
     #[test]
-    fn test_iter_mut() {
+    #[should_panic]
+    fn test_append_bounded_full() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
-        list.push_back(1);
-        list.push_back(2);
-        list.push_back(3);
+        let mut list = unsafe_queue::List::bounded(2);
+        list.push(1);
+        let mut other: unsafe_queue::List<i32> = (2..4).collect();
 
         // act
-        let mut iter = list.iter_mut();
-
-        // assert
-        assert_eq!(iter.size_hint(), (3, Some(3)));
-        assert_eq!(iter.next_back(), Some(&mut 3));
-        assert_eq!(iter.size_hint(), (2, Some(2)));
-        assert_eq!(iter.next_back(), Some(&mut 2));
-        assert_eq!(iter.size_hint(), (1, Some(1)));
-        assert_eq!(iter.next_back(), Some(&mut 1));
-        assert_eq!(iter.size_hint(), (0, Some(0)));
+        list.append(&mut other);
     }
 
     #[test]
-    fn test_iter_mut_empty() {
+    fn test_into_iter_partial() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+        let drops = std::rc::Rc::new(std::cell::Cell::new(0));
+        struct DropCounter(std::rc::Rc<std::cell::Cell<usize>>);
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        let list: unsafe_queue::List<_> = (0..10).map(|_| DropCounter(drops.clone())).collect();
 
         // act
-        let mut iter = list.iter_mut();
+        let mut iter = list.into_iter();
+        assert_eq!(iter.len(), 10);
+        drop(iter.next());
+        drop(iter.next());
+        assert_eq!(iter.len(), 8);
+        drop(iter);
 
         // assert
-        assert_eq!(iter.size_hint(), (0, Some(0)));
-        assert_eq!(iter.next(), None);
+        assert_eq!(drops.get(), 10);
     }
 
     #[test]
-    fn test_rev_iter() {
+    fn test_drop_long() {
         // arrange
-        let mut list = doubly_linked_list::LinkedList::new();
-        list.push_front(1);
-        list.push_front(2);
-        list.push_front(3);
+        let mut list = unsafe_queue::List::new();
+        for i in 0..1_000_000 {
+            list.push(i);
+        }
+
+        // act & assert
+        drop(list);
+    }
+}
+
+#[cfg(test)]
+// the tests spell out both forms on purpose
+#[allow(clippy::bool_assert_comparison, clippy::option_map_unit_fn, clippy::useless_vec)]
+mod test_doubly_linked_list {
+    use super::*;
+
+    #[test]
+    fn test_pop_front_empty() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::<i32>::new();
 
         // act
-        let mut rev_iter = list.iter().rev();
+        let value = list.pop_front();
 
         // assert
-        assert_eq!(rev_iter.next(), Some(&1));
-        assert_eq!(rev_iter.next(), Some(&2));
-        assert_eq!(rev_iter.next(), Some(&3));
-        assert_eq!(rev_iter.next(), None);
+        assert!(value.is_none());
     }
 
     #[test]
-    fn test_rev_iter_mut() {
+    fn test_pop_front() {
         // arrange
         let mut list = doubly_linked_list::LinkedList::new();
         list.push_front(1);
@@ -1147,17 +1698,114 @@ mod test_doubly_linked_list {
         list.push_front(3);
 
         // act
-        let mut rev_iter = list.iter_mut().rev();
+        let value = list.pop_front();
 
         // assert
-        assert_eq!(rev_iter.next(), Some(&mut 1));
-        assert_eq!(rev_iter.next(), Some(&mut 2));
-        assert_eq!(rev_iter.next(), Some(&mut 3));
-        assert_eq!(rev_iter.next(), None);
+        assert_eq!(value.is_some(), true);
+        assert_eq!(value, Some(3));
+
+        // and the other pop's
+        // act & assert
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), None);
     }
 
     #[test]
-    fn test_rev_into_iter() {
+    fn test_push_front() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+
+        // act
+        list.push_front(1);
+
+        // assert
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop_front(), Some(1));
+        assert!(list.pop_front().is_none());
+    }
+
+    #[test]
+    fn test_len_empty() {
+        // arrange
+        let list = doubly_linked_list::LinkedList::<i32>::new();
+
+        // act
+        let len = list.len();
+
+        // assert
+        assert_eq!(len, 0);
+    }
+
+    #[test]
+    fn test_len() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+        list.push_front(1);
+        list.push_front(1);
+        list.push_front(1);
+
+        // act
+        let len = list.len();
+
+        // assert
+        assert_eq!(len, 3);
+
+        // and another one
+        // arrange
+        list.pop_front();
+
+        // act
+        let len = list.len();
+
+        // assert
+        assert_eq!(len, 2);
+
+        // and another one
+        // arrange
+        list.pop_front();
+
+        // act
+        let len = list.len();
+
+        // assert
+        assert_eq!(len, 1);
+
+        // and the last one
+        // arrange
+        list.pop_front();
+
+        // act
+        let len = list.len();
+
+        // assert
+        assert_eq!(len, 0);
+
+        // just for good measure; another one
+        // arrange
+        list.pop_back();
+
+        // act
+        let len = list.len();
+
+        // assert
+        assert_eq!(len, 0);
+    }
+
+    #[test]
+    fn test_pop_back_empty() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+
+        // act
+        let value = list.pop_back();
+
+        // assert
+        assert!(value.is_none());
+    }
+
+    #[test]
+    fn test_pop_back() {
         // arrange
         let mut list = doubly_linked_list::LinkedList::new();
         list.push_front(1);
@@ -1165,937 +1813,6529 @@ mod test_doubly_linked_list {
         list.push_front(3);
 
         // act
-        let mut rev_iter = list.into_iter().rev();
+        let value = list.pop_back();
 
         // assert
-        assert_eq!(rev_iter.next(), Some(1));
-        assert_eq!(rev_iter.next(), Some(2));
-        assert_eq!(rev_iter.next(), Some(3));
-        assert_eq!(rev_iter.next(), None);
+        assert_eq!(value.is_some(), true);
+        assert_eq!(value, Some(1));
+
+        // and the other pop's
+        // act & assert
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_back(), None);
+    }
+
+    #[test]
+    fn test_push_back() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+
+        // act
+        list.push_back(1);
+
+        // assert
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop_front(), Some(1));
+        assert!(list.pop_front().is_none());
+
+        // another push back with checking for correct order!
+        // act
+        list.push_back(1);
+        list.push_back(2);
+
+        // assert
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&2));
+    }
+
+    #[test]
+    fn test_front_empty() {
+        // arrange
+        let list = doubly_linked_list::LinkedList::<i32>::new();
+
+        // act
+        let value = list.front();
+
+        // assert
+        assert!(value.is_none());
+    }
+
+    #[test]
+    fn test_front() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+        list.push_front(1);
+        list.push_back(3);
+
+        // act
+        let value = list.front();
+
+        // assert
+        assert!(value.is_some());
+        assert_eq!(value, Some(&1));
+    }
+
+    #[test]
+    fn test_front_mut_empty() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+
+        // act
+        let value = list.front_mut();
+
+        // assert
+        assert!(value.is_none());
+    }
+
+    #[test]
+    fn test_front_mut() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+        list.push_front(1);
+        list.push_back(3);
+
+        // act
+        if let Some(value) = list.front_mut() {
+            *value = 5;
+        }
+        // same as above (not sure which I like more/better):
+        list.front_mut().map(|value| {
+            *value = 5;
+        });
+
+        // assert
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.back(), Some(&3));
+        assert_eq!(list.front(), Some(&5));
+    }
+
+    #[test]
+    fn test_back_empty() {
+        // arrange
+        let list = doubly_linked_list::LinkedList::<i32>::new();
+
+        // act
+        let value = list.back();
+
+        // assert
+        assert!(value.is_none());
+    }
+
+    #[test]
+    fn test_back() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+        list.push_front(1);
+        list.push_back(3);
+
+        // act
+        let value = list.back();
+
+        // assert
+        assert!(value.is_some());
+        assert_eq!(value, Some(&3));
+
+        // after poping an element
+        // arrange
+        list.pop_back();
+
+        // act
+        let value = list.back();
+
+        // assert
+        assert_eq!(list.len(), 1);
+        assert!(value.is_some());
+        assert_eq!(value, Some(&1));
+
+        // and the last one
+        // arrange
+        list.pop_front();
+
+        // act
+        let value = list.back();
+
+        // assert
+        assert_eq!(list.len(), 0);
+        assert!(value.is_none());
     }
 
-    // +---------------+
-    // | Trait testing |
-    // +---------------+
-    fn list_from<T: Clone>(v: &[T]) -> doubly_linked_list::LinkedList<T> {
-        v.iter().map(|x| (*x).clone()).collect()
+    #[test]
+    fn test_back_mut_empty() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+
+        // act
+        let value = list.back_mut();
+
+        // assert
+        assert!(value.is_none());
+    }
+
+    #[test]
+    fn test_back_mut() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+        list.push_front(1);
+        list.push_back(3);
+
+        // act
+        if let Some(value) = list.back_mut() {
+            *value = 5;
+        }
+        // or
+        list.back_mut().map(|value| {
+            *value = 5;
+        });
+
+        // assert
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&5));
+    }
+
+    #[test]
+    fn test_clear_empty() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+
+        // act
+        list.clear();
+
+        // assert
+        assert_eq!(list.len(), 0);
+        assert!(list.front().is_none());
+    }
+
+    #[test]
+    fn test_clear() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+        list.push_back(1);
+        list.push_back(1);
+        list.push_back(1);
+        // make sure that the length changed
+        assert_eq!(list.len(), 3);
+
+        // act
+        list.clear();
+
+        // assert
+        assert_eq!(list.len(), 0);
+        assert!(list.back().is_none());
+    }
+
+    #[test]
+    fn test_is_empty() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+        list.push_front(1);
+
+        // act
+        let value = list.is_empty();
+
+        // assert
+        assert!(!value);
+
+        // now clear the list
+        // arrange
+        list.clear();
+
+        // act
+        let value = list.is_empty();
+
+        // assert
+        assert!(value);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+        list.push_front(1);
+        list.push_front(2);
+        list.push_front(3);
+
+        // act
+        let mut iter = list.into_iter();
+
+        // assert
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next_back(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_iter() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        // act
+        let mut iter = list.iter();
+
+        // assert
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_iter_empty() {
+        // arrange
+        let list = doubly_linked_list::LinkedList::<i32>::new();
+
+        // act
+        let mut iter = list.iter();
+
+        // assert
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        // act
+        let mut iter = list.iter_mut();
+
+        // assert
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.next_back(), Some(&mut 3));
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.next_back(), Some(&mut 2));
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        assert_eq!(iter.next_back(), Some(&mut 1));
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_iter_mut_empty() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::<i32>::new();
+
+        // act
+        let mut iter = list.iter_mut();
+
+        // assert
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_rev_iter() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+        list.push_front(1);
+        list.push_front(2);
+        list.push_front(3);
+
+        // act
+        let mut rev_iter = list.iter().rev();
+
+        // assert
+        assert_eq!(rev_iter.next(), Some(&1));
+        assert_eq!(rev_iter.next(), Some(&2));
+        assert_eq!(rev_iter.next(), Some(&3));
+        assert_eq!(rev_iter.next(), None);
+    }
+
+    #[test]
+    fn test_rev_iter_mut() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+        list.push_front(1);
+        list.push_front(2);
+        list.push_front(3);
+
+        // act
+        let mut rev_iter = list.iter_mut().rev();
+
+        // assert
+        assert_eq!(rev_iter.next(), Some(&mut 1));
+        assert_eq!(rev_iter.next(), Some(&mut 2));
+        assert_eq!(rev_iter.next(), Some(&mut 3));
+        assert_eq!(rev_iter.next(), None);
+    }
+
+    #[test]
+    fn test_rev_into_iter() {
+        // arrange
+        let mut list = doubly_linked_list::LinkedList::new();
+        list.push_front(1);
+        list.push_front(2);
+        list.push_front(3);
+
+        // act
+        let mut rev_iter = list.into_iter().rev();
+
+        // assert
+        assert_eq!(rev_iter.next(), Some(1));
+        assert_eq!(rev_iter.next(), Some(2));
+        assert_eq!(rev_iter.next(), Some(3));
+        assert_eq!(rev_iter.next(), None);
+    }
+
+    // +---------------+
+    // | Trait testing |
+    // +---------------+
+    fn list_from<T: Clone>(v: &[T]) -> doubly_linked_list::LinkedList<T> {
+        v.iter().map(|x| (*x).clone()).collect()
+    }
+
+    #[test]
+    fn test_eq_empty() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = list_from(&[]);
+        let other_list = list_from(&[]);
+
+        // act & assert
+        assert_eq!(list, other_list);
+    }
+
+    #[test]
+    fn test_eq() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[]);
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        let other_list = list_from(&[1, 2, 3]);
+
+        // act & assert
+        assert_eq!(list, other_list);
+
+        // arrange
+        list.push_back(5);
+
+        // act & assert
+        assert_ne!(list, other_list);
+    }
+
+    #[test]
+    fn test_ord() {
+        // arrange
+        let list = list_from(&[]);
+        let other_list = list_from(&[1, 2, 3]);
+
+        // act & assert
+        assert!(list < other_list);
+        assert!(other_list >= list);
+    }
+
+    #[test]
+    fn test_debug() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = (0..10).collect();
+
+        // act
+        let debug_str = format!("{:?}", list);
+
+        // assert
+        assert_eq!(debug_str, "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]");
+
+        // and another list content except numbers, let's try str
+        // arrange
+        let list: doubly_linked_list::LinkedList<&str> = vec!["just", "one", "more", "test"]
+            .iter()
+            .copied()
+            .collect();
+
+        // act
+        let debug_str = format!("{:?}", list);
+
+        // assert
+        assert_eq!(debug_str, r#"["just", "one", "more", "test"]"#);
+    }
+
+    #[test]
+    fn test_hashmap() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = (0..10).collect();
+        let other_list: doubly_linked_list::LinkedList<i32> = (1..11).collect();
+        let mut map = std::collections::HashMap::new();
+
+        // act & assert
+        assert_eq!(map.insert(list.clone(), "list"), None); // correctly inserted
+        assert_eq!(map.insert(other_list.clone(), "other_list"), None); // correctly inserted
+
+        // check for correct length
+        assert_eq!(map.len(), 2);
+
+        // get key of corresponding value
+        assert_eq!(map.get(&list), Some(&("list")));
+        assert_eq!(map.get(&other_list), Some(&("other_list")));
+
+        // remove value and corresponding key
+        assert_eq!(map.remove(&list), Some("list"));
+        assert_eq!(map.remove(&other_list), Some("other_list"));
+
+        // the map should be empty after removing both keys we added before
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_cursor_index_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let idx = cursor.index();
+
+        // assert
+        assert!(idx.is_none());
+
+        // arrange
+        cursor.move_next();
+
+        // act
+        let idx = cursor.index();
+
+        // assert
+        assert!(idx.is_none());
+    }
+
+    #[test]
+    fn test_cursor_index() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let idx = cursor.index();
+
+        // assert
+        assert!(idx.is_none());
+
+        // arrange
+        cursor.move_next();
+
+        // act
+        let idx = cursor.index();
+
+        // assert
+        assert!(idx.is_some());
+        assert_eq!(idx, Some(0));
+
+        // arrange
+        cursor.move_prev();
+        cursor.move_prev();
+
+        // act
+        let idx = cursor.index();
+
+        // assert
+        assert!(idx.is_some());
+        assert_eq!(idx, Some(5));
+    }
+
+    #[test]
+    fn test_cursor_peek_next_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let ghost = cursor.peek_next();
+
+        // assert
+        assert!(ghost.is_none());
+
+        // arrange
+        cursor.move_next();
+
+        // act
+        let ghost = cursor.peek_next();
+
+        // assert
+        assert!(ghost.is_none());
+
+        // arrange
+        cursor.move_prev();
+        cursor.move_prev();
+
+        // act
+        let ghost = cursor.current();
+
+        // assert
+        assert!(ghost.is_none());
+    }
+
+    #[test]
+    fn test_cursor_peek_next() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let next = cursor.peek_next();
+
+        // assert
+        assert!(next.is_some());
+        assert_eq!(next, Some(&mut 1));
+
+        // arrange
+        cursor.move_prev();
+
+        // act
+        let ghost = cursor.peek_next();
+
+        // assert
+        assert!(ghost.is_none());
+    }
+
+    #[test]
+    fn test_cursor_peek_prev_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let ghost = cursor.peek_prev();
+
+        // assert
+        assert!(ghost.is_none());
+
+        // arrange
+        cursor.move_prev();
+
+        // act
+        let ghost = cursor.peek_prev();
+
+        // assert
+        assert!(ghost.is_none());
+
+        // arrange
+        cursor.move_prev();
+        cursor.move_prev();
+
+        // act
+        let ghost = cursor.current();
+
+        // assert
+        assert!(ghost.is_none());
+    }
+
+    #[test]
+    fn test_cursor_peek_prev() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let prev = cursor.peek_prev();
+
+        // assert
+        assert!(prev.is_some());
+        assert_eq!(prev, Some(&mut 6));
+
+        // arrange
+        cursor.move_next();
+
+        // act
+        let ghost = cursor.peek_prev();
+
+        // assert
+        assert!(ghost.is_none());
+    }
+
+    #[test]
+    fn test_cursor_current_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        let ghost = cursor.current();
+        assert!(ghost.is_none());
+
+        // act & assert
+        cursor.move_next();
+        let ghost = cursor.current();
+        assert!(ghost.is_none());
+
+        // act & assert
+        cursor.move_prev();
+        cursor.move_prev();
+        let ghost = cursor.current();
+        assert!(ghost.is_none());
+    }
+
+    #[test]
+    fn test_cursor_current() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        let ghost = cursor.current();
+        assert!(ghost.is_none());
+
+        // act & assert
+        cursor.move_next();
+        let first = cursor.current();
+        assert_eq!(first, Some(&mut 1));
+
+        // act & assert
+        cursor.move_next();
+        let second = cursor.current();
+        assert_eq!(second, Some(&mut 2));
+    }
+
+    #[test]
+    fn test_cursor_insert_after_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        cursor.insert_after(1);
+
+        // assert
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert!(cursor.current().is_none());
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn test_cursor_insert_after() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 3]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+
+        // act
+        cursor.insert_after(2);
+
+        // assert
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_cursor_insert_before_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        cursor.insert_before(1);
+
+        // assert
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert!(cursor.current().is_none());
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn test_cursor_insert_before() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 3]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(1));
+
+        // act
+        cursor.insert_before(2);
+
+        // assert
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_cursor_remove_after_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let value = cursor.remove_after();
+
+        // assert
+        assert!(value.is_none());
+    }
+
+    #[test]
+    fn test_cursor_remove_after_general() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 4, 2, 3]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+
+        // act
+        let value = cursor.remove_after();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_cursor_remove_after_start() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([4, 1, 2, 3]);
+        let mut cursor = list.cursor_mut();
+        assert_eq!(cursor.index(), None);
+
+        // act
+        let value = cursor.remove_after();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_cursor_remove_after_end() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3, 4]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+
+        // act
+        let value = cursor.remove_after();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_cursor_remove_after_single_element() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([4]);
+        let mut cursor = list.cursor_mut();
+        assert_eq!(cursor.index(), None);
+
+        // act
+        let value = cursor.remove_after();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert!(list.is_empty());
+        assert_eq!(list, doubly_linked_list::LinkedList::new());
+    }
+
+    #[test]
+    fn test_cursor_remove_before_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let value = cursor.remove_before();
+
+        // assert
+        assert!(value.is_none());
+    }
+
+    #[test]
+    fn test_cursor_remove_before_general() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 4, 3]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(3));
+
+        // act
+        let value = cursor.remove_before();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_cursor_remove_before_start() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([4, 1, 2, 3]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+
+        // act
+        let value = cursor.remove_before();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_cursor_remove_before_end() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3, 4]);
+        let mut cursor = list.cursor_mut();
+        assert_eq!(cursor.index(), None);
+
+        // act
+        let value = cursor.remove_before();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_cursor_remove_before_single_element() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([4]);
+        let mut cursor = list.cursor_mut();
+        assert_eq!(cursor.index(), None);
+
+        // act
+        let value = cursor.remove_before();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert!(list.is_empty());
+        assert_eq!(list, doubly_linked_list::LinkedList::new());
+    }
+
+    #[test]
+    fn test_cursor_split_before_ghost() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let split = cursor.split_before();
+
+        // assert
+        assert_eq!(split.len(), 6);
+        assert_eq!(split, (1..7).collect());
+        assert!(cursor.current().is_none());
+        assert!(cursor.index().is_none());
+
+        // after split the cursor has no list content anymore as it has been
+        // moved out to `split`
+        // arrange
+        cursor.move_next();
+
+        // assert
+        assert!(cursor.current().is_none());
+        assert!(cursor.index().is_none());
+    }
+
+    #[test]
+    fn test_cursor_split_before_general() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+
+        // act
+        let split = cursor.split_before();
+
+        // assert
+        assert_eq!(split.len(), 2);
+        assert_eq!(split, (1..3).collect());
+        // cursor is not on the ghost but on the head of the other split half
+        // that remains in the cursor
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.peek_next(), Some(&mut 4));
+        assert_eq!(cursor.peek_prev(), None);
+    }
+
+    #[test]
+    fn test_cursor_split_after_ghost() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let split = cursor.split_after();
+
+        // assert
+        assert_eq!(split.len(), 6);
+        assert_eq!(split, (1..7).collect());
+        assert!(cursor.current().is_none());
+        assert!(cursor.index().is_none());
+
+        // after split the cursor has no list content anymore as it has been
+        // moved out to `split`
+        // arrange
+        cursor.move_next();
+
+        // assert
+        assert!(cursor.current().is_none());
+        assert!(cursor.index().is_none());
+    }
+
+    #[test]
+    fn test_cursor_split_after_general() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+
+        // act
+        let split = cursor.split_after();
+
+        // assert
+        assert_eq!(split.len(), 3);
+        assert_eq!(split, (4..7).collect());
+        // cursor is not on the ghost but on the head of the other split half
+        // that remains in the cursor
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+        assert_eq!(cursor.peek_next(), None);
+    }
+
+    #[test]
+    fn test_cursor_splice_before_ghost_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let other_list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        cursor.splice_before(other_list);
+
+        // assert
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, list_from(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_cursor_splice_before_general_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let other_list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+
+        // act
+        cursor.splice_before(other_list);
+
+        // assert
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, list_from(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_cursor_splice_before_general_start() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> =
+            doubly_linked_list::LinkedList::new();
+        other_list.extend([4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+
+        // act
+        cursor.splice_before(other_list);
+
+        // assert
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, list_from(&[1, 4, 5, 6, 2, 3]));
+    }
+
+    #[test]
+    fn test_cursor_splice_before_general_end() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> =
+            doubly_linked_list::LinkedList::new();
+        other_list.extend([4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+
+        // act
+        cursor.splice_before(other_list);
+
+        // assert
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, list_from(&[1, 2, 4, 5, 6, 3]));
+    }
+
+    #[test]
+    fn test_cursor_splice_before_first() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> =
+            doubly_linked_list::LinkedList::new();
+        other_list.extend([4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+
+        // act
+        cursor.splice_before(other_list);
+
+        // assert
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, list_from(&[4, 5, 6, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_cursor_splice_before_ghost() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> =
+            doubly_linked_list::LinkedList::new();
+        other_list.extend([4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+
+        // act
+        cursor.splice_before(other_list);
+
+        // assert
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, (1..7).collect());
+    }
+
+    #[test]
+    fn test_cursor_splice_after_ghost_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let other_list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        cursor.splice_after(other_list);
+
+        // assert
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, list_from(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_cursor_splice_after_general_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let other_list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+
+        // act
+        cursor.splice_after(other_list);
+
+        // assert
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, list_from(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_cursor_splice_after_general_start() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> =
+            doubly_linked_list::LinkedList::new();
+        other_list.extend([4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+
+        // act
+        cursor.splice_after(other_list);
+
+        // assert
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, list_from(&[1, 2, 4, 5, 6, 3]));
+    }
+
+    #[test]
+    fn test_cursor_splice_after_general_end() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> =
+            doubly_linked_list::LinkedList::new();
+        other_list.extend([4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+
+        // act
+        cursor.splice_after(other_list);
+
+        // assert
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, (1..7).collect());
+    }
+
+    #[test]
+    fn test_cursor_splice_after_last() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> =
+            doubly_linked_list::LinkedList::new();
+        other_list.extend([4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+
+        // act
+        cursor.splice_after(other_list);
+
+        // assert
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, (1..7).collect());
+    }
+
+    #[test]
+    fn test_cursor_splice_after_ghost() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> =
+            doubly_linked_list::LinkedList::new();
+        other_list.extend([4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+
+        // act
+        cursor.splice_after(other_list);
+
+        // assert
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, list_from(&[4, 5, 6, 1, 2, 3]));
+    }
+
+    fn check_links<T: Eq + std::fmt::Debug>(list: &doubly_linked_list::LinkedList<T>) {
+        let from_front: Vec<_> = list.iter().collect();
+        let from_back: Vec<_> = list.iter().rev().collect();
+        let re_reved: Vec<_> = from_back.into_iter().rev().collect();
+
+        assert_eq!(from_front, re_reved);
+    }
+
+    #[test]
+    fn test_iter_splice_after() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> =
+            doubly_linked_list::LinkedList::new();
+        other_list.extend([4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+
+        // act
+        cursor.splice_after(other_list);
+
+        // assert
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_iter_splice_before() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        list.extend([1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> =
+            doubly_linked_list::LinkedList::new();
+        other_list.extend([4, 5, 6]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+
+        // act
+        cursor.splice_before(other_list);
+
+        // assert
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_read_cursor_index_empty() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor();
+
+        // act & assert
+        assert!(cursor.index().is_none());
+
+        // act & assert
+        cursor.move_next();
+        assert!(cursor.index().is_none());
+        assert!(cursor.current().is_none());
+
+        // act & assert
+        cursor.move_prev();
+        assert!(cursor.index().is_none());
+        assert!(cursor.current().is_none());
+    }
+
+    #[test]
+    fn test_read_cursor_move() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+        let mut cursor = list.cursor();
+
+        // act & assert
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&1));
+
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&3));
+
+        // walk onto the ghost
+        cursor.move_next();
+        assert!(cursor.index().is_none());
+        assert!(cursor.current().is_none());
+
+        // and wrap around to the back
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&3));
+    }
+
+    #[test]
+    fn test_read_cursor_peek() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+        let mut cursor = list.cursor();
+
+        // act & assert
+        assert_eq!(cursor.peek_next(), Some(&1));
+        assert_eq!(cursor.peek_prev(), Some(&3));
+
+        cursor.move_next();
+        assert_eq!(cursor.peek_next(), Some(&2));
+        assert_eq!(cursor.peek_prev(), None);
+
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), Some(&2));
+    }
+
+    #[test]
+    fn test_read_cursor_multiple_readers() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = (1..7).collect();
+        let mut front = list.cursor();
+        let mut back = list.cursor();
+
+        // act
+        front.move_next();
+        back.move_prev();
+        let copy = front;
+        front.move_next();
+
+        // assert
+        // references outlive the cursors as they borrow the list
+        let (first, second, last) = (copy.current(), front.current(), back.current());
+        assert_eq!(first, Some(&1));
+        assert_eq!(second, Some(&2));
+        assert_eq!(last, Some(&6));
+        assert_eq!(copy.index(), Some(0));
+        assert_eq!(back.index(), Some(5));
+    }
+
+    #[test]
+    fn test_cursor_as_cursor() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+
+        // act
+        let mut read = cursor.as_cursor();
+
+        // assert
+        assert_eq!(read.index(), Some(1));
+        assert_eq!(read.current(), Some(&2));
+        assert_eq!(read.peek_prev(), Some(&1));
+
+        // moving the read-only cursor does not move the mutable one
+        read.move_next();
+        assert_eq!(read.current(), Some(&3));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(1));
+    }
+
+    #[test]
+    fn test_cursor_insert_before_keeps_index() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 3]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+
+        // act
+        cursor.insert_before(2);
+
+        // assert
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_cursor_insert_ghost_non_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[2]);
+        let mut cursor = list.cursor_mut();
+
+        // act
+        cursor.insert_after(1);
+        cursor.insert_before(3);
+
+        // assert
+        assert!(cursor.current().is_none());
+        assert!(cursor.index().is_none());
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_cursor_remove_before_keeps_index() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 4, 2, 3]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(2));
+
+        // act
+        let value = cursor.remove_before();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(1));
+    }
+
+    #[test]
+    fn test_cursor_remove_current_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let value = cursor.remove_current();
+
+        // assert
+        assert!(value.is_none());
+        assert!(cursor.index().is_none());
+    }
+
+    #[test]
+    fn test_cursor_remove_current_ghost() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let value = cursor.remove_current();
+
+        // assert
+        assert!(value.is_none());
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn test_cursor_remove_current_general() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 4, 2, 3]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+
+        // act
+        let value = cursor.remove_current();
+
+        // assert
+        // the cursor moved onto the next element which took over the index
+        assert_eq!(value, Some(4));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_cursor_remove_current_start() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[4, 1, 2, 3]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+
+        // act
+        let value = cursor.remove_current();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(list, (1..4).collect());
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_cursor_remove_current_end() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 2, 3, 4]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_prev();
+
+        // act
+        let value = cursor.remove_current();
+
+        // assert
+        // we removed the back, so the cursor is on the ghost now
+        assert_eq!(value, Some(4));
+        assert!(cursor.current().is_none());
+        assert!(cursor.index().is_none());
+        assert_eq!(cursor.peek_prev(), Some(&mut 3));
+        assert_eq!(list.back(), Some(&3));
+        assert_eq!(list, (1..4).collect());
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_cursor_remove_current_single_element() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[4]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+
+        // act
+        let value = cursor.remove_current();
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert!(cursor.index().is_none());
+        assert!(list.is_empty());
+        assert_eq!(list, doubly_linked_list::LinkedList::new());
+    }
+
+    #[test]
+    fn test_cursor_remove_current_as_list() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 4, 2, 3]);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+
+        // act
+        let removed = cursor.remove_current_as_list();
+
+        // assert
+        assert_eq!(removed, Some(list_from(&[4])));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(list, (1..4).collect());
+
+        // arrange
+        let mut cursor = list.cursor_mut();
+
+        // act
+        let removed = cursor.remove_current_as_list();
+
+        // assert
+        assert!(removed.is_none());
+    }
+
+    #[test]
+    fn test_cursor_replace_current() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 4, 3]);
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        // nothing to replace on the ghost, the element is handed back
+        assert_eq!(cursor.replace_current(2), Err(2));
+
+        // act & assert
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.replace_current(2), Ok(4));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_cursor_swap_with_next() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[2, 1, 3]);
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        // the ghost cannot be swapped
+        assert!(!cursor.swap_with_next());
+
+        // act & assert
+        cursor.move_next();
+        assert!(cursor.swap_with_next());
+        // the cursor follows its element
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        assert_eq!(cursor.peek_next(), Some(&mut 3));
+
+        // act & assert
+        assert!(cursor.swap_with_next());
+        assert_eq!(cursor.index(), Some(2));
+        // the back of the list cannot be swapped with the ghost
+        assert!(!cursor.swap_with_next());
+        assert_eq!(list, list_from(&[1, 3, 2]));
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&2));
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_cursor_swap_with_prev() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 3, 2]);
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        // the ghost cannot be swapped
+        assert!(!cursor.swap_with_prev());
+
+        // act & assert
+        cursor.move_prev();
+        assert!(cursor.swap_with_prev());
+        // the cursor follows its element
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(1));
+
+        // act & assert
+        assert!(cursor.swap_with_prev());
+        assert_eq!(cursor.index(), Some(0));
+        // the front of the list cannot be swapped with the ghost
+        assert!(!cursor.swap_with_prev());
+        assert_eq!(list, list_from(&[2, 1, 3]));
+        assert_eq!(list.front(), Some(&2));
+        assert_eq!(list.back(), Some(&3));
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_handle_get() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[2, 3]);
+
+        // act
+        let front = list.push_front_handle(1);
+        let back = list.push_back_handle(4);
+
+        // assert
+        assert_eq!(list.get(&front), Some(&1));
+        assert_eq!(list.get(&back), Some(&4));
+        assert_eq!(list, (1..5).collect());
+        assert!(!front.is_stale());
+    }
+
+    #[test]
+    fn test_handle_get_mut() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 3]);
+        let handle = list.push_back_handle(5);
+
+        // act
+        if let Some(value) = list.get_mut(&handle) {
+            *value = 4;
+        }
+
+        // assert
+        assert_eq!(list.back(), Some(&4));
+    }
+
+    #[test]
+    fn test_handle_remove() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 2]);
+        let handle = list.push_back_handle(4);
+        list.push_back(3);
+
+        // act
+        let value = list.remove(&handle);
+
+        // assert
+        assert_eq!(value, Some(4));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+        check_links(&list);
+
+        // the handle is stale now and must not be used anymore
+        assert!(handle.is_stale());
+        assert_eq!(list.get(&handle), None);
+        assert_eq!(list.remove(&handle), None);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn test_handle_stale_after_pop() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let front = list.push_front_handle(1);
+        let back = list.push_back_handle(2);
+
+        // act
+        list.pop_front();
+        list.pop_back();
+
+        // assert
+        assert!(front.is_stale());
+        assert!(back.is_stale());
+        assert_eq!(list.get(&front), None);
+        assert_eq!(list.remove(&back), None);
+    }
+
+    #[test]
+    fn test_handle_stale_after_drop() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+        let handle = list.push_back_handle(4);
+
+        // act
+        drop(list);
+
+        // assert
+        assert!(handle.is_stale());
+    }
+
+    #[test]
+    fn test_handle_foreign_list() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+        let mut other_list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+        let handle = list.push_back_handle(4);
+
+        // act & assert
+        assert!(!other_list.contains_handle(&handle));
+        assert_eq!(other_list.get(&handle), None);
+        assert_eq!(other_list.remove(&handle), None);
+        assert!(other_list.cursor_at(&handle).is_none());
+        assert_eq!(other_list.len(), 3);
+        assert!(list.contains_handle(&handle));
+    }
+
+    #[test]
+    fn test_handle_follows_split() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let handles: Vec<_> = (1..7).map(|i| list.push_back_handle(i)).collect();
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+
+        // act
+        let mut split = cursor.split_after();
+
+        // assert
+        assert_eq!(list.get(&handles[2]), Some(&3));
+        assert_eq!(list.get(&handles[3]), None);
+        assert_eq!(split.get(&handles[3]), Some(&4));
+        assert_eq!(split.get(&handles[0]), None);
+        assert_eq!(split.remove(&handles[5]), Some(6));
+        assert_eq!(split, list_from(&[4, 5]));
+    }
+
+    #[test]
+    fn test_handle_follows_splice() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 5]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> = list_from(&[2, 4]);
+        let handle = other_list.push_back_handle(3);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+
+        // act
+        cursor.splice_after(other_list);
+
+        // assert
+        assert_eq!(list.get(&handle), Some(&3));
+        assert_eq!(list.remove(&handle), Some(3));
+        assert_eq!(list, list_from(&[1, 2, 4, 5]));
+    }
+
+    #[test]
+    fn test_handle_follows_splice_into_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut other_list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 2]);
+        let handle = other_list.push_back_handle(3);
+
+        // act
+        list.cursor_mut().splice_before(other_list);
+
+        // assert
+        assert_eq!(list.get(&handle), Some(&3));
+        assert_eq!(list, (1..4).collect());
+    }
+
+    #[test]
+    fn test_handle_follows_remove_current_as_list() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 2]);
+        let handle = list.push_back_handle(3);
+        let mut cursor = list.cursor_mut();
+        cursor.move_prev();
+
+        // act
+        let removed = cursor.remove_current_as_list().unwrap();
+
+        // assert
+        assert_eq!(list.get(&handle), None);
+        assert_eq!(removed.get(&handle), Some(&3));
+    }
+
+    #[test]
+    fn test_handle_cursor_at() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 2, 3]);
+        let handle = list.push_back_handle(5);
+
+        // act
+        let mut cursor = list.cursor_at(&handle).unwrap();
+
+        // assert
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.current(), Some(&mut 5));
+
+        // and the cursor is fully functional
+        cursor.insert_before(4);
+        assert_eq!(cursor.index(), Some(4));
+        assert_eq!(list, (1..6).collect());
+    }
+
+    #[test]
+    fn test_cursor_insert_handle() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[2]);
+        let mut cursor = list.cursor_mut();
+
+        // act
+        // on the ghost after is the front and before is the back
+        let first = cursor.insert_after_handle(1);
+        let last = cursor.insert_before_handle(4);
+        cursor.move_prev();
+        let third = cursor.insert_before_handle(3);
+
+        // assert
+        assert_eq!(list, (1..5).collect());
+        assert_eq!(list.get(&first), Some(&1));
+        assert_eq!(list.get(&third), Some(&3));
+        assert_eq!(list.get(&last), Some(&4));
+        assert_eq!(list.cursor_at(&third).unwrap().index(), Some(2));
+    }
+
+    #[test]
+    fn test_handle_eq() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let handle = list.push_back_handle(1);
+        // same value, but another node
+        let other = list.push_back_handle(1);
+
+        // act & assert
+        assert_ne!(handle, other);
+        assert_eq!(handle, handle.clone());
+
+        // act & assert
+        // a stale handle is still equal to its clones
+        let copy = handle.clone();
+        list.remove(&handle);
+        assert!(copy.is_stale());
+        assert_eq!(handle, copy);
+    }
+
+    #[test]
+    fn test_sort_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+
+        // act
+        list.sort();
+
+        // assert
+        assert!(list.is_empty());
+
+        // arrange
+        list.push_back(1);
+
+        // act
+        list.sort();
+
+        // assert
+        assert_eq!(list, list_from(&[1]));
+    }
+
+    #[test]
+    fn test_sort() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[5, 3, 9, 1, 2, 8, 7, 4, 6]);
+
+        // act
+        list.sort();
+
+        // assert
+        assert_eq!(list.len(), 9);
+        assert_eq!(list, (1..10).collect());
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&9));
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_sort_large() {
+        // arrange
+        // simple linear congruential generator for reproducible input
+        let mut seed: u32 = 42;
+        let mut values: Vec<u32> = (0..1000)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                seed % 100
+            })
+            .collect();
+        let mut list: doubly_linked_list::LinkedList<u32> = values.iter().copied().collect();
+
+        // act
+        list.sort();
+
+        // assert
+        values.sort();
+        assert_eq!(list, values.iter().copied().collect());
+        assert!(list.iter().rev().eq(values.iter().rev()));
+    }
+
+    #[test]
+    fn test_sort_by_key_stable() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<(i32, char)> =
+            list_from(&[(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e'), (2, 'f')]);
+
+        // act
+        list.sort_by_key(|(key, _)| *key);
+
+        // assert
+        assert_eq!(
+            list,
+            list_from(&[(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c'), (2, 'f')])
+        );
+    }
+
+    #[test]
+    fn test_sort_unstable_by() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..8).collect();
+
+        // act
+        list.sort_unstable_by(|a, b| b.cmp(a));
+
+        // assert
+        assert_eq!(list, (1..8).rev().collect());
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_sort_keeps_nodes() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[3, 1]);
+        let handle = list.push_back_handle(2);
+
+        // act
+        list.sort();
+
+        // assert
+        // no element was moved, so the handle is still valid
+        assert_eq!(list.get(&handle), Some(&2));
+        assert_eq!(list.cursor_at(&handle).unwrap().index(), Some(1));
+    }
+
+    #[test]
+    fn test_sort_panic() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (0..20).rev().collect();
+        let mut comparisons = 0;
+
+        // act
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.sort_by(|a, b| {
+                comparisons += 1;
+                if comparisons == 25 {
+                    panic!("comparison failed");
+                }
+                a.cmp(b)
+            })
+        }));
+
+        // assert
+        // the list is still consistent and contains all elements
+        assert!(result.is_err());
+        assert_eq!(list.len(), 20);
+        check_links(&list);
+        let mut values: Vec<_> = list.iter().copied().collect();
+        values.sort();
+        assert_eq!(values, (0..20).collect::<Vec<_>>());
+        assert_eq!(list.iter().rev().count(), 20);
+    }
+
+    #[test]
+    fn test_merge() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 3, 5, 7]);
+        let other_list: doubly_linked_list::LinkedList<i32> = list_from(&[2, 3, 4, 8, 9]);
+
+        // act
+        list.merge(other_list, |a, b| a.cmp(b));
+
+        // assert
+        assert_eq!(list.len(), 9);
+        assert_eq!(list, list_from(&[1, 2, 3, 3, 4, 5, 7, 8, 9]));
+        assert_eq!(list.back(), Some(&9));
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_merge_stable() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<(i32, char)> = list_from(&[(1, 'a'), (2, 'a')]);
+        let other_list = list_from(&[(1, 'b'), (2, 'b')]);
+
+        // act
+        list.merge(other_list, |a, b| a.0.cmp(&b.0));
+
+        // assert
+        assert_eq!(list, list_from(&[(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]));
+    }
+
+    #[test]
+    fn test_merge_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut other_list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 2]);
+        let handle = other_list.push_back_handle(3);
+
+        // act
+        list.merge(other_list, |a, b| a.cmp(b));
+        list.merge(doubly_linked_list::LinkedList::new(), |a, b| a.cmp(b));
+
+        // assert
+        assert_eq!(list, (1..4).collect());
+        assert_eq!(list.get(&handle), Some(&3));
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_append() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 2, 3]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> = list_from(&[4, 5]);
+        let handle = other_list.push_back_handle(6);
+
+        // act
+        list.append(&mut other_list);
+
+        // assert
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, (1..7).collect());
+        assert_eq!(list.back(), Some(&6));
+        assert_eq!(list.get(&handle), Some(&6));
+        assert!(other_list.is_empty());
+        check_links(&list);
+
+        // the emptied list is still usable
+        other_list.push_back(7);
+        list.append(&mut other_list);
+        assert_eq!(list, (1..8).collect());
+    }
+
+    #[test]
+    fn test_append_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+        let mut other_list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 2]);
+
+        // act
+        list.append(&mut doubly_linked_list::LinkedList::new());
+        list.append(&mut other_list);
+        list.append(&mut doubly_linked_list::LinkedList::new());
+
+        // assert
+        assert_eq!(list, list_from(&[1, 2]));
+        assert!(other_list.is_empty());
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_prepend() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[4, 5, 6]);
+        let mut other_list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 2, 3]);
+
+        // act
+        list.prepend(&mut other_list);
+
+        // assert
+        assert_eq!(list.len(), 6);
+        assert_eq!(list, (1..7).collect());
+        assert_eq!(list.front(), Some(&1));
+        assert!(other_list.is_empty());
+        check_links(&list);
+
+        // act & assert
+        let mut empty = doubly_linked_list::LinkedList::new();
+        empty.prepend(&mut list);
+        assert_eq!(empty, (1..7).collect());
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_split_off() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..11).collect();
+
+        // act
+        // closer to the front
+        let split = list.split_off(2);
+
+        // assert
+        assert_eq!(list, list_from(&[1, 2]));
+        assert_eq!(split, (3..11).collect());
+        assert_eq!(split.len(), 8);
+
+        // arrange
+        let mut list = split;
+
+        // act
+        // closer to the back
+        let split = list.split_off(6);
+
+        // assert
+        assert_eq!(list, (3..9).collect());
+        assert_eq!(list.back(), Some(&8));
+        assert_eq!(split, list_from(&[9, 10]));
+        check_links(&list);
+        check_links(&split);
+    }
+
+    #[test]
+    fn test_split_off_bounds() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+
+        // act & assert
+        let split = list.split_off(3);
+        assert!(split.is_empty());
+        assert_eq!(list, (1..4).collect());
+
+        // act & assert
+        let split = list.split_off(0);
+        assert!(list.is_empty());
+        assert_eq!(split, (1..4).collect());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_off_out_of_bounds() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+
+        // act
+        list.split_off(4);
+    }
+
+    #[test]
+    fn test_retain() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..11).collect();
+
+        // act
+        list.retain(|x| x % 2 == 0);
+
+        // assert
+        assert_eq!(list.len(), 5);
+        assert_eq!(list, list_from(&[2, 4, 6, 8, 10]));
+        assert_eq!(list.front(), Some(&2));
+        assert_eq!(list.back(), Some(&10));
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_retain_none() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..11).collect();
+
+        // act
+        list.retain(|_| false);
+
+        // assert
+        assert!(list.is_empty());
+        assert_eq!(list.front(), None);
+        assert_eq!(list.back(), None);
+
+        // act & assert
+        list.retain(|_| true);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_retain_mut() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..7).collect();
+
+        // act
+        list.retain_mut(|x| {
+            *x *= 10;
+            *x > 30
+        });
+
+        // assert
+        assert_eq!(list, list_from(&[40, 50, 60]));
+    }
+
+    #[test]
+    fn test_retain_panic() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..11).collect();
+
+        // act
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.retain(|x| {
+                if *x == 6 {
+                    panic!("predicate failed");
+                }
+                x % 2 == 0
+            })
+        }));
+
+        // assert
+        // everything in front of the panic has been filtered, the rest is
+        // untouched
+        assert!(result.is_err());
+        assert_eq!(list.len(), 7);
+        assert_eq!(list, list_from(&[2, 4, 6, 7, 8, 9, 10]));
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_extract_if() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..11).collect();
+
+        // act
+        let extracted: Vec<_> = list.extract_if(|x| *x % 3 == 0).collect();
+
+        // assert
+        assert_eq!(extracted, vec![3, 6, 9]);
+        assert_eq!(list.len(), 7);
+        assert_eq!(list, list_from(&[1, 2, 4, 5, 7, 8, 10]));
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_extract_if_lazy() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..11).collect();
+
+        // act
+        let (first, second) = {
+            let mut iter = list.extract_if(|x| *x % 2 == 1);
+            (iter.next(), iter.next())
+        };
+
+        // assert
+        // only the visited elements have been removed
+        assert_eq!(first, Some(1));
+        assert_eq!(second, Some(3));
+        assert_eq!(list, list_from(&[2, 4, 5, 6, 7, 8, 9, 10]));
+    }
+
+    #[test]
+    fn test_extract_if_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+
+        // act
+        let mut iter = list.extract_if(|_| true);
+
+        // assert
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_drain() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..11).collect();
+
+        // act
+        let drained: Vec<_> = list.drain(2..5).collect();
+
+        // assert
+        assert_eq!(drained, vec![3, 4, 5]);
+        assert_eq!(list.len(), 7);
+        assert_eq!(list, list_from(&[1, 2, 6, 7, 8, 9, 10]));
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_drain_ranges() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..11).collect();
+
+        // act & assert
+        assert_eq!(list.drain(8..).collect::<Vec<_>>(), vec![9, 10]);
+        assert_eq!(list.drain(..=1).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(list.drain(3..3).count(), 0);
+        assert_eq!(list, (3..9).collect());
+
+        // act & assert
+        // the elements are removed even if the iterator is not used
+        drop(list.drain(1..5));
+        assert_eq!(list, list_from(&[3, 8]));
+
+        // act & assert
+        assert_eq!(list.drain(..).rev().collect::<Vec<_>>(), vec![8, 3]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_drain_out_of_bounds() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+
+        // act
+        list.drain(2..4);
+    }
+
+    #[test]
+    fn test_get_index() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = (0..10).collect();
+
+        // act & assert
+        // from the front and from the back half
+        assert_eq!(list.get(0), Some(&0));
+        assert_eq!(list.get(3), Some(&3));
+        assert_eq!(list.get(7), Some(&7));
+        assert_eq!(list.get(9), Some(&9));
+        assert_eq!(list.get(10), None);
+    }
+
+    #[test]
+    fn test_get_index_empty() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+
+        // act & assert
+        assert_eq!(list.get(0), None);
+    }
+
+    #[test]
+    fn test_get_mut_index() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 5, 3]);
+
+        // act
+        if let Some(value) = list.get_mut(1) {
+            *value = 2;
+        }
+
+        // assert
+        assert_eq!(list, (1..4).collect());
+        assert_eq!(list.get_mut(3), None);
+    }
+
+    #[test]
+    fn test_index() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (0..5).collect();
+
+        // act
+        list[4] = 10;
+
+        // assert
+        assert_eq!(list[0], 0);
+        assert_eq!(list[4], 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = (0..5).collect();
+
+        // act
+        let _ = list[5];
+    }
+
+    #[test]
+    fn test_insert_index() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[2, 4]);
+
+        // act
+        list.insert(0, 1);
+        list.insert(2, 3);
+        list.insert(4, 5);
+
+        // assert
+        assert_eq!(list.len(), 5);
+        assert_eq!(list, (1..6).collect());
+        check_links(&list);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_index_out_of_bounds() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (0..5).collect();
+
+        // act
+        list.insert(6, 6);
+    }
+
+    #[test]
+    fn test_remove_index() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[0, 1, 5, 2, 3, 6]);
+
+        // act & assert
+        assert_eq!(list.remove(2), Some(5));
+        assert_eq!(list.remove(4), Some(6));
+        assert_eq!(list.remove(0), Some(0));
+        assert_eq!(list.remove(3), None);
+
+        // assert
+        assert_eq!(list.len(), 3);
+        assert_eq!(list, (1..4).collect());
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_swap_index() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[5, 2, 3, 4, 1]);
+        let handle = list.push_back_handle(6);
+
+        // act & assert
+        // the ends of the list
+        list.swap(0, 4);
+        assert_eq!(list, (1..7).collect());
+
+        // act & assert
+        // neighbours in both directions
+        list.swap(1, 2);
+        list.swap(2, 1);
+        assert_eq!(list, (1..7).collect());
+
+        // act & assert
+        // the handle follows its element
+        list.swap(5, 2);
+        assert_eq!(list, list_from(&[1, 2, 6, 4, 5, 3]));
+        assert_eq!(list.cursor_at(&handle).unwrap().index(), Some(2));
+        assert_eq!(list.back(), Some(&3));
+
+        // act & assert
+        list.swap(3, 3);
+        assert_eq!(list, list_from(&[1, 2, 6, 4, 5, 3]));
+        check_links(&list);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_swap_index_out_of_bounds() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (0..5).collect();
+
+        // act
+        list.swap(0, 5);
+    }
+
+    #[test]
+    fn test_cursor_at_index() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (0..10).collect();
+
+        // act
+        let mut cursor = list.cursor_at(7).unwrap();
+
+        // assert
+        assert_eq!(cursor.index(), Some(7));
+        assert_eq!(cursor.current(), Some(&mut 7));
+        assert_eq!(cursor.peek_prev(), Some(&mut 6));
+        assert!(list.cursor_at(10).is_none());
+    }
+
+    #[test]
+    fn test_contains() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = (1..4).collect();
+
+        // act & assert
+        assert!(list.contains(&1));
+        assert!(list.contains(&3));
+        assert!(!list.contains(&4));
+        assert!(!doubly_linked_list::LinkedList::new().contains(&1));
+    }
+
+    #[test]
+    fn test_position() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<i32> = list_from(&[1, 2, 3, 2, 1]);
+
+        // act & assert
+        assert_eq!(list.position(|x| *x == 2), Some(1));
+        assert_eq!(list.rposition(|x| *x == 2), Some(3));
+        assert_eq!(list.rposition(|x| *x == 1), Some(4));
+        assert_eq!(list.position(|x| *x == 4), None);
+        assert_eq!(list.rposition(|x| *x == 4), None);
+    }
+
+    #[test]
+    fn test_find() {
+        // arrange
+        let list: doubly_linked_list::LinkedList<(i32, char)> =
+            list_from(&[(1, 'a'), (2, 'b'), (2, 'c')]);
+
+        // act & assert
+        assert_eq!(list.find(|(x, _)| *x == 2), Some(&(2, 'b')));
+        assert_eq!(list.find(|(x, _)| *x == 3), None);
+    }
+
+    #[test]
+    fn test_dedup() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> =
+            list_from(&[1, 1, 2, 3, 3, 3, 1, 4, 4]);
+
+        // act
+        list.dedup();
+
+        // assert
+        assert_eq!(list.len(), 5);
+        assert_eq!(list, list_from(&[1, 2, 3, 1, 4]));
+        assert_eq!(list.back(), Some(&4));
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_dedup_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+
+        // act
+        list.dedup();
+
+        // assert
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_dedup_by_key() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[10, 11, 20, 21, 22, 30]);
+
+        // act
+        list.dedup_by_key(|x| *x / 10);
+
+        // assert
+        // the first element of every run is kept
+        assert_eq!(list, list_from(&[10, 20, 30]));
+    }
+
+    #[test]
+    fn test_dedup_by() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<(char, i32)> =
+            list_from(&[('a', 1), ('a', 2), ('b', 1), ('a', 1), ('a', 1), ('a', 3)]);
+
+        // act
+        // accumulate the counts of a run into the kept element
+        list.dedup_by(|next, kept| {
+            if next.0 == kept.0 {
+                kept.1 += next.1;
+                true
+            } else {
+                false
+            }
+        });
+
+        // assert
+        assert_eq!(list, list_from(&[('a', 3), ('b', 1), ('a', 5)]));
+    }
+
+    #[test]
+    fn test_reverse() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (1..7).collect();
+        let handle = list.push_back_handle(7);
+
+        // act
+        list.reverse();
+
+        // assert
+        assert_eq!(list, (1..8).rev().collect());
+        assert_eq!(list.front(), Some(&7));
+        assert_eq!(list.back(), Some(&1));
+        assert_eq!(list.cursor_at(&handle).unwrap().index(), Some(0));
+        check_links(&list);
+
+        // and the list still works as usual
+        list.push_back(0);
+        assert_eq!(list.pop_front(), Some(7));
+        assert_eq!(list, (0..7).rev().collect());
+    }
+
+    #[test]
+    fn test_reverse_empty() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
+
+        // act
+        list.reverse();
+
+        // assert
+        assert!(list.is_empty());
+
+        // arrange
+        list.push_back(1);
+
+        // act
+        list.reverse();
+
+        // assert
+        assert_eq!(list, list_from(&[1]));
+    }
+
+    #[test]
+    fn test_rotate_left() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (0..10).collect();
+
+        // act
+        list.rotate_left(3);
+
+        // assert
+        assert_eq!(list, (3..10).chain(0..3).collect());
+        assert_eq!(list.front(), Some(&3));
+        assert_eq!(list.back(), Some(&2));
+        check_links(&list);
+
+        // act & assert
+        list.rotate_left(0);
+        list.rotate_left(10);
+        assert_eq!(list, (3..10).chain(0..3).collect());
+    }
+
+    #[test]
+    fn test_rotate_right() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (0..10).collect();
+
+        // act
+        list.rotate_right(3);
+
+        // assert
+        assert_eq!(list, (7..10).chain(0..7).collect());
+        check_links(&list);
+
+        // act & assert
+        list.rotate_left(3);
+        assert_eq!(list, (0..10).collect());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rotate_out_of_bounds() {
+        // arrange
+        let mut list: doubly_linked_list::LinkedList<i32> = (0..3).collect();
+
+        // act
+        list.rotate_right(4);
+    }
+}
+
+// Shared test suites for the traits of this module. Each implementor gets its
+// own module with all checks of the suite, so adding a new implementation to
+// the list of a suite is all that is required to test it.
+#[cfg(test)]
+mod test_unrolled_linked_list {
+    use super::*;
+    use std::collections::VecDeque;
+
+    type List<T> = unrolled_linked_list::UnrolledList<T, 4>;
+
+    fn check_links<T: Eq + std::fmt::Debug, const N: usize>(
+        list: &unrolled_linked_list::UnrolledList<T, N>,
+    ) {
+        let from_front: Vec<_> = list.iter().collect();
+        let from_back: Vec<_> = list.iter().rev().collect();
+        let re_reved: Vec<_> = from_back.into_iter().rev().collect();
+
+        assert_eq!(from_front, re_reved);
+        assert_eq!(from_front.len(), list.len());
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        // arrange
+        let mut list = List::new();
+
+        // act
+        for i in 0..10 {
+            list.push_back(i);
+            list.push_front(-i - 1);
+        }
+
+        // assert
+        check_links(&list);
+        assert_eq!(list.len(), 20);
+        assert_eq!(list.front(), Some(&-10));
+        assert_eq!(list.back(), Some(&9));
+        // pushing at the ends fills the nodes completely
+        assert_eq!(list.node_count(), 5);
+        for i in (0..10).rev() {
+            assert_eq!(list.pop_back(), Some(i));
+            assert_eq!(list.pop_front(), Some(-i - 1));
+        }
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.node_count(), 0);
+    }
+
+    #[test]
+    fn test_front_back_mut() {
+        // arrange
+        let mut list: List<i32> = (1..6).collect();
+
+        // act
+        *list.front_mut().unwrap() = 10;
+        *list.back_mut().unwrap() = 50;
+
+        // assert
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 2, 3, 4, 50]);
+    }
+
+    #[test]
+    fn test_iter() {
+        // arrange
+        let mut list: List<i32> = (0..10).collect();
+
+        // act
+        for elem in list.iter_mut() {
+            *elem *= 2;
+        }
+        let mut iter = list.iter();
+
+        // assert
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&18));
+        assert_eq!(iter.len(), 8);
+        assert_eq!(iter.copied().collect::<Vec<_>>(), vec![2, 4, 6, 8, 10, 12, 14, 16]);
+        assert_eq!(list.iter_mut().rev().count(), 10);
+        assert_eq!(
+            list.into_iter().rev().collect::<Vec<_>>(),
+            vec![18, 16, 14, 12, 10, 8, 6, 4, 2, 0]
+        );
+    }
+
+    #[test]
+    fn test_cursor_move() {
+        // arrange
+        let mut list: List<i32> = (0..6).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 0));
+        assert_eq!(cursor.peek_prev(), Some(&mut 5));
+        for i in 0..6 {
+            cursor.move_next();
+            assert_eq!(cursor.index(), Some(i));
+            assert_eq!(cursor.current(), Some(&mut (i as i32)));
+        }
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(5));
+        cursor.move_prev();
+        assert_eq!(cursor.peek_next(), Some(&mut 5));
+        assert_eq!(cursor.peek_prev(), Some(&mut 3));
+    }
+
+    #[test]
+    fn test_cursor_insert_splits() {
+        // arrange
+        let mut list: List<i32> = (0..8).collect();
+        assert_eq!(list.node_count(), 2);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+
+        // act
+        cursor.insert_after(10);
+        cursor.insert_before(20);
+
+        // assert
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_next(), Some(&mut 10));
+        assert_eq!(cursor.peek_prev(), Some(&mut 20));
+        check_links(&list);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![0, 20, 1, 10, 2, 3, 4, 5, 6, 7]
+        );
+        assert_eq!(list.node_count(), 3);
+    }
+
+    #[test]
+    fn test_cursor_insert_at_ghost() {
+        // arrange
+        let mut list = List::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        cursor.insert_after(2);
+        cursor.insert_after(1);
+        cursor.insert_before(3);
+
+        // assert
+        assert_eq!(cursor.index(), None);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_cursor_remove_merges() {
+        // arrange
+        let mut list: List<i32> = (0..12).collect();
+        assert_eq!(list.node_count(), 3);
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+
+        // act
+        // emptying the first node merges it with its neighbours
+        assert_eq!(cursor.remove_current(), Some(0));
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.remove_current(), Some(2));
+
+        // assert
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 4));
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), (3..12).collect::<Vec<_>>());
+        assert_eq!(list.node_count(), 3);
+
+        // removing the last element moves onto the ghost
+        let mut cursor = list.cursor_mut();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(11));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        check_links(&list);
+    }
+
+    #[test]
+    fn test_cursor_against_model() {
+        // arrange
+        let mut list = unrolled_linked_list::UnrolledList::<usize, 5>::new();
+        let mut model = VecDeque::new();
+        let mut seed: u64 = 7;
+        let mut random = move |bound: usize| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
+
+        // act & assert
+        for i in 0..2_000 {
+            let at = random(model.len() + 1);
+            let mut cursor = list.cursor_mut();
+            for _ in 0..=at {
+                cursor.move_next();
+            }
+            match random(4) {
+                0 => {
+                    cursor.insert_before(i);
+                    model.insert(at.min(model.len()), i);
+                },
+                1 => {
+                    cursor.insert_after(i);
+                    if at < model.len() {
+                        model.insert(at + 1, i);
+                    } else {
+                        model.push_front(i);
+                    }
+                },
+                _ => {
+                    let removed = cursor.remove_current();
+                    assert_eq!(removed, model.remove(at));
+                    if at < model.len() {
+                        assert_eq!(cursor.current(), Some(&mut model[at]));
+                        assert_eq!(cursor.index(), Some(at));
+                    }
+                },
+            }
+            check_links(&list);
+            assert!(list.iter().eq(model.iter()));
+            // no two neighbouring nodes could be merged
+            assert!(list.node_count() <= 2 * list.len() / 5 + 1);
+        }
+    }
+
+    #[test]
+    fn test_drop_elements() {
+        // arrange
+        let drops = std::rc::Rc::new(std::cell::Cell::new(0));
+        struct DropCounter(std::rc::Rc<std::cell::Cell<usize>>);
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        let mut list: List<_> = (0..10).map(|_| DropCounter(drops.clone())).collect();
+
+        // act
+        drop(list.pop_front());
+        let mut iter = list.into_iter();
+        drop(iter.next_back());
+        drop(iter);
+
+        // assert
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    fn test_traits() {
+        // arrange
+        let list: List<i32> = (1..8).collect();
+        let mut other = List::new();
+        other.extend([1, 2, 3]);
+        other.extend(4..8);
+        let mut set = std::collections::HashSet::new();
+
+        // act & assert
+        assert_eq!(list, other);
+        assert_eq!(list.clone(), list);
+        assert!(list < List::from_iter([1, 3]));
+        assert_eq!(format!("{:?}", list), "[1, 2, 3, 4, 5, 6, 7]");
+        assert!(set.insert(list));
+        assert!(!set.insert(other.clone()));
+        other.clear();
+        assert!(other.is_empty());
+        assert_eq!(other, List::default());
+    }
+
+    #[test]
+    fn test_cursor_insert_into_full_node() {
+        for at in 0..4 {
+            for before in [true, false] {
+                // arrange
+                let mut list: List<i32> = (0..4).collect();
+                assert_eq!(list.node_count(), 1);
+                let mut cursor = list.cursor_mut();
+                for _ in 0..=at {
+                    cursor.move_next();
+                }
+
+                // act
+                if before {
+                    cursor.insert_before(10);
+                } else {
+                    cursor.insert_after(10);
+                }
+
+                // assert
+                let index = if before { at + 1 } else { at };
+                assert_eq!(cursor.index(), Some(index));
+                assert_eq!(cursor.current(), Some(&mut (at as i32)));
+                let mut expected: Vec<i32> = (0..4).collect();
+                expected.insert(if before { at } else { at + 1 }, 10);
+                check_links(&list);
+                assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+                assert_eq!(list.node_count(), 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pop_merges_nodes() {
+        // arrange
+        let mut list: List<i32> = (0..8).collect();
+        // splits the front node into [0, 100, 1] and [2, 3]
+        list.insert(1, 100);
+        assert_eq!(list.node_count(), 3);
+
+        // act
+        list.pop_front();
+        list.pop_front();
+
+        // assert
+        // [1] and [2, 3] fit into a single node
+        assert_eq!(list.node_count(), 2);
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), (1..8).collect::<Vec<_>>());
+
+        // arrange
+        let mut list: List<i32> = (0..8).collect();
+        // splits the back node into [4, 5] and [6, 100, 7]
+        list.insert(7, 100);
+        assert_eq!(list.node_count(), 3);
+
+        // act
+        list.pop_back();
+        list.pop_back();
+
+        // assert
+        assert_eq!(list.node_count(), 2);
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), (0..7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_get_insert_remove() {
+        // arrange
+        let mut list: List<i32> = (0..10).collect();
+
+        // act & assert
+        for i in 0..10 {
+            assert_eq!(list.get(i), Some(&(i as i32)));
+            assert_eq!(list[i], i as i32);
+        }
+        assert_eq!(list.get(10), None);
+        *list.get_mut(9).unwrap() = 90;
+        list[0] = -1;
+        list.insert(0, -2);
+        list.insert(5, 100);
+        list.insert(12, 200);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![-2, -1, 1, 2, 3, 100, 4, 5, 6, 7, 8, 90, 200]
+        );
+        assert_eq!(list.remove(5), Some(100));
+        assert_eq!(list.remove(11), Some(200));
+        assert_eq!(list.remove(11), None);
+        assert_eq!(list.remove(0), Some(-2));
+        check_links(&list);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![-1, 1, 2, 3, 4, 5, 6, 7, 8, 90]
+        );
+        let mut cursor = list.cursor_at(3).unwrap();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_out_of_bounds() {
+        // arrange
+        let mut list: List<i32> = (0..3).collect();
+
+        // act
+        list.insert(4, 4);
+    }
+
+    #[test]
+    fn test_append_prepend_split_off() {
+        // arrange
+        let mut list: List<i32> = (3..6).collect();
+        let mut back: List<i32> = (6..12).collect();
+        let mut front: List<i32> = (0..3).collect();
+
+        // act
+        list.append(&mut back);
+        list.prepend(&mut front);
+
+        // assert
+        assert!(back.is_empty());
+        assert!(front.is_empty());
+        check_links(&list);
+        assert_eq!(list.len(), 12);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), (0..12).collect::<Vec<_>>());
+
+        // act
+        let mut tail = list.split_off(5);
+        let rest = tail.split_off(7);
+        let all = list.split_off(0);
+
+        // assert
+        assert!(list.is_empty());
+        check_links(&all);
+        check_links(&tail);
+        assert_eq!(all.iter().copied().collect::<Vec<_>>(), (0..5).collect::<Vec<_>>());
+        assert_eq!(tail.iter().copied().collect::<Vec<_>>(), (5..12).collect::<Vec<_>>());
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_retain_dedup_search() {
+        // arrange
+        let mut list: List<i32> = (0..20).collect();
+        let mut dups: List<i32> = [1, 1, 2, 2, 2, 3, 1, 1, 4, 4, 4, 4, 4, 5].into_iter().collect();
+
+        // act
+        list.retain(|elem| elem % 3 != 0);
+        list.retain_mut(|elem| {
+            *elem *= 2;
+            *elem != 10
+        });
+        dups.dedup();
+
+        // assert
+        check_links(&list);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![2, 4, 8, 14, 16, 20, 22, 26, 28, 32, 34, 38]
+        );
+        check_links(&dups);
+        assert_eq!(dups.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 1, 4, 5]);
+        dups.dedup_by_key(|elem| *elem / 2);
+        assert_eq!(dups.iter().copied().collect::<Vec<_>>(), vec![1, 2, 1, 4]);
+        assert!(list.contains(&38));
+        assert!(!list.contains(&10));
+        assert_eq!(list.position(|elem| *elem > 10), Some(3));
+        assert_eq!(list.rposition(|elem| *elem < 10), Some(2));
+        assert_eq!(list.find(|elem| *elem % 7 == 0), Some(&14));
+    }
+
+    #[test]
+    fn test_reverse_sort() {
+        // arrange
+        let mut list: List<i32> = (0..10).collect();
+        list.insert(3, 100);
+        let mut pairs: List<(i32, usize)> = [3, 1, 2, 1, 3, 2, 1]
+            .into_iter()
+            .enumerate()
+            .map(|(i, key)| (key, i))
+            .collect();
+
+        // act
+        list.reverse();
+        pairs.sort_by_key(|pair| pair.0);
+
+        // assert
+        check_links(&list);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![9, 8, 7, 6, 5, 4, 3, 100, 2, 1, 0]
+        );
+        list.sort();
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 100]
+        );
+        // the sort is stable
+        assert_eq!(
+            pairs.iter().map(|pair| pair.1).collect::<Vec<_>>(),
+            vec![1, 3, 6, 2, 5, 0, 4]
+        );
+    }
+
+    #[test]
+    fn test_read_cursor() {
+        // arrange
+        let mut list: List<i32> = (0..6).collect();
+        let mut front = list.cursor();
+        let mut back = list.cursor();
+
+        // act
+        front.move_next();
+        back.move_prev();
+        let copy = front;
+        front.move_next();
+
+        // assert
+        assert_eq!(copy.current(), Some(&0));
+        assert_eq!(front.current(), Some(&1));
+        assert_eq!(front.peek_prev(), Some(&0));
+        assert_eq!(back.current(), Some(&5));
+        assert_eq!(back.peek_next(), None);
+        assert_eq!(back.index(), Some(5));
+        back.move_next();
+        assert_eq!(back.index(), None);
+        assert_eq!(back.peek_next(), Some(&0));
+
+        // act
+        let mut cursor = list.cursor_mut();
+        cursor.move_prev();
+        cursor.move_prev();
+        let read = cursor.as_cursor();
+
+        // assert
+        assert_eq!(read.index(), Some(4));
+        assert_eq!(read.current(), Some(&4));
+        assert_eq!(read.peek_next(), Some(&5));
+    }
+
+    #[test]
+    fn test_cursor_remove_after_before() {
+        // arrange
+        let mut list: List<i32> = (0..8).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        // on the ghost the front is after and the back before us
+        assert_eq!(cursor.remove_after(), Some(0));
+        assert_eq!(cursor.remove_before(), Some(7));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.remove_before(), Some(2));
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.remove_after(), Some(4));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.remove_before(), Some(1));
+        assert_eq!(cursor.remove_before(), None);
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_after(), None);
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 5, 6]);
+    }
+
+    #[test]
+    fn test_cursor_split_splice() {
+        // arrange
+        let mut list: List<i32> = (0..10).collect();
+        let mut cursor = list.cursor_mut();
+        for _ in 0..6 {
+            cursor.move_next();
+        }
+
+        // act
+        let before = cursor.split_before();
+        let after = cursor.split_after();
+
+        // assert
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 5));
+        check_links(&before);
+        check_links(&after);
+        assert_eq!(before.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(after.iter().copied().collect::<Vec<_>>(), vec![6, 7, 8, 9]);
+
+        // act
+        cursor.splice_after(after);
+        cursor.splice_before(before);
+
+        // assert
+        assert_eq!(cursor.index(), Some(5));
+        assert_eq!(cursor.current(), Some(&mut 5));
+        while cursor.index().is_some() {
+            cursor.move_next();
+        }
+        // on the ghost the whole list is split off
+        let all = cursor.split_after();
+        assert!(list.is_empty());
+        check_links(&all);
+        assert_eq!(all.iter().copied().collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_cursor_split_splice_against_model() {
+        // arrange
+        let mut list = unrolled_linked_list::UnrolledList::<usize, 5>::new();
+        let mut model = VecDeque::new();
+        let mut seed: u64 = 11;
+        let mut random = move |bound: usize| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
+
+        // act & assert
+        for i in 0..1_000 {
+            let at = random(model.len() + 1);
+            let input_len = random(12);
+            let input: unrolled_linked_list::UnrolledList<usize, 5> =
+                (0..input_len).map(|j| i * 100 + j).collect();
+            let mut cursor = list.cursor_mut();
+            for _ in 0..=at {
+                cursor.move_next();
+            }
+            let on_ghost = at == model.len();
+            match random(6) {
+                0 => {
+                    let before = cursor.split_before();
+                    let split: Vec<_> = if on_ghost {
+                        model.drain(..).collect()
+                    } else {
+                        model.drain(..at).collect()
+                    };
+                    assert!(before.iter().eq(split.iter()));
+                    check_links(&before);
+                },
+                1 => {
+                    let after = cursor.split_after();
+                    let split: Vec<_> = if on_ghost {
+                        model.drain(..).collect()
+                    } else {
+                        model.drain(at + 1..).collect()
+                    };
+                    assert!(after.iter().eq(split.iter()));
+                    check_links(&after);
+                },
+                2 => {
+                    cursor.splice_before(input);
+                    let at = if on_ghost { model.len() } else { at };
+                    for j in (0..input_len).rev() {
+                        model.insert(at, i * 100 + j);
+                    }
+                },
+                3 => {
+                    cursor.splice_after(input);
+                    let at = if on_ghost { 0 } else { at + 1 };
+                    for j in (0..input_len).rev() {
+                        model.insert(at, i * 100 + j);
+                    }
+                },
+                4 => {
+                    let removed = cursor.remove_after();
+                    let at = if on_ghost { 0 } else { at + 1 };
+                    assert_eq!(removed, model.remove(at));
+                },
+                _ => {
+                    let removed = cursor.remove_before();
+                    let at = if on_ghost { model.len().checked_sub(1) } else { at.checked_sub(1) };
+                    assert_eq!(removed, at.and_then(|at| model.remove(at)));
+                },
+            }
+            // the cursor stays on its element
+            let index = cursor.index();
+            let current = cursor.current().copied();
+            match index {
+                Some(index) => assert_eq!(current, Some(model[index])),
+                None => assert_eq!(current, None),
+            }
+            check_links(&list);
+            assert!(list.iter().eq(model.iter()));
+        }
+    }
+
+    #[test]
+    fn test_try_push() {
+        // arrange
+        let mut list = List::new();
+
+        // act
+        list.try_extend(1..9).unwrap();
+        list.try_push_front(0).unwrap();
+        list.try_push_back(9).unwrap();
+        let mut cursor = list.cursor_at(2).unwrap();
+        // the node of the cursor is full, so both of these split it
+        cursor.try_insert_after(20).unwrap();
+        cursor.try_insert_before(10).unwrap();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        list.try_insert(5, 30).unwrap();
+        list.try_insert(list.len(), 40).unwrap();
+
+        // assert
+        check_links(&list);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 10, 2, 20, 30, 3, 4, 5, 6, 7, 8, 9, 40]
+        );
+    }
+
+    #[test]
+    fn test_sort_panic_keeps_the_list() {
+        // arrange
+        let unsorted: Vec<String> = (0..10).rev().map(|i| i.to_string()).collect();
+        let mut list: List<String> = unsorted.iter().cloned().collect();
+
+        // act
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut compares = 0;
+            list.sort_by(|a, b| {
+                compares += 1;
+                assert!(compares < 5, "bad compare");
+                a.cmp(b)
+            })
+        }));
+
+        // assert
+        assert!(result.is_err());
+        check_links(&list);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), unsorted);
+        list.sort();
+        let sorted: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), sorted);
+    }
+}
+
+#[cfg(test)]
+mod test_skip_list {
+    use super::skip_list::{SkipList, SkipSet};
+    use std::collections::BTreeMap;
+    use std::ops::Bound;
+
+    #[test]
+    fn test_insert_get_remove() {
+        // arrange
+        let mut list = SkipList::new();
+
+        // act
+        for i in [5, 1, 9, 3, 7] {
+            assert_eq!(list.insert(i, i * 10), None);
+        }
+        let replaced = list.insert(3, 33);
+
+        // assert
+        assert_eq!(replaced, Some(30));
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.get(&3), Some(&33));
+        assert_eq!(list.get(&4), None);
+        *list.get_mut(&9).unwrap() += 1;
+        assert_eq!(list.get_key_value(&9), Some((&9, &91)));
+        assert!(list.contains_key(&1));
+        assert_eq!(list.remove(&1), Some(10));
+        assert_eq!(list.remove(&1), None);
+        assert!(!list.contains_key(&1));
+        assert_eq!(list.keys().copied().collect::<Vec<_>>(), vec![3, 5, 7, 9]);
+        assert_eq!(list.values().copied().collect::<Vec<_>>(), vec![33, 50, 70, 91]);
+    }
+
+    #[test]
+    fn test_first_last_pop() {
+        // arrange
+        let mut list: SkipList<_, _> = (0..10).map(|i| (i, -i)).collect();
+
+        // act & assert
+        assert_eq!(list.first(), Some((&0, &0)));
+        assert_eq!(list.last(), Some((&9, &-9)));
+        assert_eq!(list.pop_first(), Some((0, 0)));
+        assert_eq!(list.pop_last(), Some((9, -9)));
+        assert_eq!(list.first(), Some((&1, &-1)));
+        assert_eq!(list.last(), Some((&8, &-8)));
+        while list.pop_last().is_some() {}
+        assert!(list.is_empty());
+        assert_eq!(list.height(), 0);
+        assert_eq!(list.first(), None);
+        assert_eq!(list.pop_first(), None);
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        // arrange
+        let mut list = SkipList::new();
+        list.insert(String::from("b"), 2);
+        list.insert(String::from("a"), 1);
+
+        // act & assert
+        assert_eq!(list.get("a"), Some(&1));
+        assert_eq!(list.range::<str, _>((Bound::Included("b"), Bound::Unbounded)).count(), 1);
+        assert_eq!(list.remove("b"), Some(2));
+    }
+
+    #[test]
+    fn test_range() {
+        // arrange
+        let mut list: SkipList<_, _> = (0..20).map(|i| (i * 2, i)).collect();
+
+        // act & assert
+        let keys = |range: super::skip_list::Range<i32, i32>| {
+            range.map(|(k, _)| *k).collect::<Vec<_>>()
+        };
+        assert_eq!(keys(list.range(3..9)), vec![4, 6, 8]);
+        assert_eq!(keys(list.range(4..=8)), vec![4, 6, 8]);
+        assert_eq!(keys(list.range(..3)), vec![0, 2]);
+        assert_eq!(keys(list.range(35..)), vec![36, 38]);
+        assert_eq!(keys(list.range((Bound::Excluded(4), Bound::Excluded(10)))), vec![6, 8]);
+        assert_eq!(keys(list.range(5..6)), vec![]);
+        assert_eq!(keys(list.range(50..)), vec![]);
+        assert_eq!(keys(list.range(..)).len(), 20);
+        assert_eq!(list.range(3..9).rev().map(|(k, _)| *k).collect::<Vec<_>>(), vec![8, 6, 4]);
+
+        let mut range = list.range(0..=6);
+        assert_eq!(range.next(), Some((&0, &0)));
+        assert_eq!(range.next_back(), Some((&6, &3)));
+        assert_eq!(range.next_back(), Some((&4, &2)));
+        assert_eq!(range.next(), Some((&2, &1)));
+        assert_eq!(range.next(), None);
+        assert_eq!(range.next_back(), None);
+
+        for (_, value) in list.range_mut(10..14) {
+            *value = 0;
+        }
+        assert_eq!(list.get(&10), Some(&0));
+        assert_eq!(list.get(&12), Some(&0));
+        assert_eq!(list.get(&14), Some(&7));
+    }
+
+    #[test]
+    fn test_iter() {
+        // arrange
+        let mut list: SkipList<_, _> = [(3, 'c'), (1, 'a'), (2, 'b')].into_iter().collect();
+
+        // act
+        for (_, value) in &mut list {
+            *value = value.to_ascii_uppercase();
+        }
+
+        // assert
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some((&3, &'C')));
+        assert_eq!(iter.next(), Some((&1, &'A')));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![(1, 'A'), (2, 'B'), (3, 'C')]);
+    }
+
+    #[test]
+    fn test_cursor() {
+        // arrange
+        let mut list: SkipList<_, _> = (0..10).map(|i| (i, i)).collect();
+
+        // act & assert
+        let mut cursor = list.cursor_mut();
+        assert_eq!(cursor.key(), None);
+        assert_eq!(cursor.peek_next(), Some((&0, &mut 0)));
+        assert_eq!(cursor.peek_prev(), Some((&9, &mut 9)));
+        cursor.move_prev();
+        assert_eq!(cursor.key(), Some(&9));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.key(), Some(&0));
+
+        let mut cursor = list.lower_bound_mut(Bound::Excluded(&4));
+        assert_eq!(cursor.key(), Some(&5));
+        *cursor.current().unwrap().1 = 50;
+        assert_eq!(cursor.remove_current(), Some((5, 50)));
+        assert_eq!(cursor.key(), Some(&6));
+        assert_eq!(cursor.peek_prev(), Some((&4, &mut 4)));
+        // removes all remaining even keys
+        while cursor.key().is_some() {
+            if cursor.key().unwrap() % 2 == 0 {
+                cursor.remove_current();
+            } else {
+                cursor.move_next();
+            }
+        }
+        assert_eq!(list.keys().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 7, 9]);
+        assert_eq!(list.lower_bound_mut(Bound::Included(&10)).key(), None);
+        assert_eq!(list.lower_bound_mut(Bound::Unbounded).key(), Some(&0));
+    }
+
+    #[test]
+    fn test_same_seed_same_shape() {
+        // arrange
+        let mut seed = 7u64;
+        let keys: Vec<usize> = (0..200)
+            .map(|_| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (seed >> 33) as usize % 1000
+            })
+            .collect();
+
+        // act
+        let mut first = SkipList::with_seed(42);
+        let mut second = SkipList::with_seed(42);
+        let mut heights = (Vec::new(), Vec::new());
+        for key in &keys {
+            first.insert(*key, ());
+            second.insert(*key, ());
+            heights.0.push(first.height());
+            heights.1.push(second.height());
+        }
+
+        // assert
+        assert_eq!(heights.0, heights.1);
+        // about log2(200) levels
+        assert!((4..=16).contains(&first.height()));
+    }
+
+    #[test]
+    fn test_against_btree_map() {
+        // arrange
+        let mut list = SkipList::with_seed(3);
+        let mut model = BTreeMap::new();
+        let mut seed = 11u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
+
+        // act & assert
+        for i in 0..2000 {
+            let key = next(100);
+            match next(6) {
+                0 | 1 => assert_eq!(list.insert(key, i), model.insert(key, i)),
+                2 => assert_eq!(list.remove(&key), model.remove(&key)),
+                3 => assert_eq!(list.pop_first(), model.pop_first()),
+                4 => assert_eq!(list.pop_last(), model.pop_last()),
+                _ => {
+                    let end = key + next(20);
+                    assert!(list.range(key..end).eq(model.range(key..end)));
+                    assert!(list.range(key..end).rev().eq(model.range(key..end).rev()));
+                },
+            }
+            assert_eq!(list.len(), model.len());
+            assert_eq!(list.get(&key), model.get(&key));
+        }
+        assert!(list.iter().eq(model.iter()));
+        assert!(list.iter().rev().eq(model.iter().rev()));
+    }
+
+    #[test]
+    fn test_drop_elements() {
+        // arrange
+        let drops = std::rc::Rc::new(std::cell::Cell::new(0));
+        struct DropCounter(std::rc::Rc<std::cell::Cell<usize>>);
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        let mut list: SkipList<_, _> = (0..10).map(|i| (i, DropCounter(drops.clone()))).collect();
+
+        // act
+        drop(list.insert(3, DropCounter(drops.clone())));
+        drop(list.remove(&5));
+        drop(list.pop_first());
+        let mut iter = list.into_iter();
+        drop(iter.next());
+        drop(iter);
+
+        // assert
+        assert_eq!(drops.get(), 11);
+    }
+
+    #[test]
+    fn test_traits() {
+        // arrange
+        let list: SkipList<_, _> = (1..4).map(|i| (i, i)).collect();
+        let mut other = SkipList::default();
+        other.extend([(3, 3), (2, 2), (1, 1)]);
+        let mut set = std::collections::HashSet::new();
+
+        // act & assert
+        assert_eq!(list, other);
+        assert_eq!(list.clone(), list);
+        assert!(list < SkipList::from_iter([(1, 1), (3, 0)]));
+        assert_eq!(format!("{:?}", list), "{1: 1, 2: 2, 3: 3}");
+        assert!(set.insert(list));
+        assert!(!set.insert(other.clone()));
+        other.clear();
+        assert!(other.is_empty());
+        assert_eq!(other, SkipList::new());
+    }
+
+    #[test]
+    fn test_set() {
+        // arrange
+        let mut set = SkipSet::with_seed(5);
+
+        // act
+        for key in [4, 2, 8, 6, 2] {
+            set.insert(key);
+        }
+
+        // assert
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(&6));
+        assert!(!set.insert(6));
+        assert!(set.remove(&6));
+        assert!(!set.remove(&6));
+        assert_eq!(set.get(&4), Some(&4));
+        assert_eq!(set.take(&4), Some(4));
+        assert_eq!(set.first(), Some(&2));
+        assert_eq!(set.last(), Some(&8));
+        assert_eq!(set.range(3..).copied().collect::<Vec<_>>(), vec![8]);
+        assert_eq!(format!("{:?}", set), "{2, 8}");
+        assert_eq!(set.clone(), SkipSet::from_iter([8, 2]));
+        assert_eq!(set.iter().rev().copied().collect::<Vec<_>>(), vec![8, 2]);
+        assert_eq!(set.pop_last(), Some(8));
+        assert_eq!(set.pop_first(), Some(2));
+        assert!(set.is_empty());
+    }
+
+    // claims to be smaller than everything, even itself
+    #[derive(Debug, PartialEq, Eq)]
+    struct AlwaysLess(i32);
+
+    impl PartialOrd for AlwaysLess {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for AlwaysLess {
+        fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+            std::cmp::Ordering::Less
+        }
+    }
+
+    // answers at random, but consistently for the same pair of keys
+    #[derive(Debug, PartialEq, Eq)]
+    struct Scrambled(u64);
+
+    impl PartialOrd for Scrambled {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Scrambled {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            let mixed = (self.0 ^ other.0.rotate_left(17)).wrapping_mul(6364136223846793005);
+            (mixed >> 62).cmp(&1)
+        }
+    }
+
+    #[test]
+    fn test_bad_ord_stays_memory_safe() {
+        // arrange
+        let mut list = SkipList::new();
+        for i in 0..50 {
+            list.insert(AlwaysLess(i), i);
+        }
+
+        // act
+        let last = list.pop_last();
+        let first = list.pop_first();
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        let removed = cursor.remove_current();
+        let missing = list.remove(&AlwaysLess(20));
+
+        // assert
+        // nothing can be found by key, but the entries are all still there
+        assert!(last.is_some());
+        assert!(first.is_some());
+        assert!(removed.is_some());
+        assert_eq!(missing, None);
+        assert_eq!(list.len(), 47);
+        assert_eq!(list.iter().count(), 47);
+        while list.pop_last().is_some() {}
+        assert!(list.is_empty());
+        assert_eq!(list.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_scrambled_ord_stays_memory_safe() {
+        // arrange
+        let mut list = SkipList::new();
+        for i in 0..200 {
+            list.insert(Scrambled(i), i);
+        }
+        let len = list.len();
+
+        // act
+        let mut removed = 0;
+        for i in 0..200 {
+            removed += list.remove(&Scrambled(i)).is_some() as usize;
+            if i % 3 == 0 {
+                removed += list.pop_last().is_some() as usize;
+            }
+            if i % 5 == 0 {
+                let mut cursor = list.cursor_mut();
+                cursor.move_next();
+                removed += cursor.remove_current().is_some() as usize;
+            }
+        }
+
+        // assert
+        assert_eq!(list.len(), len - removed);
+        assert_eq!(list.iter().count(), list.len());
+        assert_eq!(list.iter().rev().count(), list.len());
+    }
+}
+
+#[cfg(test)]
+mod test_xor_linked_list {
+    use super::xor_linked_list::XorLinkedList;
+    use std::collections::VecDeque;
+
+    fn check_links<T: Eq + std::fmt::Debug>(list: &XorLinkedList<T>) {
+        let from_front: Vec<_> = list.iter().collect();
+        let from_back: Vec<_> = list.iter().rev().collect();
+        let re_reved: Vec<_> = from_back.into_iter().rev().collect();
+
+        assert_eq!(from_front, re_reved);
+        assert_eq!(from_front.len(), list.len());
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        // arrange
+        let mut list = XorLinkedList::new();
+
+        // act
+        for i in 0..5 {
+            list.push_back(i);
+            list.push_front(-i - 1);
+        }
+
+        // assert
+        check_links(&list);
+        assert_eq!(list.front(), Some(&-5));
+        assert_eq!(list.back(), Some(&4));
+        *list.front_mut().unwrap() *= 10;
+        *list.back_mut().unwrap() *= 10;
+        assert_eq!(list.pop_front(), Some(-50));
+        assert_eq!(list.pop_back(), Some(40));
+        assert_eq!(list.len(), 8);
+        check_links(&list);
+        while list.pop_back().is_some() {}
+        assert!(list.is_empty());
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.front(), None);
+    }
+
+    #[test]
+    fn test_reverse() {
+        // arrange
+        let mut list: XorLinkedList<_> = (0..5).collect();
+
+        // act
+        list.reverse();
+        list.push_back(-1);
+
+        // assert
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1, 0, -1]);
+    }
+
+    #[test]
+    fn test_iterators() {
+        // arrange
+        let mut list: XorLinkedList<_> = (0..6).collect();
+
+        // act
+        for elem in list.iter_mut().rev().take(2) {
+            *elem *= 10;
+        }
+
+        // assert
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&50));
+        assert_eq!(iter.next_back(), Some(&40));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3]);
+        let mut into_iter = list.into_iter();
+        assert_eq!(into_iter.next_back(), Some(50));
+        assert_eq!(into_iter.collect::<Vec<_>>(), vec![0, 1, 2, 3, 40]);
+    }
+
+    #[test]
+    fn test_cursor_walk() {
+        // arrange
+        let mut list: XorLinkedList<_> = (0..3).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 0));
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 0));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(2));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+    }
+
+    #[test]
+    fn test_cursor_against_vec_deque() {
+        // arrange
+        let mut list = XorLinkedList::new();
+        let mut model = VecDeque::new();
+        let mut seed = 5u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
+
+        // act & assert
+        for i in 0..300 {
+            let mut cursor = list.cursor_mut();
+            let steps = next(model.len() + 1);
+            for _ in 0..steps {
+                cursor.move_next();
+            }
+            // the ghost when all steps went to the elements
+            let at = steps.checked_sub(1);
+            assert_eq!(cursor.index(), at);
+            match next(4) {
+                0 => {
+                    cursor.insert_after(i);
+                    model.insert(at.map_or(0, |at| at + 1), i);
+                },
+                1 => {
+                    cursor.insert_before(i);
+                    model.insert(at.unwrap_or(model.len()), i);
+                    assert_eq!(cursor.index(), at.map(|at| at + 1));
+                },
+                2 => {
+                    list.reverse();
+                    model = model.into_iter().rev().collect();
+                },
+                _ => {
+                    let removed = cursor.remove_current();
+                    assert_eq!(removed, at.and_then(|at| model.remove(at)));
+                    if let Some(at) = at.filter(|at| *at < model.len()) {
+                        assert_eq!(cursor.current(), Some(&mut model[at]));
+                        assert_eq!(cursor.index(), Some(at));
+                    } else {
+                        assert_eq!(cursor.current(), None);
+                    }
+                },
+            }
+            check_links(&list);
+            assert!(list.iter().eq(model.iter()));
+        }
+    }
+
+    #[test]
+    fn test_drop_elements() {
+        // arrange
+        let drops = std::rc::Rc::new(std::cell::Cell::new(0));
+        struct DropCounter(std::rc::Rc<std::cell::Cell<usize>>);
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        let mut list: XorLinkedList<_> = (0..10).map(|_| DropCounter(drops.clone())).collect();
+
+        // act
+        drop(list.pop_back());
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        drop(cursor.remove_current());
+        drop(list);
+
+        // assert
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    fn test_traits() {
+        // arrange
+        let list: XorLinkedList<i32> = (1..8).collect();
+        let mut other = XorLinkedList::new();
+        other.extend([1, 2, 3]);
+        other.extend(4..8);
+        let mut set = std::collections::HashSet::new();
+
+        // act & assert
+        assert_eq!(list, other);
+        assert_eq!(list.clone(), list);
+        assert!(list < XorLinkedList::from_iter([1, 3]));
+        assert_eq!(format!("{:?}", list), "[1, 2, 3, 4, 5, 6, 7]");
+        assert!(set.insert(list));
+        assert!(!set.insert(other.clone()));
+        other.clear();
+        assert!(other.is_empty());
+        assert_eq!(other, XorLinkedList::default());
+    }
+
+    #[test]
+    fn test_try_push() {
+        // arrange
+        let mut list = XorLinkedList::new();
+
+        // act
+        list.try_push_back(1).unwrap();
+        list.try_push_front(0).unwrap();
+        list.try_extend([3, 4]).unwrap();
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.try_insert_after(2).unwrap();
+        cursor.try_insert_before(-1).unwrap();
+
+        // assert
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, -1, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_append_prepend_split_off() {
+        // arrange
+        let mut list: XorLinkedList<_> = (2..4).collect();
+        let mut back: XorLinkedList<_> = (4..6).collect();
+        let mut front: XorLinkedList<_> = (0..2).collect();
+
+        // act
+        list.append(&mut back);
+        list.prepend(&mut front);
+        list.append(&mut XorLinkedList::new());
+        let tail = list.split_off(4);
+        let rest = list.split_off(0);
+
+        // assert
+        assert!(back.is_empty() && front.is_empty() && list.is_empty());
+        check_links(&list);
+        check_links(&rest);
+        check_links(&tail);
+        assert_eq!(rest.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(tail.iter().copied().collect::<Vec<_>>(), vec![4, 5]);
+        let mut list = rest;
+        list.reverse();
+        let mut tail = list.split_off(3);
+        list.prepend(&mut tail);
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 3, 2, 1]);
+        assert!(list.split_off(4).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_off_out_of_bounds() {
+        let mut list: XorLinkedList<_> = (0..3).collect();
+        list.split_off(4);
+    }
+
+    #[test]
+    fn test_cursor_remove_split_splice() {
+        // arrange
+        let mut list: XorLinkedList<_> = (0..6).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        assert_eq!(cursor.remove_after(), Some(0));
+        assert_eq!(cursor.remove_before(), Some(5));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.remove_before(), Some(1));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.remove_before(), None);
+        assert_eq!(cursor.remove_after(), Some(3));
+        cursor.splice_before((10..12).collect());
+        cursor.splice_after((20..22).collect());
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.as_cursor().peek_prev(), Some(&11));
+        let after = cursor.split_after();
+        let before = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.remove_current_as_list().map(|list| list.len()), Some(1));
+        assert_eq!(cursor.current(), None);
+
+        assert!(list.is_empty());
+        check_links(&after);
+        check_links(&before);
+        assert_eq!(after.iter().copied().collect::<Vec<_>>(), vec![20, 21, 4]);
+        assert_eq!(before.iter().copied().collect::<Vec<_>>(), vec![10, 11]);
+    }
+
+    #[test]
+    fn test_cursor_on_the_ghost() {
+        // arrange
+        let mut list: XorLinkedList<_> = (0..3).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        cursor.splice_after((10..12).collect());
+        cursor.splice_before((20..22).collect());
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_prev(), Some(&mut 21));
+        let all = cursor.split_before();
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.split_after().len(), 0);
+        cursor.splice_before(all);
+        assert_eq!(cursor.remove_before(), Some(21));
+        assert_eq!(cursor.remove_after(), Some(10));
+        let all = cursor.split_after();
+        assert_eq!(cursor.remove_after(), None);
+        assert_eq!(cursor.remove_before(), None);
+        assert_eq!(cursor.remove_current_as_list(), None);
+        cursor.splice_after(all);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 20));
+
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![11, 0, 1, 2, 20]);
+    }
+
+    #[test]
+    fn test_read_only_cursor() {
+        // arrange
+        let list: XorLinkedList<_> = (0..3).collect();
+        let mut first = list.cursor();
+
+        // act
+        first.move_prev();
+        let mut second = first;
+        second.move_next();
+        second.move_next();
+
+        // assert
+        assert_eq!(first.current(), Some(&2));
+        assert_eq!(first.index(), Some(2));
+        assert_eq!(first.peek_next(), None);
+        assert_eq!(first.peek_prev(), Some(&1));
+        assert_eq!(second.current(), Some(&0));
+        assert_eq!(second.index(), Some(0));
+        assert_eq!(second.peek_prev(), None);
+        second.move_prev();
+        assert_eq!(second.current(), None);
+        assert_eq!(second.peek_next(), Some(&0));
+    }
+
+    #[test]
+    fn test_splitting_against_vec_deque() {
+        // arrange
+        let mut list = XorLinkedList::new();
+        let mut model = VecDeque::new();
+        let mut seed = 11u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
+
+        // act & assert
+        for i in 0..300 {
+            let mut cursor = list.cursor_mut();
+            let steps = next(model.len() + 1);
+            for _ in 0..steps {
+                cursor.move_next();
+            }
+            // the ghost when all steps went to the elements
+            let at = steps.checked_sub(1);
+            // elements in front of the cursor
+            let split = at.unwrap_or(model.len());
+            let input: XorLinkedList<_> = (0..next(3)).map(|j| i * 10 + j).collect();
+            match next(6) {
+                0 => {
+                    let removed = cursor.remove_after();
+                    let after = at.map_or(0, |at| at + 1);
+                    assert_eq!(removed, model.remove(after));
+                },
+                1 => {
+                    let removed = cursor.remove_before();
+                    let before = split.checked_sub(1);
+                    assert_eq!(removed, before.and_then(|before| model.remove(before)));
+                    assert_eq!(cursor.index(), at.map(|at| at.saturating_sub(1)));
+                },
+                2 => {
+                    let after = at.map_or(0, |at| at + 1);
+                    let len = input.len();
+                    cursor.splice_after(input);
+                    for j in (0..len).rev() {
+                        model.insert(after, i * 10 + j);
+                    }
+                    assert_eq!(cursor.index(), at);
+                },
+                3 => {
+                    let len = input.len();
+                    cursor.splice_before(input);
+                    for j in (0..len).rev() {
+                        model.insert(split, i * 10 + j);
+                    }
+                    assert_eq!(cursor.index(), at.map(|at| at + len));
+                },
+                4 => {
+                    let mut tail = cursor.split_after();
+                    let expected = model.split_off(at.map_or(0, |at| at + 1));
+                    assert!(tail.iter().eq(expected.iter()));
+                    check_links(&tail);
+                    list.prepend(&mut tail);
+                    model = expected.into_iter().chain(model).collect();
+                },
+                _ => {
+                    let mut head = cursor.split_before();
+                    let rest = model.split_off(split);
+                    assert!(head.iter().eq(model.iter()));
+                    check_links(&head);
+                    list.append(&mut head);
+                    model = rest.into_iter().chain(model).collect();
+                },
+            }
+            check_links(&list);
+            assert!(list.iter().eq(model.iter()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_intrusive_list {
+    use super::intrusive_list::{IntrusiveList, Linked, Links};
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+    use std::pin::{pin, Pin};
+
+    #[derive(Debug, Default)]
+    struct Item {
+        value: Cell<i32>,
+        links: Links<Item>,
+    }
+
+    unsafe impl Linked for Item {
+        fn links(&self) -> &Links<Self> {
+            &self.links
+        }
+    }
+
+    fn items(n: i32) -> Vec<Pin<Box<Item>>> {
+        (0..n)
+            .map(|i| {
+                Box::pin(Item {
+                    value: Cell::new(i),
+                    links: Links::new(),
+                })
+            })
+            .collect()
+    }
+
+    fn values(list: &IntrusiveList<'_, Item>) -> Vec<i32> {
+        let from_front: Vec<_> = list.iter().map(|item| item.value.get()).collect();
+        let mut from_back: Vec<_> = list.iter().rev().map(|item| item.value.get()).collect();
+        from_back.reverse();
+        assert_eq!(from_front, from_back);
+        assert_eq!(from_front.len(), list.len());
+        from_front
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        // arrange
+        let items = items(6);
+        let mut list = IntrusiveList::new();
+
+        // act
+        for item in &items[..3] {
+            list.push_back(item.as_ref());
+        }
+        for item in &items[3..] {
+            list.push_front(item.as_ref());
+        }
+
+        // assert
+        assert_eq!(values(&list), vec![5, 4, 3, 0, 1, 2]);
+        assert_eq!(list.front().map(|item| item.value.get()), Some(5));
+        assert_eq!(list.back().map(|item| item.value.get()), Some(2));
+        let popped = list.pop_front().unwrap();
+        assert!(!popped.links.is_linked());
+        assert_eq!(popped.value.get(), 5);
+        assert_eq!(list.pop_back().map(|item| item.value.get()), Some(2));
+        assert_eq!(values(&list), vec![4, 3, 0, 1]);
+        // popped elements can be linked again
+        list.push_back(popped);
+        assert_eq!(values(&list), vec![4, 3, 0, 1, 5]);
+        list.clear();
+        assert!(list.is_empty());
+        assert!(items.iter().all(|item| !item.links.is_linked()));
+    }
+
+    #[test]
+    fn test_pinned_on_stack() {
+        // arrange
+        let first = pin!(Item::default());
+        let second = pin!(Item::default());
+        second.value.set(1);
+        let mut list = IntrusiveList::new();
+
+        // act
+        list.push_back(first.as_ref());
+        list.push_back(second.as_ref());
+        for item in &list {
+            item.value.set(item.value.get() + 10);
+        }
+
+        // assert
+        assert_eq!(values(&list), vec![10, 11]);
+        drop(list);
+        assert!(!first.links.is_linked());
+        assert_eq!(second.value.get(), 11);
+    }
+
+    #[test]
+    fn test_remove_and_contains() {
+        // arrange
+        let items = items(5);
+        let mut list: IntrusiveList<_> = items[..4].iter().map(|item| item.as_ref()).collect();
+        let mut other = IntrusiveList::new();
+        other.push_back(items[4].as_ref());
+
+        // act & assert
+        assert!(list.contains(&items[2]));
+        assert!(!list.contains(&items[4]));
+        assert!(!list.remove(&items[4]));
+        assert!(list.remove(&items[2]));
+        assert!(!list.remove(&items[2]));
+        assert!(list.remove(&items[0]));
+        assert!(list.remove(&items[3]));
+        assert_eq!(values(&list), vec![1]);
+        assert_eq!(values(&other), vec![4]);
+        assert!(other.contains(&items[4]));
+    }
+
+    #[test]
+    #[should_panic(expected = "element is already in a list")]
+    fn test_link_twice() {
+        // arrange
+        let items = items(1);
+        let mut list = IntrusiveList::new();
+        let mut other = IntrusiveList::new();
+        list.push_back(items[0].as_ref());
+
+        // act
+        other.push_back(items[0].as_ref());
+    }
+
+    #[test]
+    fn test_iterators() {
+        // arrange
+        let items = items(5);
+        let list: IntrusiveList<_> = items.iter().map(|item| item.as_ref()).collect();
+
+        // act
+        let mut iter = list.iter();
+
+        // assert
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next().map(|item| item.value.get()), Some(0));
+        assert_eq!(iter.next_back().map(|item| item.value.get()), Some(4));
+        assert_eq!(iter.len(), 3);
+        let mut into_iter = list.into_iter();
+        assert_eq!(into_iter.next_back().map(|item| item.value.get()), Some(4));
+        drop(into_iter);
+        assert!(items.iter().all(|item| !item.links.is_linked()));
+    }
+
+    #[test]
+    fn test_cursor_against_vec_deque() {
+        // arrange
+        let items = items(300);
+        let mut list = IntrusiveList::new();
+        let mut model = VecDeque::new();
+        let mut seed = 9u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
+
+        // act & assert
+        for (i, item) in items.iter().enumerate() {
+            let i = i as i32;
+            let mut cursor = list.cursor_mut();
+            let steps = next(model.len() + 1);
+            for _ in 0..steps {
+                cursor.move_next();
+            }
+            // the ghost when all steps went to the elements
+            let at = steps.checked_sub(1);
+            assert_eq!(cursor.index(), at);
+            match next(3) {
+                0 => {
+                    cursor.insert_after(item.as_ref());
+                    model.insert(at.map_or(0, |at| at + 1), i);
+                },
+                1 => {
+                    cursor.insert_before(item.as_ref());
+                    model.insert(at.unwrap_or(model.len()), i);
+                    assert_eq!(cursor.index(), at.map(|at| at + 1));
+                },
+                _ => {
+                    let removed = cursor.remove_current();
+                    assert_eq!(
+                        removed.map(|item| item.value.get()),
+                        at.and_then(|at| model.remove(at))
+                    );
+                    let current = cursor.current().map(|item| item.value.get());
+                    assert_eq!(current, at.and_then(|at| model.get(at).copied()));
+                    assert_eq!(cursor.peek_prev().map(|item| item.value.get()), match at {
+                        Some(0) => None,
+                        Some(at) => Some(model[at - 1]),
+                        None => model.back().copied(),
+                    });
+                },
+            }
+            assert!(values(&list).into_iter().eq(model.iter().copied()));
+        }
+    }
+
+    #[test]
+    fn test_append_prepend() {
+        // arrange
+        let items = items(6);
+        let mut list: IntrusiveList<_> = items[2..4].iter().map(|item| item.as_ref()).collect();
+        let mut back: IntrusiveList<_> = items[4..].iter().map(|item| item.as_ref()).collect();
+        let mut front: IntrusiveList<_> = items[..2].iter().map(|item| item.as_ref()).collect();
+
+        // act
+        list.append(&mut back);
+        list.prepend(&mut front);
+        list.append(&mut IntrusiveList::new());
+
+        // assert
+        assert_eq!(values(&list), vec![0, 1, 2, 3, 4, 5]);
+        assert!(back.is_empty() && front.is_empty());
+        assert!(items.iter().all(|item| list.contains(item)));
+        assert!(!back.remove(&items[5]));
+        assert!(list.remove(&items[5]));
+        assert_eq!(values(&list), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_cursor_remove_split_splice() {
+        // arrange
+        let items = items(10);
+        let mut list: IntrusiveList<_> = items[..6].iter().map(|item| item.as_ref()).collect();
+        let mut input: IntrusiveList<_> = items[6..8].iter().map(|item| item.as_ref()).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        let value = |item: Option<Pin<&Item>>| item.map(|item| item.value.get());
+        assert_eq!(value(cursor.remove_after()), Some(0));
+        assert_eq!(value(cursor.remove_before()), Some(5));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(value(cursor.remove_before()), Some(1));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(value(cursor.remove_before()), None);
+        assert_eq!(value(cursor.remove_after()), Some(3));
+        cursor.splice_before(std::mem::take(&mut input));
+        input.push_back(items[8].as_ref());
+        input.push_back(items[9].as_ref());
+        cursor.splice_after(input);
+        assert_eq!(cursor.index(), Some(2));
+        let after = cursor.split_after();
+        let before = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(value(cursor.current()), Some(2));
+
+        // assert
+        assert_eq!(values(&list), vec![2]);
+        assert_eq!(values(&after), vec![8, 9, 4]);
+        assert_eq!(values(&before), vec![6, 7]);
+        assert!(after.contains(&items[4]) && before.contains(&items[6]));
+        assert!(!list.contains(&items[4]) && !list.contains(&items[0]));
+        assert!(!items[0].links.is_linked());
+    }
+
+    #[test]
+    fn test_cursor_split_splice_on_the_ghost() {
+        // arrange
+        let items = items(4);
+        let mut list: IntrusiveList<_> = items.iter().map(|item| item.as_ref()).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        let all = cursor.split_before();
+        assert_eq!(cursor.peek_prev().map(|item| item.value.get()), None);
+        assert!(cursor.split_after().is_empty());
+        cursor.splice_after(all);
+        let all = cursor.split_after();
+        assert!(cursor.remove_after().is_none() && cursor.remove_before().is_none());
+        cursor.splice_before(all);
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(3));
+
+        // assert
+        assert_eq!(values(&list), vec![0, 1, 2, 3]);
+        assert!(items.iter().all(|item| list.contains(item)));
+    }
+
+    #[test]
+    fn test_splitting_against_vec_deque() {
+        // arrange
+        let items = items(300);
+        let mut list: IntrusiveList<_> = items[..20].iter().map(|item| item.as_ref()).collect();
+        let mut model: VecDeque<i32> = (0..20).collect();
+        let mut unused = items[20..].iter();
+        let mut seed = 13u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
+
+        // act & assert
+        for _ in 0..120 {
+            let mut input = IntrusiveList::new();
+            for _ in 0..next(3) {
+                if let Some(item) = unused.next() {
+                    input.push_back(item.as_ref());
+                }
+            }
+            let added: Vec<i32> = input.iter().map(|item| item.value.get()).collect();
+            let mut cursor = list.cursor_mut();
+            let steps = next(model.len() + 1);
+            for _ in 0..steps {
+                cursor.move_next();
+            }
+            // the ghost when all steps went to the elements
+            let at = steps.checked_sub(1);
+            // elements in front of the cursor
+            let split = at.unwrap_or(model.len());
+            match next(6) {
+                0 => {
+                    let removed = cursor.remove_after().map(|item| item.value.get());
+                    assert_eq!(removed, model.remove(at.map_or(0, |at| at + 1)));
+                },
+                1 => {
+                    let removed = cursor.remove_before().map(|item| item.value.get());
+                    let before = split.checked_sub(1);
+                    assert_eq!(removed, before.and_then(|before| model.remove(before)));
+                    assert_eq!(cursor.index(), at.map(|at| at.saturating_sub(1)));
+                },
+                2 => {
+                    cursor.splice_after(input);
+                    let after = at.map_or(0, |at| at + 1);
+                    for value in added.iter().rev() {
+                        model.insert(after, *value);
+                    }
+                    assert_eq!(cursor.index(), at);
+                },
+                3 => {
+                    cursor.splice_before(input);
+                    for value in added.iter().rev() {
+                        model.insert(split, *value);
+                    }
+                    assert_eq!(cursor.index(), at.map(|at| at + added.len()));
+                },
+                4 => {
+                    let mut tail = cursor.split_after();
+                    let expected = model.split_off(at.map_or(0, |at| at + 1));
+                    assert!(values(&tail).into_iter().eq(expected.iter().copied()));
+                    list.prepend(&mut tail);
+                    model = expected.into_iter().chain(model).collect();
+                },
+                _ => {
+                    let mut head = cursor.split_before();
+                    let rest = model.split_off(split);
+                    assert!(values(&head).into_iter().eq(model.iter().copied()));
+                    list.append(&mut head);
+                    model = rest.into_iter().chain(model).collect();
+                },
+            }
+            assert!(values(&list).into_iter().eq(model.iter().copied()));
+            assert!(list.iter().all(|item| list.contains(&item)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_circular_list {
+    use super::circular_list::CircularList;
+    use std::collections::VecDeque;
+
+    fn check_links<T: Eq + std::fmt::Debug>(list: &CircularList<T>) {
+        let from_front: Vec<_> = list.iter().collect();
+        let from_back: Vec<_> = list.iter().rev().collect();
+        let re_reved: Vec<_> = from_back.into_iter().rev().collect();
+
+        assert_eq!(from_front, re_reved);
+        assert_eq!(from_front.len(), list.len());
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        // arrange
+        let mut list = CircularList::new();
+
+        // act
+        for i in 0..5 {
+            list.push_back(i);
+            list.push_front(-i - 1);
+        }
+
+        // assert
+        check_links(&list);
+        assert_eq!(list.front(), Some(&-5));
+        assert_eq!(list.back(), Some(&4));
+        *list.back_mut().unwrap() = 40;
+        assert_eq!(list.pop_back(), Some(40));
+        assert_eq!(list.pop_front(), Some(-5));
+        check_links(&list);
+        while list.pop_front().is_some() {}
+        assert!(list.is_empty());
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.back(), None);
+    }
+
+    #[test]
+    fn test_rotate() {
+        // arrange
+        let mut list: CircularList<_> = (0..5).collect();
+
+        // act & assert
+        list.rotate(2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 0, 1]);
+        list.rotate(-3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 0, 1, 2, 3]);
+        list.rotate(11);
+        assert_eq!(list.front(), Some(&0));
+        list.rotate(-5);
+        assert_eq!(list.front(), Some(&0));
+        check_links(&list);
+        CircularList::<i32>::new().rotate(3);
+    }
+
+    #[test]
+    fn test_cursor_wraps_around() {
+        // arrange
+        let mut list: CircularList<_> = (0..4).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        assert_eq!(cursor.current(), Some(&mut 0));
+        assert_eq!(cursor.peek_prev(), Some(&mut 3));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.peek_next(), Some(&mut 0));
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        cursor.step(10);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(2));
+        cursor.step(-7);
+        assert_eq!(cursor.current(), Some(&mut 3));
+        cursor.set_front();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 0, 1, 2]);
+
+        // any step wraps around, even the largest ones
+        let mut list: CircularList<_> = (0..3).collect();
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.step(isize::MAX);
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        cursor.step(isize::MIN);
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 0));
+
+        let mut empty = CircularList::<i32>::new();
+        let mut cursor = empty.cursor_mut();
+        cursor.step(3);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        cursor.insert_before(1);
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+    }
+
+    #[test]
+    fn test_josephus() {
+        // arrange
+        let mut list: CircularList<_> = (1..=7).collect();
+        let mut order = Vec::new();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        // every third one is out
+        while cursor.index().is_some() {
+            cursor.step(2);
+            order.push(cursor.remove_current().unwrap());
+        }
+
+        // assert
+        assert_eq!(order, vec![3, 6, 2, 7, 5, 1, 4]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_splice() {
+        // arrange
+        let mut list: CircularList<_> = (0..3).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act
+        cursor.splice_before((10..12).collect());
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.splice_after((20..22).collect());
+        cursor.splice_after(CircularList::new());
+
+        // assert
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(4));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 11, 0, 1, 2, 20, 21]);
+        check_links(&list);
+
+        let mut empty = CircularList::new();
+        let mut cursor = empty.cursor_mut();
+        cursor.splice_before((0..2).collect());
+        assert_eq!(cursor.current(), Some(&mut 0));
+        let mut other: CircularList<_> = (2..4).collect();
+        empty.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(empty.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
     }
 
     #[test]
-    fn test_eq_empty() {
+    fn test_cursor_against_vec_deque() {
         // arrange
-        let list: doubly_linked_list::LinkedList<i32> = list_from(&[]);
-        let other_list = list_from(&[]);
+        let mut list = CircularList::new();
+        let mut model = VecDeque::new();
+        let mut seed = 13u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
 
         // act & assert
-        assert_eq!(list, other_list);
+        let mut at = 0;
+        for i in 0..300 {
+            let mut cursor = list.cursor_mut();
+            cursor.step(at as isize);
+            let steps = next(21) as isize - 10;
+            cursor.step(steps);
+            if !model.is_empty() {
+                at = (at as isize + steps).rem_euclid(model.len() as isize) as usize;
+            }
+            assert_eq!(cursor.index(), (!model.is_empty()).then_some(at));
+            match next(5) {
+                0 => {
+                    cursor.insert_after(i);
+                    model.insert(if model.is_empty() { 0 } else { at + 1 }, i);
+                },
+                1 => {
+                    cursor.insert_before(i);
+                    if model.is_empty() {
+                        model.push_back(i);
+                    } else {
+                        model.insert(at, i);
+                        at += 1;
+                    }
+                },
+                2 => {
+                    let input: Vec<_> = (0..next(3) as i32).map(|j| 1000 + j).collect();
+                    cursor.splice_after(input.iter().copied().collect());
+                    let after = if model.is_empty() { 0 } else { at + 1 };
+                    for (offset, elem) in input.into_iter().enumerate() {
+                        model.insert(after + offset, elem);
+                    }
+                },
+                _ => {
+                    assert_eq!(cursor.remove_current(), model.remove(at));
+                    if at == model.len() {
+                        at = 0;
+                    }
+                },
+            }
+            check_links(&list);
+            assert!(list.iter().eq(model.iter()));
+        }
     }
 
     #[test]
-    fn test_eq() {
+    fn test_drop_elements() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = list_from(&[]);
-        list.push_back(1);
-        list.push_back(2);
-        list.push_back(3);
-        let other_list = list_from(&[1, 2, 3]);
-
-        // act & assert
-        assert_eq!(list, other_list);
+        let drops = std::rc::Rc::new(std::cell::Cell::new(0));
+        struct DropCounter(std::rc::Rc<std::cell::Cell<usize>>);
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        let mut list: CircularList<_> = (0..10).map(|_| DropCounter(drops.clone())).collect();
 
-        // arrange
-        list.push_back(5);
+        // act
+        list.rotate(3);
+        drop(list.pop_back());
+        let mut iter = list.into_iter();
+        drop(iter.next());
+        drop(iter);
 
-        // act & assert
-        assert_ne!(list, other_list);
+        // assert
+        assert_eq!(drops.get(), 10);
     }
 
     #[test]
-    fn test_ord() {
+    fn test_traits() {
         // arrange
-        let list = list_from(&[]);
-        let other_list = list_from(&[1, 2, 3]);
+        let list: CircularList<i32> = (1..8).collect();
+        let mut other = CircularList::new();
+        other.extend([1, 2, 3]);
+        other.extend(4..8);
+        let mut set = std::collections::HashSet::new();
 
         // act & assert
-        assert!(list < other_list);
-        assert!(other_list >= list);
+        assert_eq!(list, other);
+        assert_eq!(list.clone(), list);
+        assert!(list < CircularList::from_iter([1, 3]));
+        assert_eq!(format!("{:?}", list), "[1, 2, 3, 4, 5, 6, 7]");
+        other.rotate(1);
+        assert_ne!(list, other);
+        assert!(set.insert(list));
+        assert!(set.insert(other.clone()));
+        other.clear();
+        assert!(other.is_empty());
+        assert_eq!(other, CircularList::default());
     }
 
     #[test]
-    fn test_debug() {
+    fn test_try_push() {
         // arrange
-        let list: doubly_linked_list::LinkedList<i32> = (0..10).collect();
+        let mut list = CircularList::new();
 
         // act
-        let debug_str = format!("{:?}", list);
+        list.try_push_back(1).unwrap();
+        list.try_push_front(0).unwrap();
+        list.try_extend([3, 4]).unwrap();
+        let mut cursor = list.cursor_mut();
+        // the cursor starts on the front, there is no ghost
+        cursor.move_next();
+        cursor.try_insert_after(2).unwrap();
+        cursor.try_insert_before(-1).unwrap();
 
         // assert
-        assert_eq!(debug_str, "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]");
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, -1, 1, 2, 3, 4]);
+    }
+}
 
-        // and another list content except numbers, let's try str
+#[cfg(test)]
+mod test_arena_list {
+    use super::arena_list::ArenaList;
+    use std::collections::VecDeque;
+
+    fn check_links<T: Eq + std::fmt::Debug>(list: &ArenaList<T>) {
+        let from_front: Vec<_> = list.iter().collect();
+        let from_back: Vec<_> = list.iter().rev().collect();
+        let re_reved: Vec<_> = from_back.into_iter().rev().collect();
+
+        assert_eq!(from_front, re_reved);
+        assert_eq!(from_front.len(), list.len());
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
         // arrange
-        let list: doubly_linked_list::LinkedList<&str> = vec!["just", "one", "more", "test"]
-            .iter()
-            .copied()
-            .collect();
+        let mut list = ArenaList::new();
 
         // act
-        let debug_str = format!("{:?}", list);
+        for i in 0..5 {
+            list.push_back(i);
+            list.push_front(-i - 1);
+        }
 
         // assert
-        assert_eq!(debug_str, r#"["just", "one", "more", "test"]"#);
+        check_links(&list);
+        assert_eq!(list.front(), Some(&-5));
+        assert_eq!(list.back(), Some(&4));
+        *list.front_mut().unwrap() *= 10;
+        assert_eq!(list.pop_front(), Some(-50));
+        assert_eq!(list.pop_back(), Some(4));
+        check_links(&list);
+        while list.pop_front().is_some() {}
+        assert!(list.is_empty());
+        assert_eq!(list.pop_back(), None);
     }
 
     #[test]
-    fn test_hashmap() {
+    fn test_reuses_free_slots() {
         // arrange
-        let list: doubly_linked_list::LinkedList<i32> = (0..10).collect();
-        let other_list: doubly_linked_list::LinkedList<i32> = (1..11).collect();
-        let mut map = std::collections::HashMap::new();
+        let mut list = ArenaList::with_capacity(4);
+        list.extend(0..4);
+        let capacity = list.capacity();
 
-        // act & assert
-        assert_eq!(map.insert(list.clone(), "list"), None); // correctly inserted
-        assert_eq!(map.insert(other_list.clone(), "other_list"), None); // correctly inserted
+        // act
+        for i in 4..100 {
+            list.pop_front();
+            list.push_back(i);
+        }
 
-        // check for correct length
-        assert_eq!(map.len(), 2);
+        // assert
+        assert!(capacity >= 4);
+        assert_eq!(list.capacity(), capacity);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![96, 97, 98, 99]);
+    }
 
-        // get key of corresponding value
-        assert_eq!(map.get(&list), Some(&("list")));
-        assert_eq!(map.get(&other_list), Some(&("other_list")));
+    #[test]
+    fn test_compact_and_shrink() {
+        // arrange
+        let mut list: ArenaList<_> = (0..100).collect();
+        list.retain(|elem| elem % 10 == 0);
+        list.push_front(-1);
+        assert!(list.capacity() >= 100);
 
-        // remove value and corresponding key
-        assert_eq!(map.remove(&list), Some("list"));
-        assert_eq!(map.remove(&other_list), Some("other_list"));
+        // act
+        list.compact();
 
-        // the map should be empty after removing both keys we added before
-        assert!(map.is_empty());
+        // assert
+        check_links(&list);
+        assert_eq!(list.len(), 11);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>()[..3], [-1, 0, 10]);
+        assert!(list.capacity() >= 100);
+        list.shrink_to_fit();
+        assert_eq!(list.capacity(), 11);
+        list.push_back(100);
+        assert_eq!(list.back(), Some(&100));
+        check_links(&list);
+
+        let mut empty = ArenaList::<i32>::with_capacity(8);
+        empty.shrink_to_fit();
+        assert_eq!(empty.capacity(), 0);
+        empty.push_back(1);
+        assert_eq!(empty.front(), Some(&1));
     }
 
     #[test]
-    fn test_cursor_index_empty() {
+    fn test_iterators() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
+        let mut list: ArenaList<_> = (0..6).collect();
+        list.remove(2);
+        list.insert(2, 20);
 
         // act
-        let idx = cursor.index();
+        for elem in list.iter_mut().rev().take(2) {
+            *elem *= 10;
+        }
 
         // assert
-        assert!(idx.is_none());
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&50));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &20, &3, &40]);
+        let mut iter_mut = list.iter_mut();
+        assert_eq!(iter_mut.next_back(), Some(&mut 50));
+        assert_eq!(iter_mut.len(), 5);
+        let mut into_iter = list.into_iter();
+        assert_eq!(into_iter.next_back(), Some(50));
+        assert_eq!(into_iter.collect::<Vec<_>>(), vec![0, 1, 20, 3, 40]);
+    }
 
+    #[test]
+    fn test_indexed_access() {
         // arrange
-        cursor.move_next();
+        let mut list: ArenaList<_> = (0..5).collect();
 
         // act
-        let idx = cursor.index();
+        list[1] = 10;
+        list.insert(5, 5);
+        list.insert(0, -1);
 
         // assert
-        assert!(idx.is_none());
+        assert_eq!(list[0], -1);
+        assert_eq!(list[2], 10);
+        assert_eq!(list.get(6), Some(&5));
+        assert_eq!(list.get(7), None);
+        assert_eq!(list.remove(3), Some(2));
+        assert_eq!(list.remove(9), None);
+        assert!(list.contains(&10));
+        check_links(&list);
     }
 
     #[test]
-    fn test_cursor_index() {
+    fn test_split_and_append() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3, 4, 5, 6]);
-        let mut cursor = list.cursor_mut();
+        let mut list: ArenaList<_> = (0..10).collect();
 
         // act
-        let idx = cursor.index();
+        let mut back = list.split_off(6);
+        let mut front = list.split_off(0);
+        front.append(&mut back);
+        let mut other: ArenaList<_> = (-3..0).collect();
+        front.prepend(&mut other);
+        front.reverse();
 
         // assert
-        assert!(idx.is_none());
+        assert!(list.is_empty());
+        assert!(back.is_empty());
+        assert!(other.is_empty());
+        assert_eq!(front.iter().copied().collect::<Vec<_>>(), (-3..10).rev().collect::<Vec<_>>());
+        check_links(&front);
+    }
 
+    #[test]
+    fn test_cursor() {
         // arrange
+        let mut list: ArenaList<_> = (0..6).collect();
+        let mut cursor = list.cursor_mut();
+
+        // act & assert
+        assert_eq!(cursor.peek_next(), Some(&mut 0));
+        assert_eq!(cursor.peek_prev(), Some(&mut 5));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 4));
+        assert_eq!(cursor.index(), Some(4));
+        assert_eq!(cursor.as_cursor().peek_next(), Some(&5));
+        let after = cursor.split_after();
+        assert_eq!(after.iter().copied().collect::<Vec<_>>(), vec![5]);
+        cursor.move_prev();
+        let before = cursor.split_before();
+        assert_eq!(before.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(cursor.index(), Some(0));
+        cursor.splice_before(before);
+        assert_eq!(cursor.index(), Some(3));
+        cursor.splice_after(after);
+        assert_eq!(cursor.peek_next(), Some(&mut 5));
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.current(), Some(&mut 5));
+        cursor.insert_before(3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 5, 4]);
+
+        let mut cursor = list.cursor();
+        cursor.move_next();
+        let copy = cursor;
         cursor.move_next();
+        assert_eq!(copy.current(), Some(&0));
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.peek_prev(), Some(&0));
+    }
 
-        // act
-        let idx = cursor.index();
+    #[test]
+    fn test_cursor_walk_back_past_front() {
+        // arrange
+        let mut list: ArenaList<_> = (0..3).collect();
 
-        // assert
-        assert!(idx.is_some());
-        assert_eq!(idx, Some(0));
+        // act & assert
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
 
-        // arrange
+        let mut cursor = list.cursor();
+        cursor.move_next();
         cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
         cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+    }
 
-        // act
-        let idx = cursor.index();
+    #[test]
+    fn test_cursor_against_vec_deque() {
+        // arrange
+        let mut list = ArenaList::new();
+        let mut model = VecDeque::new();
+        let mut seed = 17u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
 
-        // assert
-        assert!(idx.is_some());
-        assert_eq!(idx, Some(5));
+        // act & assert
+        for i in 0..500 {
+            let mut cursor = list.cursor_mut();
+            let steps = next(model.len() + 1);
+            for _ in 0..steps {
+                cursor.move_next();
+            }
+            // the ghost when all steps went to the elements
+            let at = steps.checked_sub(1);
+            assert_eq!(cursor.index(), at);
+            match next(5) {
+                0 => {
+                    cursor.insert_after(i);
+                    model.insert(at.map_or(0, |at| at + 1), i);
+                },
+                1 => {
+                    cursor.insert_before(i);
+                    model.insert(at.unwrap_or(model.len()), i);
+                    assert_eq!(cursor.index(), at.map(|at| at + 1));
+                },
+                2 if i % 50 == 0 => list.compact(),
+                _ => {
+                    let removed = cursor.remove_current();
+                    assert_eq!(removed, at.and_then(|at| model.remove(at)));
+                },
+            }
+            check_links(&list);
+            assert!(list.iter().eq(model.iter()));
+            assert!(list.capacity() >= list.len());
+        }
     }
 
     #[test]
-    fn test_cursor_peek_next_empty() {
+    fn test_drop_elements() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
+        let drops = std::rc::Rc::new(std::cell::Cell::new(0));
+        struct DropCounter(std::rc::Rc<std::cell::Cell<usize>>);
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        let mut list: ArenaList<_> = (0..10).map(|_| DropCounter(drops.clone())).collect();
 
         // act
-        let ghost = cursor.peek_next();
+        drop(list.remove(4));
+        list.compact();
+        drop(list.pop_back());
+        list.clear();
+        list.push_back(DropCounter(drops.clone()));
+        drop(list);
 
         // assert
-        assert!(ghost.is_none());
+        assert_eq!(drops.get(), 11);
+    }
 
+    #[test]
+    fn test_traits() {
         // arrange
-        cursor.move_next();
-
-        // act
-        let ghost = cursor.peek_next();
+        let list: ArenaList<i32> = (1..8).collect();
+        let mut other = ArenaList::new();
+        other.extend([1, 2, 3]);
+        other.extend(4..8);
+        let mut set = std::collections::HashSet::new();
 
-        // assert
-        assert!(ghost.is_none());
+        // act & assert
+        assert_eq!(list, other);
+        assert_eq!(list.clone(), list);
+        assert!(list < ArenaList::from_iter([1, 3]));
+        assert_eq!(format!("{:?}", list), "[1, 2, 3, 4, 5, 6, 7]");
+        assert!(set.insert(list));
+        assert!(!set.insert(other.clone()));
+        other.clear();
+        assert!(other.is_empty());
+        assert_eq!(other, ArenaList::default());
+    }
 
+    #[test]
+    fn test_try_push() {
         // arrange
-        cursor.move_prev();
-        cursor.move_prev();
+        let mut list = ArenaList::new();
 
         // act
-        let ghost = cursor.current();
+        list.try_push_back(1).unwrap();
+        list.try_push_front(0).unwrap();
+        list.try_extend([3, 4]).unwrap();
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.try_insert_after(2).unwrap();
+        cursor.try_insert_before(-1).unwrap();
 
         // assert
-        assert!(ghost.is_none());
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, -1, 1, 2, 3, 4]);
+        assert!(list.try_reserve(10).is_ok());
+        assert!(list.capacity() >= 16);
     }
 
     #[test]
-    fn test_cursor_peek_next() {
+    fn test_search_dedup_sort() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3, 4, 5, 6]);
-        let mut cursor = list.cursor_mut();
+        let mut list: ArenaList<_> = [3, 3, 1, 2, 2, 2, 3, 1].into_iter().collect();
+        list.pop_front();
+        list.push_front(3);
 
         // act
-        let next = cursor.peek_next();
+        list.dedup();
 
         // assert
-        assert!(next.is_some());
-        assert_eq!(next, Some(&mut 1));
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 1, 2, 3, 1]);
+        assert_eq!(list.position(|&x| x == 3), Some(0));
+        assert_eq!(list.rposition(|&x| x == 3), Some(3));
+        assert_eq!(list.find(|&x| x < 2), Some(&1));
+        list.sort();
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 1, 2, 3, 3]);
+        list.dedup_by_key(|x| *x / 2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+    }
 
+    #[test]
+    fn test_sort_is_stable() {
         // arrange
-        cursor.move_prev();
+        let mut list = ArenaList::new();
+        let mut seed = 7u64;
+        for i in 0..200 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            list.push_back(((seed >> 33) % 10, i));
+            if seed >> 62 == 0 {
+                // leave some free slots behind
+                list.pop_front();
+            }
+        }
+        let mut model: Vec<_> = list.iter().copied().collect();
 
         // act
-        let ghost = cursor.peek_next();
+        list.sort_by_key(|&(key, _)| key);
+        model.sort_by_key(|&(key, _)| key);
 
         // assert
-        assert!(ghost.is_none());
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), model);
     }
 
     #[test]
-    fn test_cursor_peek_prev_empty() {
+    fn test_sort_panic_keeps_the_list() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
+        let mut list: ArenaList<_> = (0..10).rev().collect();
 
         // act
-        let ghost = cursor.peek_prev();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut compares = 0;
+            list.sort_by(|a, b| {
+                compares += 1;
+                assert!(compares < 5, "bad compare");
+                a.cmp(b)
+            })
+        }));
 
         // assert
-        assert!(ghost.is_none());
+        assert!(result.is_err());
+        check_links(&list);
+        let unsorted: Vec<_> = (0..10).rev().collect();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), unsorted);
+    }
 
+    #[test]
+    fn test_drain_extract_if() {
         // arrange
-        cursor.move_prev();
+        let mut list: ArenaList<_> = (0..10).collect();
 
         // act
-        let ghost = cursor.peek_prev();
+        let drained: Vec<_> = list.drain(2..=4).collect();
+        let odd: Vec<_> = list.extract_if(|x| *x % 2 == 1).collect();
 
         // assert
-        assert!(ghost.is_none());
+        check_links(&list);
+        assert_eq!(drained, vec![2, 3, 4]);
+        assert_eq!(odd, vec![1, 5, 7, 9]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 6, 8]);
+        {
+            let mut extract = list.extract_if(|x| *x > 0);
+            assert_eq!(extract.size_hint(), (0, Some(3)));
+            assert_eq!(extract.next(), Some(6));
+        }
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 8]);
+        assert_eq!(list.drain(..).count(), 2);
+        assert!(list.is_empty());
+    }
 
+    #[test]
+    #[should_panic(expected = "drain end is out of bounds")]
+    fn test_drain_out_of_bounds() {
         // arrange
-        cursor.move_prev();
-        cursor.move_prev();
+        let mut list: ArenaList<_> = (0..3).collect();
 
         // act
-        let ghost = cursor.current();
-
-        // assert
-        assert!(ghost.is_none());
+        list.drain(1..4);
     }
 
     #[test]
-    fn test_cursor_peek_prev() {
+    fn test_swap_rotate() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3, 4, 5, 6]);
-        let mut cursor = list.cursor_mut();
+        let mut list: ArenaList<_> = (0..6).collect();
 
         // act
-        let prev = cursor.peek_prev();
+        list.swap(0, 5);
+        list.swap(2, 2);
+        list.rotate_left(2);
 
         // assert
-        assert!(prev.is_some());
-        assert_eq!(prev, Some(&mut 6));
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 0, 5, 1]);
+        list.rotate_right(2);
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 1, 2, 3, 4, 0]);
+        list.rotate_left(6);
+        list.rotate_right(0);
+        assert_eq!(list.front(), Some(&5));
+    }
 
+    #[test]
+    fn test_cursor_at_remove_replace() {
         // arrange
-        cursor.move_next();
+        let mut list: ArenaList<_> = (0..6).collect();
 
         // act
-        let ghost = cursor.peek_prev();
+        let mut cursor = list.cursor_at(2).unwrap();
+        assert_eq!(cursor.replace_current(20), Ok(2));
+        assert_eq!(cursor.remove_after(), Some(3));
+        assert_eq!(cursor.remove_before(), Some(1));
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 20));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.replace_current(7), Err(7));
+        assert_eq!(cursor.remove_after(), Some(0));
+        assert_eq!(cursor.remove_before(), Some(5));
 
         // assert
-        assert!(ghost.is_none());
+        check_links(&list);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![20, 4]);
+        assert!(list.cursor_at(2).is_none());
     }
+}
 
-    #[test]
-    fn test_cursor_current_empty() {
-        // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
+#[cfg(test)]
+mod test_allocator {
+    use super::allocator::{AllocError, Allocator, Box, Global, TryReserveErrorKind};
+    use super::{better_stack, doubly_linked_list::LinkedList, stack, unsafe_queue, Stack};
+    use std::alloc::Layout;
+    use std::cell::Cell;
+    use std::ptr::NonNull;
+
+    // forwards to the global allocator and keeps track of the live blocks,
+    // optionally failing once `limit` blocks are live
+    #[derive(Default)]
+    struct Counting {
+        allocs: Cell<usize>,
+        frees: Cell<usize>,
+        limit: Option<usize>,
+    }
 
-        // act & assert
-        let ghost = cursor.current();
-        assert!(ghost.is_none());
+    impl Counting {
+        fn with_limit(limit: usize) -> Self {
+            Counting {
+                limit: Some(limit),
+                ..Counting::default()
+            }
+        }
 
-        // act & assert
-        cursor.move_next();
-        let ghost = cursor.current();
-        assert!(ghost.is_none());
+        fn live(&self) -> usize {
+            self.allocs.get() - self.frees.get()
+        }
+    }
 
-        // act & assert
-        cursor.move_prev();
-        cursor.move_prev();
-        let ghost = cursor.current();
-        assert!(ghost.is_none());
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            if self.limit.is_some_and(|limit| self.live() >= limit) {
+                return Err(AllocError);
+            }
+            let block = Global.allocate(layout)?;
+            self.allocs.set(self.allocs.get() + 1);
+            Ok(block)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.frees.set(self.frees.get() + 1);
+            Global.deallocate(ptr, layout)
+        }
     }
 
     #[test]
-    fn test_cursor_current() {
+    fn test_box() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3, 4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-
-        // act & assert
-        let ghost = cursor.current();
-        assert!(ghost.is_none());
+        let counting = Counting::default();
 
-        // act & assert
-        cursor.move_next();
-        let first = cursor.current();
-        assert_eq!(first, Some(&mut 1));
+        // act
+        let mut boxed = Box::new_in(String::from("a"), &counting);
+        boxed.push('b');
 
-        // act & assert
-        cursor.move_next();
-        let second = cursor.current();
-        assert_eq!(second, Some(&mut 2));
+        // assert
+        assert_eq!(counting.live(), 1);
+        assert_eq!(*boxed, "ab");
+        assert_eq!(format!("{:?}", boxed), "\"ab\"");
+        assert_eq!(boxed.into_inner(), "ab");
+        assert_eq!(counting.live(), 0);
+        drop(Box::new_in(String::from("c"), &counting));
+        assert_eq!((counting.allocs.get(), counting.frees.get()), (2, 2));
     }
 
     #[test]
-    fn test_cursor_insert_after_empty() {
+    fn test_box_zero_sized_and_failing() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
+        let counting = Counting::with_limit(1);
 
         // act
-        cursor.insert_after(1);
+        let first = Box::try_new_in(1, &counting);
+        let second = Box::try_new_in(2, &counting);
 
         // assert
-        assert_eq!(cursor.peek_next(), Some(&mut 1));
-        assert!(cursor.current().is_none());
-        assert_eq!(cursor.peek_prev(), Some(&mut 1));
-        assert_eq!(list.len(), 1);
+        assert_eq!(first.map(Box::into_inner), Ok(1));
+        assert_eq!(second.err(), Some(2));
+        assert_eq!(*Box::new(()), ());
+        assert_eq!(AllocError.to_string(), "memory allocation failed");
     }
 
     #[test]
-    fn test_cursor_insert_after() {
+    fn test_stack() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 3]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        assert_eq!(cursor.index(), Some(0));
+        let counting = Counting::default();
+        let mut list = stack::List::new_in(&counting);
 
         // act
-        cursor.insert_after(2);
+        list.extend(0..3);
+        let cloned = list.clone();
 
         // assert
-        assert_eq!(cursor.peek_next(), Some(&mut 2));
-        assert_eq!(cursor.current(), Some(&mut 1));
-        assert_eq!(cursor.peek_prev(), None);
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, (1..4).collect());
+        assert_eq!(counting.live(), 6);
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(counting.live(), 5);
+        assert!(std::ptr::eq(*list.allocator(), &counting));
+        drop(list);
+        assert_eq!(cloned.into_iter().collect::<Vec<_>>(), vec![2, 1, 0]);
+        assert_eq!(counting.live(), 0);
     }
 
     #[test]
-    fn test_cursor_insert_before_empty() {
+    fn test_better_stack() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
+        let counting = Counting::default();
+        let mut list = better_stack::List::new_in(&counting);
 
         // act
-        cursor.insert_before(1);
+        list.extend(0..3);
+        let cloned = list.clone();
 
         // assert
-        assert_eq!(cursor.peek_next(), Some(&mut 1));
-        assert!(cursor.current().is_none());
-        assert_eq!(cursor.peek_prev(), Some(&mut 1));
-        assert_eq!(list.len(), 1);
+        assert_eq!(counting.live(), 6);
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.peek(), Some(&1));
+        assert_eq!(counting.live(), 5);
+        drop(list);
+        assert_eq!(cloned.into_iter().collect::<Vec<_>>(), vec![2, 1, 0]);
+        assert_eq!(counting.live(), 0);
     }
 
     #[test]
-    fn test_cursor_insert_before() {
+    fn test_unsafe_queue() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 3]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_prev();
-        assert_eq!(cursor.index(), Some(1));
+        let counting = Counting::default();
+        let mut list = unsafe_queue::List::bounded_in(4, &counting);
+        let mut other = unsafe_queue::List::new_in(&counting);
 
         // act
-        cursor.insert_before(2);
+        list.extend(0..2);
+        other.extend(2..4);
+        list.append(&mut other);
 
         // assert
-        assert_eq!(cursor.peek_prev(), Some(&mut 2));
-        assert_eq!(cursor.current(), Some(&mut 3));
-        assert_eq!(cursor.peek_next(), None);
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, (1..4).collect());
+        assert_eq!(counting.live(), 4);
+        assert_eq!(list.try_push(4).map_err(|err| err.into_inner()), Err(4));
+        assert_eq!(list.pop(), Some(0));
+        assert_eq!(counting.live(), 3);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(counting.live(), 2);
+        drop(iter);
+        drop(other);
+        assert_eq!(counting.live(), 0);
+        assert_eq!(counting.allocs.get(), 4);
     }
 
     #[test]
-    fn test_cursor_remove_after_empty() {
+    fn test_doubly_linked_list() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
+        let counting = Counting::default();
+        let mut list = LinkedList::new_in(&counting);
 
         // act
-        let value = cursor.remove_after();
+        list.extend(0..6);
+        list.push_front(-1);
+        let handle = list.push_back_handle(6);
+        let mut back = list.split_off(4);
+        {
+            let mut cursor = list.cursor_mut();
+            cursor.move_next();
+            cursor.insert_after(10);
+            cursor.remove_current();
+        }
+        list.append(&mut back);
 
         // assert
-        assert!(value.is_none());
+        assert_eq!(counting.live(), 8);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(list.remove(&handle), Some(6));
+        assert_eq!(list.drain(..2).collect::<Vec<_>>(), vec![10, 0]);
+        assert_eq!(counting.live(), 5);
+        let cloned = list.clone();
+        assert_eq!(counting.live(), 10);
+        drop(list);
+        drop(back);
+        drop(cloned);
+        assert_eq!(counting.live(), 0);
     }
 
     #[test]
-    fn test_cursor_remove_after_general() {
+    fn test_doubly_linked_list_sorting() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 4, 2, 3]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        assert_eq!(cursor.index(), Some(0));
+        let counting = Counting::default();
+        let mut list = LinkedList::new_in(&counting);
+        let mut other = LinkedList::new_in(&counting);
 
         // act
-        let value = cursor.remove_after();
+        list.extend([5, 1, 4]);
+        other.extend([2, 3]);
+        list.sort();
+        list.merge(other, |a, b| a.cmp(b));
+        list.retain(|&elem| elem != 4);
 
         // assert
-        assert_eq!(value, Some(4));
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, (1..4).collect());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 5]);
+        assert_eq!(counting.live(), 4);
+        drop(list);
+        assert_eq!((counting.allocs.get(), counting.frees.get()), (5, 5));
+    }
+    #[test]
+    fn test_try_push_stacks() {
+        // arrange
+        let counting = Counting::with_limit(2);
+        let mut list = stack::List::new_in(&counting);
+        let mut better = better_stack::List::new_in(&counting);
+
+        // act
+        let pushed = list.try_extend([1, 2, 3, 4]);
+        let err = better.try_push(5).unwrap_err();
+
+        // assert
+        let err_kind = pushed.as_ref().unwrap_err().kind();
+        assert!(matches!(err_kind, TryReserveErrorKind::AllocError { .. }));
+        assert_eq!(pushed.unwrap_err().into_inner(), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(list.len(), 2);
+        assert_eq!(*err.elem(), 5);
+        assert!(better.is_empty());
+        list.pop();
+        assert_eq!(better.try_push(5).map_err(|err| err.into_inner()), Ok(()));
+        assert_eq!(better.peek(), Some(&5));
     }
 
     #[test]
-    fn test_cursor_remove_after_start() {
+    fn test_try_push_unsafe_queue() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([4, 1, 2, 3]);
-        let mut cursor = list.cursor_mut();
-        assert_eq!(cursor.index(), None);
+        let counting = Counting::with_limit(3);
+        let mut bounded = unsafe_queue::List::bounded_in(2, &counting);
+        let mut unbounded = unsafe_queue::List::new_in(&counting);
 
         // act
-        let value = cursor.remove_after();
+        bounded.try_extend(0..2).unwrap();
+        let full = bounded.try_push(2).unwrap_err();
+        let pushed = unbounded.try_extend(10..20);
 
         // assert
-        assert_eq!(value, Some(4));
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, (1..4).collect());
+        assert_eq!(full.kind(), TryReserveErrorKind::CapacityOverflow);
+        assert_eq!(full.into_inner(), 2);
+        assert_eq!(pushed.unwrap_err().into_inner(), 11);
+        assert_eq!(unbounded.peek_back(), Some(&10));
+        assert_eq!(counting.live(), 3);
+        assert_eq!(bounded.pop(), Some(0));
+        assert!(unbounded.try_push(11).is_ok());
+        assert_eq!(unbounded.into_iter().collect::<Vec<_>>(), vec![10, 11]);
     }
 
     #[test]
-    fn test_cursor_remove_after_end() {
+    fn test_try_push_doubly_linked_list() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3, 4]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_prev();
-        cursor.move_prev();
-        assert_eq!(cursor.index(), Some(2));
+        let counting = Counting::with_limit(4);
+        let mut list = LinkedList::new_in(&counting);
 
         // act
-        let value = cursor.remove_after();
+        list.try_push_back(2).unwrap();
+        list.try_push_front(0).unwrap();
+        list.try_insert(1, 1).unwrap();
+        {
+            let mut cursor = list.cursor_mut();
+            cursor.move_prev();
+            cursor.try_insert_after(3).unwrap();
+            assert_eq!(cursor.try_insert_before(-1).unwrap_err().into_inner(), -1);
+            assert_eq!(cursor.try_insert_after(-2).unwrap_err().into_inner(), -2);
+            assert_eq!(cursor.index(), Some(2));
+        }
 
         // assert
-        assert_eq!(value, Some(4));
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, (1..4).collect());
+        assert_eq!(list.try_push_front(-3).unwrap_err().into_inner(), -3);
+        assert_eq!(list.try_push_back(-4).unwrap_err().into_inner(), -4);
+        assert_eq!(list.try_insert(2, -5).unwrap_err().into_inner(), -5);
+        assert_eq!(list.try_extend([-6, -7]).unwrap_err().into_inner(), -6);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(list.iter().rev().count(), 4);
+        assert_eq!(counting.live(), 4);
+        list.pop_back();
+        list.try_extend([4]).unwrap();
+        assert_eq!(list.back(), Some(&4));
     }
 
     #[test]
-    fn test_cursor_remove_after_single_element() {
+    fn test_try_reserve_error() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([4]);
-        let mut cursor = list.cursor_mut();
-        assert_eq!(cursor.index(), None);
+        let counting = Counting::with_limit(0);
+        let mut list = LinkedList::new_in(&counting);
 
         // act
-        let value = cursor.remove_after();
+        let err = list.try_push_back(String::from("a")).unwrap_err();
 
         // assert
-        assert_eq!(value, Some(4));
-        assert!(list.is_empty());
-        assert_eq!(list, doubly_linked_list::LinkedList::new());
+        let debug = format!("{:?}", err);
+        assert!(debug.starts_with("TryReserveError { kind: AllocError { layout: "));
+        assert!(debug.ends_with(", .. }"));
+        assert_eq!(err.to_string(), "memory allocation failed");
+        assert_eq!(err.into_inner(), "a");
+        let full = unsafe_queue::List::bounded(0).try_push(1).unwrap_err();
+        assert_eq!(full.to_string(), "the list can't hold any more elements");
     }
 
     #[test]
-    fn test_cursor_remove_before_empty() {
+    fn test_stacks_own_their_allocator() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
+        fn fill<S: Stack<i32>>(stack: &mut S) {
+            stack.push(1);
+            stack.push(2);
+        }
+        let mut list = stack::List::new_in(Counting::default());
+        let mut better = better_stack::List::new_in(Counting::with_limit(2));
 
         // act
-        let value = cursor.remove_before();
+        fill(&mut list);
+        fill(&mut better);
+        let full = better.try_push(3).map_err(|err| err.into_inner());
 
         // assert
-        assert!(value.is_none());
+        assert_eq!(list.allocator().live(), 2);
+        assert_eq!(full, Err(3));
+        assert_eq!(better.iter().collect::<Vec<_>>(), vec![&2, &1]);
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(better.pop(), Some(2));
+        assert_eq!(list.allocator().live(), 1);
+        assert_eq!(better.allocator().live(), 1);
     }
 
     #[test]
-    fn test_cursor_remove_before_general() {
+    fn test_append_prepend_without_clone() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 4, 3]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_prev();
-        assert_eq!(cursor.index(), Some(3));
+        // forwards to a shared `Counting`, but can't be cloned
+        struct Shared<'a>(&'a Counting);
+
+        unsafe impl Allocator for Shared<'_> {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.0.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.0.deallocate(ptr, layout)
+            }
+        }
+
+        let counting = Counting::default();
+        let mut list = LinkedList::new_in(Shared(&counting));
+        let mut other = LinkedList::new_in(Shared(&counting));
+        list.extend([2, 3]);
+        other.push_back(4);
+        let handle = other.push_back_handle(5);
 
         // act
-        let value = cursor.remove_before();
+        list.append(&mut other);
+        other.push_back(1);
+        list.prepend(&mut other);
 
         // assert
-        assert_eq!(value, Some(4));
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, (1..4).collect());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert!(other.is_empty());
+        assert!(list.contains_handle(&handle));
+        assert!(!other.contains_handle(&handle));
+        assert_eq!(counting.live(), 5);
+        drop(list);
+        assert_eq!(counting.live(), 0);
     }
+}
+
+#[cfg(test)]
+mod test_sync {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
 
     #[test]
-    fn test_cursor_remove_before_start() {
+    fn test_push_pop() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([4, 1, 2, 3]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        cursor.move_next();
-        assert_eq!(cursor.index(), Some(1));
+        let queue = sync::BlockingQueue::new();
 
         // act
-        let value = cursor.remove_before();
+        queue.push(1).unwrap();
+        queue.push(2).unwrap();
 
         // assert
-        assert_eq!(value, Some(4));
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, (1..4).collect());
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(2));
+        assert!(queue.is_empty());
     }
 
     #[test]
-    fn test_cursor_remove_before_end() {
+    fn test_pop_timeout() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3, 4]);
-        let mut cursor = list.cursor_mut();
-        assert_eq!(cursor.index(), None);
+        let queue = sync::BlockingQueue::new();
+
+        // act & assert
+        assert_eq!(
+            queue.pop_timeout(Duration::from_millis(10)),
+            Err(sync::PopTimeoutError::Timeout)
+        );
+        queue.push(1).unwrap();
+        assert_eq!(queue.pop_timeout(Duration::from_millis(10)), Ok(1));
+        queue.close();
+        assert_eq!(
+            queue.pop_timeout(Duration::from_millis(10)),
+            Err(sync::PopTimeoutError::Closed)
+        );
+    }
+
+    #[test]
+    fn test_close() {
+        // arrange
+        let queue = sync::BlockingQueue::new();
+        queue.push(1).unwrap();
 
         // act
-        let value = cursor.remove_before();
+        queue.close();
 
         // assert
-        assert_eq!(value, Some(4));
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, (1..4).collect());
+        assert!(queue.is_closed());
+        assert_eq!(queue.push(2), Err(2));
+        // the remaining elements are still handed out
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), None);
     }
 
     #[test]
-    fn test_cursor_remove_before_single_element() {
+    fn test_close_wakes_waiting() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([4]);
-        let mut cursor = list.cursor_mut();
-        assert_eq!(cursor.index(), None);
+        let queue = Arc::new(sync::BlockingQueue::<i32>::new());
+        let full = Arc::new(sync::BlockingQueue::bounded(1));
+        full.push(0).unwrap();
 
         // act
-        let value = cursor.remove_before();
+        let consumer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.pop())
+        };
+        let producer = {
+            let full = Arc::clone(&full);
+            thread::spawn(move || full.push(1))
+        };
+        thread::sleep(Duration::from_millis(20));
+        queue.close();
+        full.close();
 
         // assert
-        assert_eq!(value, Some(4));
-        assert!(list.is_empty());
-        assert_eq!(list, doubly_linked_list::LinkedList::new());
+        assert_eq!(consumer.join().unwrap(), None);
+        assert_eq!(producer.join().unwrap(), Err(1));
     }
 
     #[test]
-    fn test_cursor_split_before_ghost() {
+    fn test_bounded_backpressure() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3, 4, 5, 6]);
-        let mut cursor = list.cursor_mut();
+        let queue = Arc::new(sync::BlockingQueue::bounded(4));
+        let producers: Vec<_> = (0..4)
+            .map(|p| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for i in 0..1_000 {
+                        queue.push(p * 1_000 + i).unwrap();
+                        assert!(queue.len() <= 4);
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    let mut popped = Vec::new();
+                    while let Some(elem) = queue.pop() {
+                        popped.push(elem);
+                    }
+                    popped
+                })
+            })
+            .collect();
 
         // act
-        let split = cursor.split_before();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        queue.close();
+        let mut popped: Vec<i32> = consumers
+            .into_iter()
+            .flat_map(|consumer| consumer.join().unwrap())
+            .collect();
 
         // assert
-        assert_eq!(split.len(), 6);
-        assert_eq!(split, (1..7).collect());
-        assert!(cursor.current().is_none());
-        assert!(cursor.index().is_none());
+        popped.sort();
+        assert_eq!(popped, (0..4_000).collect::<Vec<_>>());
+        assert_eq!(queue.capacity(), Some(4));
+    }
 
-        // after split the cursor has no list content anymore as it has been
-        // moved out to `split`
+    #[test]
+    #[should_panic(expected = "capacity of at least 1")]
+    fn test_bounded_zero_capacity() {
+        // act
+        sync::BlockingQueue::<i32>::bounded(0);
+    }
+
+    // counts how often it was dropped, to find leaked or double freed elements
+    struct DropCounter(Arc<AtomicUsize>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_lock_free_queue_fifo() {
         // arrange
-        cursor.move_next();
+        let queue = sync::LockFreeQueue::new();
+
+        // act
+        queue.push(1);
+        queue.push(2);
+        queue.push(3);
 
         // assert
-        assert!(cursor.current().is_none());
-        assert!(cursor.index().is_none());
+        assert!(!queue.is_empty());
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(2));
+        queue.push(4);
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), Some(4));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.is_empty());
     }
 
     #[test]
-    fn test_cursor_split_before_general() {
+    fn test_epoch_idle_record_garbage() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3, 4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        cursor.move_next();
-        cursor.move_next();
+        let drops = Arc::new(AtomicUsize::new(0));
+        let retire = |guard: &sync::epoch::Guard<'_>| unsafe {
+            guard.retire(Box::into_raw(Box::new(DropCounter(Arc::clone(&drops)))));
+        };
+        let collector = sync::epoch::Collector::new();
+        // two records, the first one is never acquired again afterwards, as
+        // records are taken from the most recent one on
+        let idle = collector.pin();
+        let busy = collector.pin();
+        retire(&idle);
+        drop(idle);
+        drop(busy);
 
         // act
-        let split = cursor.split_before();
+        for _ in 0..4 {
+            let guard = collector.pin();
+            retire(&guard);
+        }
 
         // assert
-        assert_eq!(split.len(), 2);
-        assert_eq!(split, (1..3).collect());
-        // cursor is not on the ghost but on the head of the other split half
-        // that remains in the cursor
-        assert_eq!(cursor.current(), Some(&mut 3));
-        assert_eq!(cursor.index(), Some(0));
-        assert_eq!(cursor.peek_next(), Some(&mut 4));
-        assert_eq!(cursor.peek_prev(), None);
+        // the garbage of the idle record and all but the two most recent
+        // retires are freed before the collector is dropped
+        assert_eq!(drops.load(Ordering::SeqCst), 3);
+        drop(collector);
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
     }
 
     #[test]
-    fn test_cursor_split_after_ghost() {
+    fn test_lock_free_queue_drop() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3, 4, 5, 6]);
-        let mut cursor = list.cursor_mut();
+        let drops = Arc::new(AtomicUsize::new(0));
+        let queue: sync::LockFreeQueue<_> = (0..1_000)
+            .map(|_| DropCounter(Arc::clone(&drops)))
+            .collect();
 
         // act
-        let split = cursor.split_after();
+        for _ in 0..400 {
+            drop(queue.pop());
+        }
+        let popped = drops.load(Ordering::SeqCst);
+        drop(queue);
 
         // assert
-        assert_eq!(split.len(), 6);
-        assert_eq!(split, (1..7).collect());
-        assert!(cursor.current().is_none());
-        assert!(cursor.index().is_none());
+        assert_eq!(popped, 400);
+        assert_eq!(drops.load(Ordering::SeqCst), 1_000);
+    }
 
-        // after split the cursor has no list content anymore as it has been
-        // moved out to `split`
+    #[test]
+    fn test_lock_free_queue_stress() {
         // arrange
-        cursor.move_next();
+        const PRODUCERS: usize = 4;
+        const CONSUMERS: usize = 4;
+        const ELEMS: usize = 20_000;
+        let queue = Arc::new(sync::LockFreeQueue::new());
+        let done = Arc::new(AtomicBool::new(false));
+
+        // act
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for i in 0..ELEMS {
+                        queue.push((p, i));
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                let done = Arc::clone(&done);
+                thread::spawn(move || {
+                    let mut popped = Vec::new();
+                    loop {
+                        match queue.pop() {
+                            Some(elem) => popped.push(elem),
+                            None if done.load(Ordering::SeqCst) => {
+                                break;
+                            },
+                            None => thread::yield_now(),
+                        }
+                    }
+                    popped
+                })
+            })
+            .collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        done.store(true, Ordering::SeqCst);
+        let popped: Vec<Vec<(usize, usize)>> = consumers
+            .into_iter()
+            .map(|consumer| consumer.join().unwrap())
+            .collect();
 
         // assert
-        assert!(cursor.current().is_none());
-        assert!(cursor.index().is_none());
+        // every consumer sees the elements of a producer in the order they
+        // were pushed
+        for elems in &popped {
+            let mut last = [None; PRODUCERS];
+            for &(p, i) in elems {
+                assert!(last[p] < Some(i));
+                last[p] = Some(i);
+            }
+        }
+        let mut all: Vec<_> = popped.into_iter().flatten().collect();
+        all.sort();
+        let expected: Vec<_> = (0..PRODUCERS)
+            .flat_map(|p| (0..ELEMS).map(move |i| (p, i)))
+            .collect();
+        assert_eq!(all, expected);
+        assert!(queue.is_empty());
     }
 
     #[test]
-    fn test_cursor_split_after_general() {
+    fn test_lock_free_queue_stress_drop() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3, 4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        cursor.move_next();
-        cursor.move_next();
+        let drops = Arc::new(AtomicUsize::new(0));
+        let queue = Arc::new(sync::LockFreeQueue::new());
 
         // act
-        let split = cursor.split_after();
+        // every thread pushes two and pops one element, the rest is dropped
+        // together with the queue
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                let drops = Arc::clone(&drops);
+                thread::spawn(move || {
+                    for _ in 0..5_000 {
+                        queue.push(DropCounter(Arc::clone(&drops)));
+                        queue.push(DropCounter(Arc::clone(&drops)));
+                        drop(queue.pop());
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        drop(queue);
 
         // assert
-        assert_eq!(split.len(), 3);
-        assert_eq!(split, (4..7).collect());
-        // cursor is not on the ghost but on the head of the other split half
-        // that remains in the cursor
-        assert_eq!(cursor.current(), Some(&mut 3));
-        assert_eq!(cursor.index(), Some(2));
-        assert_eq!(cursor.peek_prev(), Some(&mut 2));
-        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(drops.load(Ordering::SeqCst), 8 * 5_000 * 2);
     }
 
     #[test]
-    fn test_cursor_splice_before_ghost_empty() {
+    fn test_lock_free_stack_lifo() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let other_list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
+        let mut stack = sync::LockFreeStack::new();
 
         // act
-        cursor.splice_before(other_list);
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
 
         // assert
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, list_from(&[1, 2, 3]));
+        assert_eq!(stack.peek(), Some(&3));
+        if let Some(elem) = stack.peek_mut() {
+            *elem = 30;
+        }
+        assert_eq!(stack.pop(), Some(30));
+        assert_eq!(stack.pop(), Some(2));
+        stack.push(4);
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.peek(), None);
+        assert!(stack.is_empty());
     }
 
     #[test]
-    fn test_cursor_splice_before_general_empty() {
+    fn test_lock_free_stack_drop() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let other_list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        cursor.move_next();
+        let drops = Arc::new(AtomicUsize::new(0));
+        let stack: sync::LockFreeStack<_> = (0..1_000)
+            .map(|_| DropCounter(Arc::clone(&drops)))
+            .collect();
 
         // act
-        cursor.splice_before(other_list);
+        for _ in 0..400 {
+            drop(stack.pop());
+        }
+        let popped = drops.load(Ordering::SeqCst);
+        drop(stack);
 
         // assert
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, list_from(&[1, 2, 3]));
+        assert_eq!(popped, 400);
+        assert_eq!(drops.load(Ordering::SeqCst), 1_000);
     }
 
     #[test]
-    fn test_cursor_splice_before_general_start() {
+    fn test_lock_free_stack_free_list() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let mut other_list: doubly_linked_list::LinkedList<i32> =
-            doubly_linked_list::LinkedList::new();
-        other_list.extend([4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        cursor.move_next();
-        assert_eq!(cursor.index(), Some(1));
+        // a pool of ids shared by several threads, which take an id, use it
+        // and give it back, such that nodes are popped and pushed all the time
+        const IDS: usize = 16;
+        let pool: Arc<sync::LockFreeStack<usize>> = Arc::new((0..IDS).collect());
+        let in_use: Arc<Vec<AtomicBool>> =
+            Arc::new((0..IDS).map(|_| AtomicBool::new(false)).collect());
 
         // act
-        cursor.splice_before(other_list);
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                let in_use = Arc::clone(&in_use);
+                thread::spawn(move || {
+                    for _ in 0..20_000 {
+                        if let Some(id) = pool.pop() {
+                            // no id is handed out twice at the same time
+                            assert!(!in_use[id].swap(true, Ordering::SeqCst));
+                            in_use[id].store(false, Ordering::SeqCst);
+                            pool.push(id);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
 
         // assert
-        assert_eq!(list.len(), 6);
-        assert_eq!(list, list_from(&[1, 4, 5, 6, 2, 3]));
+        let mut ids = Vec::new();
+        while let Some(id) = pool.pop() {
+            ids.push(id);
+        }
+        ids.sort();
+        assert_eq!(ids, (0..IDS).collect::<Vec<_>>());
     }
 
     #[test]
-    fn test_cursor_splice_before_general_end() {
+    fn test_lock_free_stack_stress_drop() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let mut other_list: doubly_linked_list::LinkedList<i32> =
-            doubly_linked_list::LinkedList::new();
-        other_list.extend([4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_prev();
-        assert_eq!(cursor.index(), Some(2));
+        let drops = Arc::new(AtomicUsize::new(0));
+        let stack = Arc::new(sync::LockFreeStack::new());
 
         // act
-        cursor.splice_before(other_list);
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let stack = Arc::clone(&stack);
+                let drops = Arc::clone(&drops);
+                thread::spawn(move || {
+                    for _ in 0..5_000 {
+                        stack.push(DropCounter(Arc::clone(&drops)));
+                        stack.push(DropCounter(Arc::clone(&drops)));
+                        drop(stack.pop());
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        drop(stack);
 
         // assert
-        assert_eq!(list.len(), 6);
-        assert_eq!(list, list_from(&[1, 2, 4, 5, 6, 3]));
+        assert_eq!(drops.load(Ordering::SeqCst), 8 * 5_000 * 2);
     }
+}
 
-    #[test]
-    fn test_cursor_splice_before_first() {
+#[cfg(test)]
+mod test_conformance {
+    use super::*;
+
+    fn check_stack_empty<S: Stack<i32> + Default>() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let mut other_list: doubly_linked_list::LinkedList<i32> =
-            doubly_linked_list::LinkedList::new();
-        other_list.extend([4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        assert_eq!(cursor.index(), Some(0));
+        let mut stack = S::default();
+
+        // act & assert
+        assert!(stack.is_empty());
+        assert_eq!(stack.len(), 0);
+        assert_eq!(stack.peek(), None);
+        assert_eq!(stack.peek_mut(), None);
+        assert_eq!(stack.pop(), None);
+    }
+
+    fn check_stack_lifo<S: Stack<i32> + Default>() {
+        // arrange
+        let mut stack = S::default();
 
         // act
-        cursor.splice_before(other_list);
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
 
         // assert
-        assert_eq!(list.len(), 6);
-        assert_eq!(list, list_from(&[4, 5, 6, 1, 2, 3]));
+        assert_eq!(stack.len(), 3);
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+
+        // act & assert
+        stack.push(4);
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+        assert!(stack.is_empty());
     }
 
-    #[test]
-    fn test_cursor_splice_before_ghost() {
+    fn check_stack_peek<S: Stack<i32> + Default>() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let mut other_list: doubly_linked_list::LinkedList<i32> =
-            doubly_linked_list::LinkedList::new();
-        other_list.extend([4, 5, 6]);
-        let mut cursor = list.cursor_mut();
+        let mut stack = S::default();
+        stack.push(1);
+        stack.push(2);
 
         // act
-        cursor.splice_before(other_list);
+        if let Some(value) = stack.peek_mut() {
+            *value = 5;
+        }
 
         // assert
-        assert_eq!(list.len(), 6);
-        assert_eq!(list, (1..7).collect());
+        assert_eq!(stack.peek(), Some(&5));
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack.pop(), Some(5));
+        assert_eq!(stack.peek(), Some(&1));
     }
 
-    #[test]
-    fn test_cursor_splice_after_ghost_empty() {
+    fn check_stack_drop_long<S: Stack<i32> + Default>() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let other_list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
+        let mut stack = S::default();
+        for i in 0..100_000 {
+            stack.push(i);
+        }
 
-        // act
-        cursor.splice_after(other_list);
+        // act & assert
+        // dropping must not recurse through all nodes
+        assert_eq!(stack.len(), 100_000);
+        drop(stack);
+    }
 
-        // assert
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, list_from(&[1, 2, 3]));
+    macro_rules! stack_conformance {
+        ($($name:ident: $ty:ty),+ $(,)?) => {$(
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn test_empty() {
+                    check_stack_empty::<$ty>();
+                }
+
+                #[test]
+                fn test_lifo() {
+                    check_stack_lifo::<$ty>();
+                }
+
+                #[test]
+                fn test_peek() {
+                    check_stack_peek::<$ty>();
+                }
+
+                #[test]
+                fn test_drop_long() {
+                    check_stack_drop_long::<$ty>();
+                }
+            }
+        )+};
     }
 
-    #[test]
-    fn test_cursor_splice_after_general_empty() {
+    stack_conformance! {
+        stack_list: stack::List<i32>,
+        better_stack_list: better_stack::List<i32>,
+        doubly_linked_list_stack: doubly_linked_list::LinkedList<i32>,
+        unrolled_linked_list_stack: unrolled_linked_list::UnrolledList<i32, 4>,
+        xor_linked_list_stack: xor_linked_list::XorLinkedList<i32>,
+        circular_list_stack: circular_list::CircularList<i32>,
+        arena_list_stack: arena_list::ArenaList<i32>,
+    }
+
+    fn check_queue_empty<Q: Queue<i32> + Default>() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let other_list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        cursor.move_next();
+        let mut queue = Q::default();
+
+        // act & assert
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+        assert_eq!(queue.front(), None);
+        assert_eq!(queue.front_mut(), None);
+        assert_eq!(queue.pop_front(), None);
+    }
+
+    fn check_queue_fifo<Q: Queue<i32> + Default>() {
+        // arrange
+        let mut queue = Q::default();
 
         // act
-        cursor.splice_after(other_list);
+        queue.push_back(1);
+        queue.push_back(2);
+        queue.push_back(3);
 
         // assert
-        assert_eq!(list.len(), 3);
-        assert_eq!(list, list_from(&[1, 2, 3]));
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.pop_front(), Some(1));
+        assert_eq!(queue.pop_front(), Some(2));
+
+        // act & assert
+        // refill after (almost) running empty
+        queue.push_back(4);
+        assert_eq!(queue.pop_front(), Some(3));
+        assert_eq!(queue.pop_front(), Some(4));
+        assert_eq!(queue.pop_front(), None);
+        assert!(queue.is_empty());
+
+        // act & assert
+        queue.push_back(5);
+        assert_eq!(queue.front(), Some(&5));
+        assert_eq!(queue.len(), 1);
     }
 
-    #[test]
-    fn test_cursor_splice_after_general_start() {
+    fn check_queue_front<Q: Queue<i32> + Default>() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let mut other_list: doubly_linked_list::LinkedList<i32> =
-            doubly_linked_list::LinkedList::new();
-        other_list.extend([4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        cursor.move_next();
-        assert_eq!(cursor.index(), Some(1));
+        let mut queue = Q::default();
+        queue.push_back(1);
+        queue.push_back(2);
 
         // act
-        cursor.splice_after(other_list);
+        if let Some(value) = queue.front_mut() {
+            *value = 5;
+        }
 
         // assert
-        assert_eq!(list.len(), 6);
-        assert_eq!(list, list_from(&[1, 2, 4, 5, 6, 3]));
+        assert_eq!(queue.front(), Some(&5));
+        assert_eq!(queue.pop_front(), Some(5));
+        assert_eq!(queue.front(), Some(&2));
     }
 
-    #[test]
-    fn test_cursor_splice_after_general_end() {
+    fn check_queue_drop_long<Q: Queue<i32> + Default>() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let mut other_list: doubly_linked_list::LinkedList<i32> =
-            doubly_linked_list::LinkedList::new();
-        other_list.extend([4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_prev();
-        assert_eq!(cursor.index(), Some(2));
+        let mut queue = Q::default();
+        for i in 0..100_000 {
+            queue.push_back(i);
+        }
+
+        // act & assert
+        // dropping must not recurse through all nodes
+        assert_eq!(queue.len(), 100_000);
+        drop(queue);
+    }
+
+    macro_rules! queue_conformance {
+        ($($name:ident: $ty:ty),+ $(,)?) => {$(
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn test_empty() {
+                    check_queue_empty::<$ty>();
+                }
+
+                #[test]
+                fn test_fifo() {
+                    check_queue_fifo::<$ty>();
+                }
+
+                #[test]
+                fn test_front() {
+                    check_queue_front::<$ty>();
+                }
+
+                #[test]
+                fn test_drop_long() {
+                    check_queue_drop_long::<$ty>();
+                }
+            }
+        )+};
+    }
+
+    queue_conformance! {
+        unsafe_queue_list: unsafe_queue::List<i32>,
+        doubly_linked_list_queue: doubly_linked_list::LinkedList<i32>,
+        unrolled_linked_list_queue: unrolled_linked_list::UnrolledList<i32, 4>,
+        xor_linked_list_queue: xor_linked_list::XorLinkedList<i32>,
+        circular_list_queue: circular_list::CircularList<i32>,
+        arena_list_queue: arena_list::ArenaList<i32>,
+    }
+
+    fn check_deque_both_ends<D: Deque<i32> + Default>() {
+        // arrange
+        let mut deque = D::default();
 
         // act
-        cursor.splice_after(other_list);
+        deque.push_front(2);
+        deque.push_back(3);
+        deque.push_front(1);
+        deque.push_back(4);
 
         // assert
-        assert_eq!(list.len(), 6);
-        assert_eq!(list, (1..7).collect());
+        assert_eq!(deque.len(), 4);
+        assert_eq!(deque.front(), Some(&1));
+        assert_eq!(deque.back(), Some(&4));
+        assert_eq!(deque.pop_back(), Some(4));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.back(), None);
+        assert!(deque.is_empty());
     }
 
-    #[test]
-    fn test_cursor_splice_after_last() {
+    fn check_deque_back<D: Deque<i32> + Default>() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let mut other_list: doubly_linked_list::LinkedList<i32> =
-            doubly_linked_list::LinkedList::new();
-        other_list.extend([4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_prev();
-        assert_eq!(cursor.index(), Some(2));
+        let mut deque = D::default();
+        deque.push_back(1);
+        deque.push_back(2);
 
         // act
-        cursor.splice_after(other_list);
+        if let Some(value) = deque.back_mut() {
+            *value = 5;
+        }
 
         // assert
-        assert_eq!(list.len(), 6);
-        assert_eq!(list, (1..7).collect());
+        assert_eq!(deque.back(), Some(&5));
+        assert_eq!(deque.pop_back(), Some(5));
+        assert_eq!(deque.back(), Some(&1));
+        assert_eq!(deque.front(), Some(&1));
     }
 
-    #[test]
-    fn test_cursor_splice_after_ghost() {
+    fn check_deque_as_stack<D: Deque<i32> + Default>() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let mut other_list: doubly_linked_list::LinkedList<i32> =
-            doubly_linked_list::LinkedList::new();
-        other_list.extend([4, 5, 6]);
-        let mut cursor = list.cursor_mut();
+        let mut deque = D::default();
 
         // act
-        cursor.splice_after(other_list);
+        // using only the back works just like a stack
+        deque.push_back(1);
+        deque.push_back(2);
 
         // assert
-        assert_eq!(list.len(), 6);
-        assert_eq!(list, list_from(&[4, 5, 6, 1, 2, 3]));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert_eq!(deque.pop_back(), Some(1));
+        assert_eq!(deque.pop_front(), None);
     }
 
-    fn check_links<T: Eq + std::fmt::Debug>(list: &doubly_linked_list::LinkedList<T>) {
-        let from_front: Vec<_> = list.iter().collect();
-        let from_back: Vec<_> = list.iter().rev().collect();
-        let re_reved: Vec<_> = from_back.into_iter().rev().collect();
+    macro_rules! deque_conformance {
+        ($($name:ident: $ty:ty),+ $(,)?) => {$(
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn test_both_ends() {
+                    check_deque_both_ends::<$ty>();
+                }
+
+                #[test]
+                fn test_back() {
+                    check_deque_back::<$ty>();
+                }
+
+                #[test]
+                fn test_as_stack() {
+                    check_deque_as_stack::<$ty>();
+                }
+            }
+        )+};
+    }
 
-        assert_eq!(from_front, re_reved);
+    deque_conformance! {
+        doubly_linked_list_deque: doubly_linked_list::LinkedList<i32>,
+        unrolled_linked_list_deque: unrolled_linked_list::UnrolledList<i32, 4>,
+        xor_linked_list_deque: xor_linked_list::XorLinkedList<i32>,
+        circular_list_deque: circular_list::CircularList<i32>,
+        arena_list_deque: arena_list::ArenaList<i32>,
     }
 
-    #[test]
-    fn test_iter_splice_after() {
+    fn check_persistent_stack_empty<P: PersistentStack<i32> + Default>() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let mut other_list: doubly_linked_list::LinkedList<i32> =
-            doubly_linked_list::LinkedList::new();
-        other_list.extend([4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        cursor.move_next();
-        assert_eq!(cursor.index(), Some(1));
+        let list = P::default();
+
+        // act & assert
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.head(), None);
+        assert!(list.tail().is_empty());
+    }
+
+    fn check_persistent_stack_versions<P: PersistentStack<i32> + Default>() {
+        // arrange
+        let empty = P::default();
 
         // act
-        cursor.splice_after(other_list);
+        let one = empty.prepend(1);
+        let two = one.prepend(2);
+        let other_two = one.prepend(3);
 
         // assert
-        check_links(&list);
+        // no operation changes an older version
+        assert!(empty.is_empty());
+        assert_eq!(one.len(), 1);
+        assert_eq!(one.head(), Some(&1));
+        assert_eq!(two.len(), 2);
+        assert_eq!(two.head(), Some(&2));
+        assert_eq!(other_two.head(), Some(&3));
+        assert_eq!(two.tail().head(), Some(&1));
+        assert_eq!(other_two.tail().head(), Some(&1));
+        assert_eq!(two.tail().tail().head(), None);
+        assert_eq!(two.head(), Some(&2));
     }
 
-    #[test]
-    fn test_iter_splice_before() {
+    fn check_persistent_stack_drop_long<P: PersistentStack<i32> + Default>() {
         // arrange
-        let mut list: doubly_linked_list::LinkedList<i32> = doubly_linked_list::LinkedList::new();
-        list.extend([1, 2, 3]);
-        let mut other_list: doubly_linked_list::LinkedList<i32> =
-            doubly_linked_list::LinkedList::new();
-        other_list.extend([4, 5, 6]);
-        let mut cursor = list.cursor_mut();
-        cursor.move_next();
-        cursor.move_next();
-        assert_eq!(cursor.index(), Some(1));
+        let mut list = P::default();
+        for i in 0..100_000 {
+            list = list.prepend(i);
+        }
+        let shared = list.tail();
 
-        // act
-        cursor.splice_before(other_list);
+        // act & assert
+        // dropping must not recurse through all nodes, neither when the nodes
+        // are shared nor when they are not
+        assert_eq!(list.len(), 100_000);
+        drop(list);
+        assert_eq!(shared.head(), Some(&99_998));
+        drop(shared);
+    }
 
-        // assert
-        check_links(&list);
+    macro_rules! persistent_stack_conformance {
+        ($($name:ident: $ty:ty),+ $(,)?) => {$(
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn test_empty() {
+                    check_persistent_stack_empty::<$ty>();
+                }
+
+                #[test]
+                fn test_versions() {
+                    check_persistent_stack_versions::<$ty>();
+                }
+
+                #[test]
+                fn test_drop_long() {
+                    check_persistent_stack_drop_long::<$ty>();
+                }
+            }
+        )+};
+    }
+
+    persistent_stack_conformance! {
+        persistent_stack_list: persistent_stack::List<i32>,
+        sync_persistent_stack_list: persistent_stack::SyncList<i32>,
     }
 }
@@ -0,0 +1,280 @@
+// Stable stand-in for the unstable `std::alloc::Allocator` API.
+//
+// The lists allocate their nodes through it, so they can be handed a bump,
+// pool or counting allocator instead of the global heap.
+
+use std::alloc::{self, Layout};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::ptr::{self, NonNull};
+
+// returned by an `Allocator` which could not satisfy a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl std::error::Error for AllocError {}
+
+// returned by the `try_*` methods of the lists, like `TryReserveError` of the
+// std collections, but it hands back the element that could not be inserted
+pub struct TryReserveError<T> {
+    elem: T,
+    kind: TryReserveErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryReserveErrorKind {
+    // e.g. a full bounded queue or an arena which ran out of indices
+    CapacityOverflow,
+    AllocError { layout: Layout },
+}
+
+impl<T> TryReserveError<T> {
+    pub(crate) fn capacity_overflow(elem: T) -> Self {
+        Self {
+            elem,
+            kind: TryReserveErrorKind::CapacityOverflow,
+        }
+    }
+
+    pub(crate) fn alloc_error(elem: T, layout: Layout) -> Self {
+        Self {
+            elem,
+            kind: TryReserveErrorKind::AllocError { layout },
+        }
+    }
+
+    pub fn kind(&self) -> TryReserveErrorKind {
+        self.kind
+    }
+
+    pub fn elem(&self) -> &T {
+        &self.elem
+    }
+
+    pub fn into_inner(self) -> T {
+        self.elem
+    }
+
+    // what the infallible methods do instead of returning the error, i.e. the same as `Vec` does
+    pub(crate) fn handle(self) -> ! {
+        match self.kind {
+            TryReserveErrorKind::CapacityOverflow => panic!("capacity overflow"),
+            TryReserveErrorKind::AllocError { layout } => alloc::handle_alloc_error(layout),
+        }
+    }
+}
+
+// `T` doesn't have to be `Debug` to debug the error, just like `std::sync::mpsc::SendError`
+impl<T> fmt::Debug for TryReserveError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryReserveError")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for TryReserveError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            TryReserveErrorKind::CapacityOverflow => {
+                f.write_str("the list can't hold any more elements")
+            }
+            TryReserveErrorKind::AllocError { .. } => f.write_str("memory allocation failed"),
+        }
+    }
+}
+
+impl<T> std::error::Error for TryReserveError<T> {}
+
+// Hands out and takes back blocks of memory, like `std::alloc::Allocator`.
+//
+// Safety: a block returned by `allocate` must fit the requested layout, must
+// not overlap any other live block and must stay valid until it is given back.
+//
+// The lists move nodes between lists of the same allocator type (`append`,
+// splicing, `split_off` and friends), so any value of the type that is still
+// alive must be able to free a block allocated by another one. Allocators
+// whose values own disjoint pools can not be used with those operations.
+#[allow(clippy::missing_safety_doc)]
+pub unsafe trait Allocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+    // Safety: `ptr` was returned by `allocate` with the same `layout` and was
+    // not given back yet.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+// `std::alloc::alloc` and `std::alloc::dealloc`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            // zero sized blocks never touch the heap, any aligned pointer will do
+            let dangling = ptr::null_mut::<u8>().wrapping_add(layout.align());
+            // SAFETY: an alignment is never zero
+            let dangling = unsafe { NonNull::new_unchecked(dangling) };
+            return Ok(NonNull::slice_from_raw_parts(dangling, 0));
+        }
+        // SAFETY: the layout isn't zero sized
+        let ptr = unsafe { alloc::alloc(layout) };
+        NonNull::new(ptr)
+            .map(|ptr| NonNull::slice_from_raw_parts(ptr, layout.size()))
+            .ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() != 0 {
+            alloc::dealloc(ptr.as_ptr(), layout);
+        }
+    }
+}
+
+unsafe impl<A: Allocator + ?Sized> Allocator for &A {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        (**self).deallocate(ptr, layout)
+    }
+}
+
+// moves `value` into a fresh block of `alloc`, handing it back if there's no memory left
+pub(crate) fn try_allocate<T, A: Allocator + ?Sized>(alloc: &A, value: T) -> Result<NonNull<T>, T> {
+    match alloc.allocate(Layout::new::<T>()) {
+        Ok(block) => {
+            let ptr = block.cast::<T>();
+            // SAFETY: the block fits a `T` and nobody else knows about it
+            unsafe { ptr.as_ptr().write(value) };
+            Ok(ptr)
+        }
+        Err(AllocError) => Err(value),
+    }
+}
+
+// like `try_allocate`, but for the nodes of a list, which only hand their element back
+pub(crate) fn try_allocate_node<N, T, A: Allocator + ?Sized>(
+    alloc: &A,
+    node: N,
+    elem: impl FnOnce(N) -> T,
+) -> Result<NonNull<N>, TryReserveError<T>> {
+    try_allocate(alloc, node)
+        .map_err(|node| TryReserveError::alloc_error(elem(node), Layout::new::<N>()))
+}
+
+// like `try_allocate`, but running out of memory aborts, just like `Box::new` does
+pub(crate) fn allocate<T, A: Allocator + ?Sized>(alloc: &A, value: T) -> NonNull<T> {
+    match try_allocate(alloc, value) {
+        Ok(ptr) => ptr,
+        Err(_) => alloc::handle_alloc_error(Layout::new::<T>()),
+    }
+}
+
+// moves the value out of its block and gives the block back to `alloc`
+//
+// SAFETY: `ptr` must come from `allocate`/`try_allocate` with an allocator that can free it
+// (see the safety section of `Allocator`), and must not be used afterwards
+pub(crate) unsafe fn deallocate<T, A: Allocator + ?Sized>(alloc: &A, ptr: NonNull<T>) -> T {
+    let value = ptr.as_ptr().read();
+    alloc.deallocate(ptr.cast(), Layout::new::<T>());
+    value
+}
+
+// minimal owning pointer allocated through an `Allocator`, like `Box<T, A>` on
+// nightly
+pub struct Box<T, A: Allocator = Global> {
+    ptr: NonNull<T>,
+    alloc: A,
+    _boo: PhantomData<T>,
+}
+
+impl<T> Box<T> {
+    pub fn new(value: T) -> Self {
+        Self::new_in(value, Global)
+    }
+}
+
+impl<T, A: Allocator> Box<T, A> {
+    pub fn new_in(value: T, alloc: A) -> Self {
+        let ptr = allocate(&alloc, value);
+        Self {
+            ptr,
+            alloc,
+            _boo: PhantomData,
+        }
+    }
+
+    // like `new_in`, but hands the value back when the allocator is out of memory
+    pub fn try_new_in(value: T, alloc: A) -> Result<Self, T> {
+        let ptr = try_allocate(&alloc, value)?;
+        Ok(Self {
+            ptr,
+            alloc,
+            _boo: PhantomData,
+        })
+    }
+
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    // moves the value out and frees the box
+    pub fn into_inner(self) -> T {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: the box is never touched again, so the allocator is read out exactly once
+        // and the block is freed exactly once
+        unsafe {
+            let alloc = ptr::read(&this.alloc);
+            deallocate(&alloc, this.ptr)
+        }
+    }
+}
+
+impl<T, A: Allocator> Drop for Box<T, A> {
+    fn drop(&mut self) {
+        // SAFETY: the box owns both the value and its block
+        unsafe {
+            ptr::drop_in_place(self.ptr.as_ptr());
+            self.alloc.deallocate(self.ptr.cast(), Layout::new::<T>());
+        }
+    }
+}
+
+impl<T, A: Allocator> Deref for Box<T, A> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the box owns a valid value
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T, A: Allocator> DerefMut for Box<T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the box owns a valid value and is borrowed mutably
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for Box<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+// +----------------------+
+// | Send and Sync Traits |
+// +----------------------+
+
+unsafe impl<T: Send, A: Allocator + Send> Send for Box<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for Box<T, A> {}
@@ -0,0 +1,1330 @@
+// Doubly linked list, which keeps all of its nodes in a single `Vec`.
+//
+// The nodes link each other by their index in the `Vec` instead of by pointer,
+// so the whole list is one allocation and no unsafe code is needed at all. A
+// removed node leaves a free slot behind, the free slots are chained into a
+// free-list of their own and get reused by the next insertions. `compact`
+// moves the nodes back into list order without any free slots in between.
+//
+// As the nodes live in the arena of their list, moving nodes into another list
+// (`append`, `split_off`, splicing) has to move the elements one by one.
+
+#![forbid(unsafe_code)]
+
+use std::alloc::Layout;
+use std::cmp::Ordering;
+use std::collections::TryReserveError as VecTryReserveError;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::{Bound, Index, IndexMut, RangeBounds};
+
+use super::allocator::TryReserveError;
+use super::{Deque, Queue, Stack};
+
+pub struct ArenaList<T> {
+    slots: Vec<Slot<T>>,
+    front: Link,
+    back: Link,
+    // first slot of the free-list
+    free: Link,
+    len: usize,
+}
+
+// index of a slot
+type Link = Option<u32>;
+
+enum Slot<T> {
+    Occupied(Node<T>),
+    // next slot of the free-list
+    Free(Link),
+}
+
+struct Node<T> {
+    front: Link,
+    back: Link,
+    elem: T,
+}
+
+impl<T> ArenaList<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        ArenaList {
+            slots: Vec::with_capacity(capacity),
+            front: None,
+            back: None,
+            free: None,
+            len: 0,
+        }
+    }
+
+    // number of elements the list can hold without allocating, which
+    // includes the free slots
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        let reused = additional.min(self.free_slots());
+        self.slots.reserve(additional - reused);
+    }
+
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), VecTryReserveError> {
+        let reused = additional.min(self.free_slots());
+        self.slots.try_reserve(additional - reused)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        self.link_between(None, self.front, elem);
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        self.link_between(self.back, None, elem);
+    }
+
+    // like `push_front`, but hands the element back instead of panicking or
+    // aborting when the arena can't grow any further
+    pub fn try_push_front(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        self.try_link_between(None, self.front, elem)?;
+        Ok(())
+    }
+
+    pub fn try_push_back(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        self.try_link_between(self.back, None, elem)?;
+        Ok(())
+    }
+
+    // pushes the elements to the back until one of them doesn't fit, that one
+    // is handed back and the rest of the iterator is dropped
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryReserveError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.try_push_back(elem)?;
+        }
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.front.map(|front| self.unlink(front))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.back.map(|back| self.unlink(back))
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.front.map(|front| &self.node(front).elem)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.front.map(|front| &mut self.node_mut(front).elem)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.back.map(|back| &self.node(back).elem)
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.back.map(|back| &mut self.node_mut(back).elem)
+    }
+
+    // keeps the allocated slots for the next elements
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.front = None;
+        self.back = None;
+        self.free = None;
+        self.len = 0;
+    }
+
+    fn node(&self, index: u32) -> &Node<T> {
+        match &self.slots[index as usize] {
+            Slot::Occupied(node) => node,
+            Slot::Free(_) => unreachable!("link to a free slot"),
+        }
+    }
+
+    fn node_mut(&mut self, index: u32) -> &mut Node<T> {
+        match &mut self.slots[index as usize] {
+            Slot::Occupied(node) => node,
+            Slot::Free(_) => unreachable!("link to a free slot"),
+        }
+    }
+
+    // mutable references to the elements in two different slots
+    fn elems_mut(&mut self, a: u32, b: u32) -> (&mut T, &mut T) {
+        let (a, b) = (a as usize, b as usize);
+        let (low, high) = self.slots.split_at_mut(a.max(b));
+        let (low, high) = match (&mut low[a.min(b)], &mut high[0]) {
+            (Slot::Occupied(low), Slot::Occupied(high)) => (&mut low.elem, &mut high.elem),
+            _ => unreachable!("link to a free slot"),
+        };
+        if a < b {
+            (low, high)
+        } else {
+            (high, low)
+        }
+    }
+
+    fn free_slots(&self) -> usize {
+        self.slots.len() - self.len
+    }
+
+    // Links a new node between the neighbours `front` and `back`, `None`
+    // stands for the ends of the list. Returns the slot of the new node.
+    fn link_between(&mut self, front: Link, back: Link, elem: T) -> u32 {
+        if self.free.is_none() {
+            // aborts just like `Vec::push` when running out of memory, which
+            // only leaves running out of indices to fail below
+            self.slots.reserve(1);
+        }
+        self.try_link_between(front, back, elem).unwrap_or_else(|_| panic!("arena list is full"))
+    }
+
+    fn try_link_between(
+        &mut self,
+        front: Link,
+        back: Link,
+        elem: T,
+    ) -> Result<u32, TryReserveError<T>> {
+        let new = match self.free {
+            Some(free) => {
+                let node = Slot::Occupied(Node { front, back, elem });
+                match std::mem::replace(&mut self.slots[free as usize], node) {
+                    Slot::Free(next_free) => self.free = next_free,
+                    Slot::Occupied(_) => unreachable!("occupied slot in the free-list"),
+                }
+                free
+            }
+            None => {
+                let new = u32::try_from(self.slots.len())
+                    .ok()
+                    .filter(|&new| new != u32::MAX);
+                let Some(new) = new else {
+                    return Err(TryReserveError::capacity_overflow(elem));
+                };
+                if self.slots.try_reserve(1).is_err() {
+                    // the `Vec` doesn't tell how much it asked for, but it
+                    // was at least one more slot
+                    let err = match Layout::array::<Slot<T>>(self.slots.len() + 1) {
+                        Ok(layout) => TryReserveError::alloc_error(elem, layout),
+                        Err(_) => TryReserveError::capacity_overflow(elem),
+                    };
+                    return Err(err);
+                }
+                self.slots.push(Slot::Occupied(Node { front, back, elem }));
+                new
+            }
+        };
+        match front {
+            Some(front) => self.node_mut(front).back = Some(new),
+            None => self.front = Some(new),
+        }
+        match back {
+            Some(back) => self.node_mut(back).front = Some(new),
+            None => self.back = Some(new),
+        }
+        self.len += 1;
+        Ok(new)
+    }
+
+    // unlinks the node in slot `index` and puts the slot onto the free-list
+    fn unlink(&mut self, index: u32) -> T {
+        let free = Slot::Free(self.free);
+        let node = match std::mem::replace(&mut self.slots[index as usize], free) {
+            Slot::Occupied(node) => node,
+            Slot::Free(_) => unreachable!("link to a free slot"),
+        };
+        self.free = Some(index);
+        match node.front {
+            Some(front) => self.node_mut(front).back = node.back,
+            None => self.front = node.back,
+        }
+        match node.back {
+            Some(back) => self.node_mut(back).front = node.front,
+            None => self.back = node.front,
+        }
+        self.len -= 1;
+        node.elem
+    }
+
+    // the slots of the nodes in list order
+    fn links(&self) -> Vec<u32> {
+        let mut links = Vec::with_capacity(self.len);
+        let mut cur = self.front;
+        while let Some(index) = cur {
+            links.push(index);
+            cur = self.node(index).back;
+        }
+        links
+    }
+
+    // Finds the node at `index` by walking from whichever end is closer.
+    fn node_at(&self, index: usize) -> Link {
+        if index >= self.len {
+            return None;
+        }
+        if index < self.len / 2 {
+            let mut node = self.front;
+            for _ in 0..index {
+                node = self.node(node?).back;
+            }
+            node
+        } else {
+            let mut node = self.back;
+            for _ in index + 1..self.len {
+                node = self.node(node?).front;
+            }
+            node
+        }
+    }
+}
+
+// +-------------------+
+// | Memory Management |
+// +-------------------+
+
+impl<T> ArenaList<T> {
+    // Moves the nodes into list order, such that the i-th element is in the
+    // i-th slot and there are no free slots left. Walking the list then walks
+    // the memory in order as well.
+    pub fn compact(&mut self) {
+        let mut old = std::mem::take(&mut self.slots);
+        let mut new_slots = Vec::with_capacity(old.capacity());
+        let mut cur = self.front;
+        while let Some(index) = cur {
+            let node = match std::mem::replace(&mut old[index as usize], Slot::Free(None)) {
+                Slot::Occupied(node) => node,
+                Slot::Free(_) => unreachable!("link to a free slot"),
+            };
+            cur = node.back;
+            let at = new_slots.len() as u32;
+            new_slots.push(Slot::Occupied(Node {
+                front: at.checked_sub(1),
+                back: Some(at + 1).filter(|_| cur.is_some()),
+                elem: node.elem,
+            }));
+        }
+        self.slots = new_slots;
+        self.front = Some(0).filter(|_| self.len > 0);
+        self.back = self.len.checked_sub(1).map(|back| back as u32);
+        self.free = None;
+    }
+
+    // compacts the list and gives back all memory which is not needed for
+    // its elements
+    pub fn shrink_to_fit(&mut self) {
+        self.compact();
+        self.slots.shrink_to_fit();
+    }
+}
+
+// +-----------------+
+// | Iterator Traits |
+// +-----------------+
+
+pub struct Iter<'a, T> {
+    list: &'a ArenaList<T>,
+    front: Link,
+    back: Link,
+    len: usize,
+}
+
+impl<T> ArenaList<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            list: self,
+            front: self.front,
+            back: self.back,
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArenaList<T> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let node = self.list.node(self.front?);
+        self.front = node.back;
+        Some(&node.elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let node = self.list.node(self.back?);
+        self.back = node.front;
+        Some(&node.elem)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+// Safe code can not hand out mutable references into the `Vec` in list order
+// one after the other, so all of them are taken up front (in slot order) and
+// then handed out in list order.
+//
+// That costs a `Vec` with an entry for every slot of the arena, free ones
+// included, which is allocated on every call of `iter_mut`. `iter` and the
+// cursors get by without it.
+pub struct IterMut<'a, T> {
+    // for every slot its links and the element, until it was handed out
+    slots: Vec<(Link, Link, Option<&'a mut T>)>,
+    front: Link,
+    back: Link,
+    len: usize,
+}
+
+impl<T> ArenaList<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let slots = self
+            .slots
+            .iter_mut()
+            .map(|slot| match slot {
+                Slot::Occupied(node) => (node.front, node.back, Some(&mut node.elem)),
+                Slot::Free(_) => (None, None, None),
+            })
+            .collect();
+        IterMut {
+            slots,
+            front: self.front,
+            back: self.back,
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArenaList<T> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let (_, back, elem) = &mut self.slots[self.front? as usize];
+        self.front = *back;
+        elem.take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let (front, _, elem) = &mut self.slots[self.back? as usize];
+        self.back = *front;
+        elem.take()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct IntoIter<T> {
+    list: ArenaList<T>,
+}
+
+impl<T> IntoIterator for ArenaList<T> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<T> Default for ArenaList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// the clone is compacted
+impl<T: Clone> Clone for ArenaList<T> {
+    fn clone(&self) -> Self {
+        let mut new_list = Self::with_capacity(self.len);
+        new_list.extend(self.iter().cloned());
+        new_list
+    }
+}
+
+impl<T> Extend<T> for ArenaList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for ArenaList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Debug> Debug for ArenaList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArenaList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for ArenaList<T> {}
+
+impl<T: PartialOrd> PartialOrd for ArenaList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T: Ord> Ord for ArenaList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+impl<T: Hash> Hash for ArenaList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for item in self {
+            item.hash(state);
+        }
+    }
+}
+
+// +-----------------------+
+// | Cursor Implementation |
+// +-----------------------+
+pub struct CursorMut<'a, T> {
+    cur: Link,
+    list: &'a mut ArenaList<T>,
+    index: Option<usize>,
+}
+
+impl<T> ArenaList<T> {
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        // because we are using a "ghost" element we start at None
+        CursorMut {
+            cur: None,
+            list: self,
+            index: None,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        match self.cur {
+            Some(cur) => {
+                // we are on a real element, go to its next (back)
+                self.cur = self.list.node(cur).back;
+                self.index = self.cur.and_then(|_| self.index.map(|index| index + 1));
+            }
+            None => {
+                // we are the ghost, the front comes next (if there is one)
+                self.cur = self.list.front;
+                self.index = self.cur.map(|_| 0);
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.cur {
+            Some(cur) => {
+                // we are on a real element, go to its previous (front)
+                self.cur = self.list.node(cur).front;
+                self.index = self.cur.and_then(|_| self.index.map(|index| index - 1));
+            }
+            None => {
+                // we are the ghost, the back comes before us (if there is one)
+                self.cur = self.list.back;
+                self.index = self.cur.map(|_| self.list.len - 1);
+            }
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.cur.map(|cur| &mut self.list.node_mut(cur).elem)
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = self.next_link();
+        next.map(|next| &mut self.list.node_mut(next).elem)
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = self.prev_link();
+        prev.map(|prev| &mut self.list.node_mut(prev).elem)
+    }
+
+    pub fn insert_after(&mut self, elem: T) {
+        let next = self.next_link();
+        self.list.link_between(self.cur, next, elem);
+    }
+
+    pub fn insert_before(&mut self, elem: T) {
+        let prev = self.prev_link();
+        self.list.link_between(prev, self.cur, elem);
+        // the cursor does not move, but its element is one further back
+        if let Some(index) = self.index.as_mut() {
+            *index += 1;
+        }
+    }
+
+    // like `insert_after`, but hands the element back instead of panicking or
+    // aborting when the arena can't grow any further
+    pub fn try_insert_after(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        let next = self.next_link();
+        self.list.try_link_between(self.cur, next, elem)?;
+        Ok(())
+    }
+
+    pub fn try_insert_before(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        let prev = self.prev_link();
+        self.list.try_link_between(prev, self.cur, elem)?;
+        if let Some(index) = self.index.as_mut() {
+            *index += 1;
+        }
+        Ok(())
+    }
+
+    // removes the next element, on the ghost the front of the list
+    pub fn remove_after(&mut self) -> Option<T> {
+        let next = self.next_link()?;
+        Some(self.list.unlink(next))
+    }
+
+    // removes the previous element, on the ghost the back of the list
+    pub fn remove_before(&mut self) -> Option<T> {
+        let prev = self.prev_link()?;
+        // the cursor does not move, but its element is one further to the front
+        if let Some(index) = self.index.as_mut() {
+            *index -= 1;
+        }
+        Some(self.list.unlink(prev))
+    }
+
+    pub fn remove_current(&mut self) -> Option<T> {
+        // the ghost cannot be removed
+        let cur = self.cur?;
+        // move onto the next element, which takes over our index, unless it is
+        // the ghost
+        self.cur = self.list.node(cur).back;
+        if self.cur.is_none() {
+            self.index = None;
+        }
+        Some(self.list.unlink(cur))
+    }
+
+    // replaces the element the cursor points to and returns the old one, on the
+    // ghost nothing can be replaced so `elem` is handed back as an error
+    pub fn replace_current(&mut self, elem: T) -> Result<T, T> {
+        match self.cur {
+            Some(cur) => Ok(std::mem::replace(&mut self.list.node_mut(cur).elem, elem)),
+            None => Err(elem),
+        }
+    }
+
+    // returns everything in front of the cursor, on the ghost the whole list
+    pub fn split_before(&mut self) -> ArenaList<T> {
+        let Some(cur) = self.cur else {
+            return std::mem::take(self.list);
+        };
+        let mut output = ArenaList::with_capacity(self.index.unwrap_or(0));
+        while let Some(prev) = self.list.node(cur).front {
+            output.push_front(self.list.unlink(prev));
+        }
+        self.index = Some(0);
+        output
+    }
+
+    // returns everything after the cursor, on the ghost the whole list
+    pub fn split_after(&mut self) -> ArenaList<T> {
+        let Some(cur) = self.cur else {
+            return std::mem::take(self.list);
+        };
+        let mut output = ArenaList::with_capacity(self.list.len - self.index.unwrap_or(0) - 1);
+        while let Some(next) = self.list.node(cur).back {
+            output.push_back(self.list.unlink(next));
+        }
+        output
+    }
+
+    // moves the elements of `input` in between the cursor and its next
+    // element, on the ghost to the front of the list
+    pub fn splice_after(&mut self, input: ArenaList<T>) {
+        self.list.reserve(input.len);
+        // every element is inserted right after the cursor, so the last one
+        // has to go first
+        for elem in input.into_iter().rev() {
+            self.insert_after(elem);
+        }
+    }
+
+    // moves the elements of `input` in between the cursor and its previous
+    // element, on the ghost to the back of the list
+    pub fn splice_before(&mut self, input: ArenaList<T>) {
+        self.list.reserve(input.len);
+        for elem in input {
+            self.insert_before(elem);
+        }
+    }
+
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            cur: self.cur,
+            list: self.list,
+            index: self.index,
+        }
+    }
+
+    fn next_link(&self) -> Link {
+        match self.cur {
+            Some(cur) => self.list.node(cur).back,
+            // ghost -> the list's front
+            None => self.list.front,
+        }
+    }
+
+    fn prev_link(&self) -> Link {
+        match self.cur {
+            Some(cur) => self.list.node(cur).front,
+            // ghost -> the list's back
+            None => self.list.back,
+        }
+    }
+}
+
+// Read-only cursor, which can be copied and shared.
+pub struct Cursor<'a, T> {
+    cur: Link,
+    list: &'a ArenaList<T>,
+    index: Option<usize>,
+}
+
+impl<T> ArenaList<T> {
+    pub fn cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            cur: None,
+            list: self,
+            index: None,
+        }
+    }
+}
+
+impl<'a, T> Clone for Cursor<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for Cursor<'a, T> {}
+
+impl<'a, T> Cursor<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        match self.cur {
+            Some(cur) => {
+                self.cur = self.list.node(cur).back;
+                self.index = self.cur.and_then(|_| self.index.map(|index| index + 1));
+            }
+            None => {
+                self.cur = self.list.front;
+                self.index = self.cur.map(|_| 0);
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.cur {
+            Some(cur) => {
+                self.cur = self.list.node(cur).front;
+                self.index = self.cur.and_then(|_| self.index.map(|index| index - 1));
+            }
+            None => {
+                self.cur = self.list.back;
+                self.index = self.cur.map(|_| self.list.len - 1);
+            }
+        }
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        self.cur.map(|cur| &self.list.node(cur).elem)
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = match self.cur {
+            Some(cur) => self.list.node(cur).back,
+            None => self.list.front,
+        };
+        next.map(|next| &self.list.node(next).elem)
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = match self.cur {
+            Some(cur) => self.list.node(cur).front,
+            None => self.list.back,
+        };
+        prev.map(|prev| &self.list.node(prev).elem)
+    }
+}
+
+// +-----------------------+
+// | Joining and Splitting |
+// +-----------------------+
+
+impl<T> ArenaList<T> {
+    // Moves all elements of `other` to the back of this list, leaving `other`
+    // empty. The elements have to move into this arena, so this is O(m).
+    pub fn append(&mut self, other: &mut ArenaList<T>) {
+        self.cursor_mut().splice_before(std::mem::take(other));
+    }
+
+    // Moves all elements of `other` to the front of this list, leaving `other`
+    // empty. The elements have to move into this arena, so this is O(m).
+    pub fn prepend(&mut self, other: &mut ArenaList<T>) {
+        self.cursor_mut().splice_after(std::mem::take(other));
+    }
+
+    // Splits the list into two at the given index. Returns everything from
+    // `at` onwards, while this list keeps everything in front of it.
+    //
+    // Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> ArenaList<T> {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+        if at == 0 {
+            return std::mem::take(self);
+        }
+
+        // the node in front of the split, `at - 1 < len` so it exists
+        let node = self.node_at(at - 1);
+        CursorMut {
+            cur: node,
+            list: self,
+            index: Some(at - 1),
+        }
+        .split_after()
+    }
+}
+
+// +-----------+
+// | Filtering |
+// +-----------+
+
+impl<T> ArenaList<T> {
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        while let Some(elem) = cursor.current() {
+            if f(elem) {
+                cursor.move_next();
+            } else {
+                // moves the cursor onto the next element
+                cursor.remove_current();
+            }
+        }
+    }
+
+    // Lazily removes and yields the elements for which `pred` returns true.
+    // Elements that are not visited, because the iterator is dropped early,
+    // remain in the list.
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        ExtractIf { cursor, pred }
+    }
+
+    // Removes the elements in `range` from the list and returns them as an
+    // iterator. The elements have to move out of the arena, which happens
+    // right away and not only while iterating.
+    //
+    // Panics if the range is out of bounds or its start is after its end.
+    pub fn drain<R>(&mut self, range: R) -> IntoIter<T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len,
+        };
+        assert!(start <= end, "drain start is after its end");
+        assert!(end <= self.len, "drain end is out of bounds");
+
+        let mut drained = ArenaList::with_capacity(end - start);
+        let mut node = self.node_at(start);
+        for _ in start..end {
+            // `end <= len`, so all of these exist
+            let index = node.unwrap();
+            node = self.node(index).back;
+            drained.push_back(self.unlink(index));
+        }
+        drained.into_iter()
+    }
+}
+
+pub struct ExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    cursor: CursorMut<'a, T>,
+    pred: F,
+}
+
+impl<'a, T, F> Iterator for ExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // once we reach the ghost we are done
+        while let Some(elem) = self.cursor.current() {
+            if (self.pred)(elem) {
+                return self.cursor.remove_current();
+            }
+            self.cursor.move_next();
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // at most all remaining elements are extracted
+        let remaining = match self.cursor.index() {
+            Some(index) => self.cursor.list.len - index,
+            None => 0,
+        };
+        (0, Some(remaining))
+    }
+}
+
+// +------------------------+
+// | Searching and Ordering |
+// +------------------------+
+
+impl<T> ArenaList<T> {
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|elem| elem == x)
+    }
+
+    pub fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    // searches from the back, but the index is still counted from the front
+    pub fn rposition<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().rposition(predicate)
+    }
+
+    pub fn find<P>(&self, mut predicate: P) -> Option<&T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|elem| predicate(elem))
+    }
+
+    // Removes consecutive repeated elements, keeping the first of each run.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    // Removes consecutive elements for which `same_bucket(next, kept)` returns
+    // true, where `kept` is the last element that stays in the list.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let mut kept = self.front;
+        while let Some(cur) = kept {
+            let Some(next) = self.node(cur).back else {
+                break;
+            };
+            let (next_elem, cur_elem) = self.elems_mut(next, cur);
+            if same_bucket(next_elem, cur_elem) {
+                self.unlink(next);
+            } else {
+                kept = Some(next);
+            }
+        }
+    }
+
+    pub fn reverse(&mut self) {
+        for slot in &mut self.slots {
+            if let Slot::Occupied(node) = slot {
+                std::mem::swap(&mut node.front, &mut node.back);
+            }
+        }
+        std::mem::swap(&mut self.front, &mut self.back);
+    }
+
+    // Rotates the list `n` places to the left, such that the element at `n`
+    // becomes the front. Only the links at the ends of the list are changed.
+    //
+    // Panics if `n > len`.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len, "cannot rotate by more than the length");
+        if n == 0 || n == self.len {
+            return;
+        }
+
+        // 0 < n < len, so all of these exist
+        let new_front = self.node_at(n).unwrap();
+        let new_back = self.node(new_front).front.unwrap();
+        let old_front = self.front.unwrap();
+        let old_back = self.back.unwrap();
+
+        // close the ring
+        self.node_mut(old_back).back = Some(old_front);
+        self.node_mut(old_front).front = Some(old_back);
+
+        // and open it up again in front of `new_front`
+        self.node_mut(new_back).back = None;
+        self.node_mut(new_front).front = None;
+        self.front = Some(new_front);
+        self.back = Some(new_back);
+    }
+
+    // Rotates the list `n` places to the right, such that the element at
+    // `len - n` becomes the front.
+    //
+    // Panics if `n > len`.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len, "cannot rotate by more than the length");
+        self.rotate_left(self.len - n);
+    }
+}
+
+// +---------+
+// | Sorting |
+// +---------+
+
+impl<T> ArenaList<T> {
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    // Stable sort, which sorts the slots of the nodes and then relinks them in
+    // that order, so no element is moved. Unlike `LinkedList::sort_by` this
+    // needs O(n) extra memory for the slots, but a panicking `compare` leaves
+    // the list as it was.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.len < 2 {
+            return;
+        }
+        let mut links = self.links();
+        links.sort_by(|&a, &b| compare(&self.node(a).elem, &self.node(b).elem));
+
+        for (i, &index) in links.iter().enumerate() {
+            let node = self.node_mut(index);
+            node.front = i.checked_sub(1).map(|prev| links[prev]);
+            node.back = links.get(i + 1).copied();
+        }
+        self.front = links.first().copied();
+        self.back = links.last().copied();
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+}
+
+// +----------------+
+// | Indexed Access |
+// +----------------+
+
+impl<T> ArenaList<T> {
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.node_at(index).map(|node| &self.node(node).elem)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.node_at(index)
+            .map(|node| &mut self.node_mut(node).elem)
+    }
+
+    // Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, elem: T) {
+        assert!(index <= self.len, "insertion index is out of bounds");
+        // in front of the node at `index`, or at the back if there is none
+        let back = self.node_at(index);
+        let front = match back {
+            Some(back) => self.node(back).front,
+            None => self.back,
+        };
+        self.link_between(front, back, elem);
+    }
+
+    // like `insert`, but hands the element back instead of panicking or
+    // aborting when the arena can't grow any further
+    //
+    // Panics if `index > len`.
+    pub fn try_insert(&mut self, index: usize, elem: T) -> Result<(), TryReserveError<T>> {
+        assert!(index <= self.len, "insertion index is out of bounds");
+        let back = self.node_at(index);
+        let front = match back {
+            Some(back) => self.node(back).front,
+            None => self.back,
+        };
+        self.try_link_between(front, back, elem)?;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        self.node_at(index).map(|node| self.unlink(node))
+    }
+
+    // places a cursor on the element at `index`
+    pub fn cursor_at(&mut self, index: usize) -> Option<CursorMut<'_, T>> {
+        let node = self.node_at(index)?;
+        Some(CursorMut {
+            cur: Some(node),
+            list: self,
+            index: Some(index),
+        })
+    }
+
+    // Swaps the elements at `i` and `j`. There are no handles into an arena
+    // list, so the elements can simply trade places.
+    //
+    // Panics if `i` or `j` are out of bounds.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let a = self.node_at(i).expect("swap index is out of bounds");
+        let b = self.node_at(j).expect("swap index is out of bounds");
+        if a != b {
+            let (a, b) = self.elems_mut(a, b);
+            std::mem::swap(a, b);
+        }
+    }
+}
+
+impl<T> Index<usize> for ArenaList<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> IndexMut<usize> for ArenaList<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+// +--------+
+// | Traits |
+// +--------+
+
+// as a stack the front of the list is its top
+impl<T> Stack<T> for ArenaList<T> {
+    fn push(&mut self, elem: T) {
+        ArenaList::push_front(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        ArenaList::pop_front(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        ArenaList::front(self)
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut T> {
+        ArenaList::front_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        ArenaList::len(self)
+    }
+}
+
+impl<T> Queue<T> for ArenaList<T> {
+    fn push_back(&mut self, elem: T) {
+        ArenaList::push_back(self, elem)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        ArenaList::pop_front(self)
+    }
+
+    fn front(&self) -> Option<&T> {
+        ArenaList::front(self)
+    }
+
+    fn front_mut(&mut self) -> Option<&mut T> {
+        ArenaList::front_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        ArenaList::len(self)
+    }
+}
+
+impl<T> Deque<T> for ArenaList<T> {
+    fn push_front(&mut self, elem: T) {
+        ArenaList::push_front(self, elem)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        ArenaList::pop_back(self)
+    }
+
+    fn back(&self) -> Option<&T> {
+        ArenaList::back(self)
+    }
+
+    fn back_mut(&mut self) -> Option<&mut T> {
+        ArenaList::back_mut(self)
+    }
+}
@@ -1,6 +1,16 @@
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use super::allocator::{self, Allocator, Global, TryReserveError};
+use super::Stack;
+
 // using option instead of an own enum enables us to use all the available
-// functions on options we don't have to implement!
-type Link<T> = Option<Box<Node<T>>>;
+// functions on options we don't have to implement! The nodes come from the
+// allocator of the list, so a link is a raw pointer and not a box.
+type Link<T> = Option<NonNull<Node<T>>>;
 
 struct Node<T> {
     elem: T,
@@ -8,31 +18,82 @@ struct Node<T> {
 }
 
 // wrapper around Link to hide the Link and Node enum/struct from the outside
-// because of zero cost abstractions this struct has the same size as that field
-pub struct List<T> {
+// because of zero cost abstractions the wrapper itself costs nothing, only the
+// length we keep track of takes additional space
+pub struct List<T, A: Allocator = Global> {
     head: Link<T>,
+    len: usize,
+    // all nodes are allocated and freed through this one
+    alloc: A,
+    // we own values of type T through the nodes
+    _boo: PhantomData<T>,
 }
 
 impl<T> List<T> {
     pub fn new() -> Self {
-	Self { head: None }
+	Self::new_in(Global)
+    }
+}
+
+impl<T, A: Allocator> List<T, A> {
+    // creates an empty list whose nodes will be allocated by `alloc`
+    pub fn new_in(alloc: A) -> Self {
+	Self { head: None, len: 0, alloc, _boo: PhantomData }
+    }
+
+    pub fn allocator(&self) -> &A {
+	&self.alloc
     }
 
     pub fn push(&mut self, elem: T) {
-	let new_node = Box::new(Node {
+	let new_node = allocator::allocate(&self.alloc, Node {
 	    elem,
 	    // the trick using std::mem::replace is actually pretty common and
 	    // for option there is actually a method `take`
 	    next: self.head.take(),
 	});
 	self.head = Some(new_node);
+	self.len += 1;
+    }
+
+    // like `push`, but hands the element back instead of aborting when the
+    // allocator is out of memory
+    pub fn try_push(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+	let node = Node {
+	    elem,
+	    next: self.head.take(),
+	};
+	let new_node = allocator::try_allocate_node(&self.alloc, node, |node| {
+	    // the old head has to go back to where it came from
+	    self.head = node.next;
+	    node.elem
+	})?;
+	self.head = Some(new_node);
+	self.len += 1;
+	Ok(())
+    }
+
+    // pushes the elements until one of them can't be allocated, that one is
+    // handed back and the rest of the iterator is dropped
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryReserveError<T>>
+    where
+	I: IntoIterator<Item = T>,
+    {
+	for elem in iter {
+	    self.try_push(elem)?;
+	}
+	Ok(())
     }
 
     pub fn pop(&mut self) -> Option<T> {
 	// instead of using the pattern matching use `map` instead
 	self.head.take().map(|node| {
+	    // the node was allocated by `alloc` and is freed again by moving it
+	    // out of its block
+	    let node = unsafe { allocator::deallocate(&self.alloc, node) };
 	    // replace this node with the next one
 	    self.head = node.next;
+	    self.len -= 1;
 	    // return the value of the current head
 	    node.elem // no need to wrap this into an Option / Some
 	})
@@ -40,52 +101,66 @@ impl<T> List<T> {
 
     pub fn peek(&self) -> Option<&T> {
 	self.head.as_ref().map(|node| {
-	    &node.elem
+	    unsafe { &node.as_ref().elem }
 	})
     }
 
     pub fn peek_mut(&mut self) -> Option<&mut T> {
 	self.head.as_mut().map(|node| {
-	    &mut node.elem
+	    unsafe { &mut node.as_mut().elem }
 	})
     }
+
+    pub fn len(&self) -> usize {
+	self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+	self.len == 0
+    }
 }
 
 
-// as the list contains types which implement the Drop Trait the implementation
-// for Drop for the List is actually not required. But the automatic handling
-// can be bad as it will recursivly call drop on each element in the linked
-// list, which can cause a stack overflow.
-impl<T> Drop for List<T> {
+// raw pointers don't free anything on their own, so without this every node
+// would leak. Walking the list in a loop also keeps a long list from
+// overflowing the stack, which recursive drops could do.
+impl<T, A: Allocator> Drop for List<T, A> {
     fn drop(&mut self) {
         let mut cur_link = self.head.take();
 
-	while let Some(mut boxed) = cur_link {
-	    cur_link = boxed.next.take();
-	    // boxed goes out of scope and gets dropped here; but its Node's
-	    // `next` field has been set to None so no unbounded recursion
-	    // occurs
+	while let Some(node) = cur_link {
+	    let node = unsafe { allocator::deallocate(&self.alloc, node) };
+	    cur_link = node.next;
+	    // the element of the node goes out of scope and gets dropped here
 	}
     }
 }
 
 // into_iter
 // trivial wrapper around list for into_iter
-pub struct IntoIter<T>(List<T>);
+pub struct IntoIter<T, A: Allocator = Global>(List<T, A>);
 
-impl<T> List<T> {
-    pub fn into_iter(self) -> IntoIter<T> {
+impl<T, A: Allocator> IntoIterator for List<T, A> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
 	IntoIter(self)
     }
 }
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
 	// access fields of a tuple struct numerically
         self.0.pop()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+	(self.0.len, Some(self.0.len))
+    }
 }
 
 // iter
@@ -94,21 +169,27 @@ pub struct Iter<'a, T> {
 }
 
 // No lifetime here, List doesn't have any associated lifetimes
-impl<T> List<T> {
+impl<T, A: Allocator> List<T, A> {
     // We declare a fresh lifetime here for the *exact* borrow thta creates the
     // iter. Now &self needs to be valid as long as the Iter is around.
-    // When using `as_deref` we don't need to provide lifetimes anymore, as the
+    // We don't need to provide the lifetimes of the references inside, as the
     // compiler can figure them out by himself.
     // Instead of "hiding" that a struct contains a lifetime, you can use the
     // explicitly elided lifetime syntax `'_`
     pub fn iter(&self) -> Iter<'_, T> {
-	// The `as_deref` and `as_defer_mut` functions are stable as of Rust
-	// 1.40. Before that you would need to do `map(|node| &**node)` and
-	// `map(|node| &mut**node)`. In this case the closure in conjunction
-	// with the fact that we have an `Option<&T>` instead of `&T` is a bit
-	// too compliacted for the bollow checker to work out, so we need to
-	// help it by being explicit.
-	Iter { next: self.head.as_deref() }
+	// With boxes `as_deref` would do here, a raw pointer has to be turned
+	// into a reference by hand instead.
+	Iter { next: self.head.map(|node| unsafe { &*node.as_ptr() }) }
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a List<T, A> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+	self.iter()
     }
 }
 
@@ -119,7 +200,7 @@ impl<'a, T> Iterator for Iter<'a, T> {
     // Self continues to be increadibly hype and amazing
     fn next(&mut self) -> Option<Self::Item> {
         self.next.map(|node| {
-	    self.next = node.next.as_deref();
+	    self.next = node.next.map(|next| unsafe { &*next.as_ptr() });
 	    &node.elem
 	})
     }
@@ -130,9 +211,19 @@ pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
 }
 
-impl<T> List<T> {
+impl<T, A: Allocator> List<T, A> {
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
-	IterMut { next: self.head.as_deref_mut() }
+	IterMut { next: self.head.map(|node| unsafe { &mut *node.as_ptr() }) }
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut List<T, A> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+	self.iter_mut()
     }
 }
 
@@ -141,8 +232,119 @@ impl<'a, T> Iterator for IterMut<'a, T> {
 
     fn next(&mut self) -> Option<Self::Item> {
         self.next.take().map(|node| {
-	    self.next = node.next.as_deref_mut();
+	    self.next = node.next.map(|next| unsafe { &mut *next.as_ptr() });
 	    &mut node.elem
 	})
     }
 }
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+	Self::new()
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for List<T, A> {
+    fn clone(&self) -> Self {
+	let mut new_list = Self::new_in(self.alloc.clone());
+	// pushing would reverse the order, so we always append to the end of
+	// the new list instead
+	let mut tail = &mut new_list.head;
+	for elem in self {
+	    let node = allocator::allocate(&new_list.alloc, Node {
+		elem: elem.clone(),
+		next: None,
+	    });
+	    *tail = Some(node);
+	    tail = unsafe { &mut (*node.as_ptr()).next };
+	}
+	new_list.len = self.len;
+	new_list
+    }
+}
+
+// elements are pushed in the order of the iterator, so the last one ends up on
+// top of the stack
+impl<T, A: Allocator> Extend<T> for List<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+	for elem in iter {
+	    self.push(elem);
+	}
+    }
+}
+
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+	let mut list = Self::new();
+	list.extend(iter);
+	list
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for List<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, A: Allocator> PartialEq for List<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+	self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for List<T, A> {}
+
+impl<T: Hash, A: Allocator> Hash for List<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+	self.len.hash(state);
+	for elem in self {
+	    elem.hash(state);
+	}
+    }
+}
+
+// +----------------------+
+// | Send and Sync Traits |
+// +----------------------+
+
+// NonNull is neither Send nor Sync, the nodes behind it belong to the list
+// though, so the list and its iterators can be sent and shared like a box
+unsafe impl<T: Send, A: Allocator + Send> Send for List<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for List<T, A> {}
+
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
+// +--------+
+// | Traits |
+// +--------+
+
+impl<T, A: Allocator> Stack<T> for List<T, A> {
+    fn push(&mut self, elem: T) {
+	List::push(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+	List::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+	List::peek(self)
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut T> {
+	List::peek_mut(self)
+    }
+
+    fn len(&self) -> usize {
+	List::len(self)
+    }
+}
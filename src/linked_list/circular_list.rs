@@ -0,0 +1,734 @@
+// Doubly linked list, where the back is linked to the front again.
+//
+// `LinkedList` closes its ring through the "ghost" element of its cursor, this
+// list leaves the ghost out. Every node always has a next and a previous node
+// (a single node is its own neighbour), so a cursor can walk around forever and
+// never reaches an end. The list only remembers which node is its front, and
+// moving that around the ring rotates the whole list in place.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use super::allocator::{self, Global, TryReserveError};
+use super::{Deque, Queue, Stack};
+
+pub struct CircularList<T> {
+    // `None` only if the list is empty
+    front: Link<T>,
+    len: usize,
+    _boo: PhantomData<T>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    // never dangling once the node is linked
+    front: NonNull<Node<T>>,
+    back: NonNull<Node<T>>,
+    elem: T,
+}
+
+impl<T> CircularList<T> {
+    pub fn new() -> Self {
+        CircularList {
+            front: None,
+            len: 0,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        // the back is right in front of the front
+        let new = self.link_before(self.front, elem);
+        self.front = Some(new);
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        self.link_before(self.front, elem);
+    }
+
+    // like `push_front`, but hands the element back instead of aborting when
+    // running out of memory
+    pub fn try_push_front(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        let new = self.try_link_before(self.front, elem)?;
+        self.front = Some(new);
+        Ok(())
+    }
+
+    pub fn try_push_back(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        self.try_link_before(self.front, elem)?;
+        Ok(())
+    }
+
+    // pushes the elements to the back until one of them can't be allocated,
+    // that one is handed back and the rest of the iterator is dropped
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryReserveError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.try_push_back(elem)?;
+        }
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let front = self.front?;
+        unsafe { Some(self.unlink(front)) }
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let back = self.back_node()?;
+        unsafe { Some(self.unlink(back)) }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        unsafe { self.front.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.front.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe { self.back_node().map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.back_node().map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    // Moves the front `n` elements further back, such that the first `n`
+    // elements end up at the back. Negative `n` rotate the other way around.
+    pub fn rotate(&mut self, n: isize) {
+        if let Some(front) = self.front {
+            self.front = Some(unsafe { walk(front, n, self.len) });
+        }
+    }
+
+    // moves all elements of `other` to the back of the list, O(1)
+    pub fn append(&mut self, other: &mut CircularList<T>) {
+        let other = std::mem::take(other);
+        match self.front {
+            Some(front) => unsafe {
+                self.splice_before(front, other);
+            },
+            None => *self = other,
+        }
+    }
+
+    fn back_node(&self) -> Link<T> {
+        unsafe { self.front.map(|front| (*front.as_ptr()).front) }
+    }
+
+    // Links a new node in front of `at`, which makes it the back if `at` is
+    // the front. The new node is the whole ring if the list is empty.
+    fn link_before(&mut self, at: Link<T>, elem: T) -> NonNull<Node<T>> {
+        self.try_link_before(at, elem).unwrap_or_else(|err| err.handle())
+    }
+
+    fn try_link_before(
+        &mut self,
+        at: Link<T>,
+        elem: T,
+    ) -> Result<NonNull<Node<T>>, TryReserveError<T>> {
+        unsafe {
+            let node = Node {
+                front: NonNull::dangling(),
+                back: NonNull::dangling(),
+                elem,
+            };
+            let new = allocator::try_allocate_node(&Global, node, |node| node.elem)?;
+            match at {
+                Some(at) => {
+                    let prev = (*at.as_ptr()).front;
+                    (*new.as_ptr()).front = prev;
+                    (*new.as_ptr()).back = at;
+                    (*prev.as_ptr()).back = new;
+                    (*at.as_ptr()).front = new;
+                }
+                None => {
+                    (*new.as_ptr()).front = new;
+                    (*new.as_ptr()).back = new;
+                    self.front = Some(new);
+                }
+            }
+            self.len += 1;
+            Ok(new)
+        }
+    }
+
+    // links all nodes of `other` in front of `at`, returns the first of them
+    unsafe fn splice_before(
+        &mut self,
+        at: NonNull<Node<T>>,
+        mut other: CircularList<T>,
+    ) -> Link<T> {
+        let first = other.front.take()?;
+        let last = (*first.as_ptr()).front;
+        let prev = (*at.as_ptr()).front;
+        (*prev.as_ptr()).back = first;
+        (*first.as_ptr()).front = prev;
+        (*last.as_ptr()).back = at;
+        (*at.as_ptr()).front = last;
+        self.len += std::mem::replace(&mut other.len, 0);
+        Some(first)
+    }
+
+    // `node` has to be in this list, the front moves on if it is removed
+    unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        let removed = allocator::deallocate(&Global, node);
+        if self.len == 1 {
+            self.front = None;
+        } else {
+            (*removed.front.as_ptr()).back = removed.back;
+            (*removed.back.as_ptr()).front = removed.front;
+            if self.front == Some(node) {
+                self.front = Some(removed.back);
+            }
+        }
+        self.len -= 1;
+        removed.elem
+    }
+}
+
+// Walks `n` steps around a ring of `len` nodes, taking the shorter way.
+unsafe fn walk<T>(mut node: NonNull<Node<T>>, n: isize, len: usize) -> NonNull<Node<T>> {
+    let forward = n.rem_euclid(len as isize) as usize;
+    if forward <= len / 2 {
+        for _ in 0..forward {
+            node = (*node.as_ptr()).back;
+        }
+    } else {
+        for _ in forward..len {
+            node = (*node.as_ptr()).front;
+        }
+    }
+    node
+}
+
+impl<T> Drop for CircularList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// +-----------------+
+// | Iterator Traits |
+// +-----------------+
+
+// Goes around once, starting at the front.
+pub struct Iter<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a T>,
+}
+
+impl<T> CircularList<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.front,
+            back: self.back_node(),
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CircularList<T> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.front.map(|node| unsafe {
+            self.front = Some((*node.as_ptr()).back);
+            &(*node.as_ptr()).elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.back.map(|node| unsafe {
+            self.back = Some((*node.as_ptr()).front);
+            &(*node.as_ptr()).elem
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct IterMut<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+impl<T> CircularList<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.front,
+            back: self.back_node(),
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut CircularList<T> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.front.map(|node| unsafe {
+            self.front = Some((*node.as_ptr()).back);
+            &mut (*node.as_ptr()).elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.back.map(|node| unsafe {
+            self.back = Some((*node.as_ptr()).front);
+            &mut (*node.as_ptr()).elem
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct IntoIter<T> {
+    list: CircularList<T>,
+}
+
+impl<T> IntoIterator for CircularList<T> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<T> Default for CircularList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for CircularList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Extend<T> for CircularList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for CircularList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Debug> Debug for CircularList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+// compares from the current front, so rotations of a list are not equal
+impl<T: PartialEq> PartialEq for CircularList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for CircularList<T> {}
+
+impl<T: PartialOrd> PartialOrd for CircularList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T: Ord> Ord for CircularList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+impl<T: Hash> Hash for CircularList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for item in self {
+            item.hash(state);
+        }
+    }
+}
+
+// +----------------------+
+// | Send and Sync Traits |
+// +----------------------+
+unsafe impl<T: Send> Send for CircularList<T> {}
+unsafe impl<T: Sync> Sync for CircularList<T> {}
+
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
+// +-----------------------+
+// | Cursor Implementation |
+// +-----------------------+
+
+// There is no ghost, the cursor is on an element unless the list is empty.
+// Moving past the back wraps around to the front and the other way around.
+// The index counts from the front of the list.
+pub struct CursorMut<'a, T> {
+    cur: Link<T>,
+    list: &'a mut CircularList<T>,
+    index: usize,
+}
+
+impl<T> CircularList<T> {
+    // starts at the front
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            cur: self.front,
+            list: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.cur.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        self.step(1);
+    }
+
+    pub fn move_prev(&mut self) {
+        self.step(-1);
+    }
+
+    // moves `n` elements forwards, or backwards if `n` is negative
+    pub fn step(&mut self, n: isize) {
+        if let Some(cur) = self.cur {
+            let len = self.list.len;
+            self.cur = Some(unsafe { walk(cur, n, len) });
+            // reduce `n` first, adding it right away could overflow
+            let forward = n.rem_euclid(len as isize) as usize;
+            self.index = (self.index + forward) % len;
+        }
+    }
+
+    // makes the current element the front of the list, such that the list
+    // looks the same when walked from the cursor and from the front
+    pub fn set_front(&mut self) {
+        if self.cur.is_some() {
+            self.list.front = self.cur;
+            self.index = 0;
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.cur.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        unsafe {
+            self.cur
+                .map(|node| &mut (*(*node.as_ptr()).back.as_ptr()).elem)
+        }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        unsafe {
+            self.cur
+                .map(|node| &mut (*(*node.as_ptr()).front.as_ptr()).elem)
+        }
+    }
+
+    // The new element follows the current one, after the back means at the
+    // back. In an empty list the cursor moves onto the new element.
+    pub fn insert_after(&mut self, elem: T) {
+        if let Err(err) = self.try_insert_after(elem) {
+            err.handle();
+        }
+    }
+
+    // like `insert_after`, but hands the element back instead of aborting
+    // when running out of memory
+    pub fn try_insert_after(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        match self.cur {
+            Some(cur) => unsafe {
+                let next = (*cur.as_ptr()).back;
+                self.list.try_link_before(Some(next), elem)?;
+            },
+            None => {
+                self.cur = Some(self.list.try_link_before(None, elem)?);
+                self.index = 0;
+            }
+        }
+        Ok(())
+    }
+
+    // The new element precedes the current one, before the front means it
+    // becomes the new front. In an empty list the cursor moves onto the new
+    // element.
+    pub fn insert_before(&mut self, elem: T) {
+        if let Err(err) = self.try_insert_before(elem) {
+            err.handle();
+        }
+    }
+
+    // like `insert_before`, but hands the element back instead of aborting
+    // when running out of memory
+    pub fn try_insert_before(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        match self.cur {
+            Some(cur) => {
+                let new = self.list.try_link_before(Some(cur), elem)?;
+                if self.index == 0 {
+                    self.list.front = Some(new);
+                }
+                // the cursor does not move, but its element is one further back
+                self.index += 1;
+            }
+            None => {
+                self.cur = Some(self.list.try_link_before(None, elem)?);
+                self.index = 0;
+            }
+        }
+        Ok(())
+    }
+
+    // removes the current element and moves onto the next one, which is the
+    // front if the back was removed
+    pub fn remove_current(&mut self) -> Option<T> {
+        let cur = self.cur?;
+        unsafe {
+            self.cur = if self.list.len == 1 {
+                None
+            } else {
+                Some((*cur.as_ptr()).back)
+            };
+            if self.index == self.list.len - 1 {
+                self.index = 0;
+            }
+            Some(self.list.unlink(cur))
+        }
+    }
+
+    // Moves all elements of `input` right after the current element, the same
+    // way `insert_after` does with a single one.
+    pub fn splice_after(&mut self, input: CircularList<T>) {
+        match self.cur {
+            Some(cur) => unsafe {
+                let next = (*cur.as_ptr()).back;
+                self.list.splice_before(next, input);
+            },
+            None => {
+                *self.list = input;
+                self.cur = self.list.front;
+                self.index = 0;
+            }
+        }
+    }
+
+    // Moves all elements of `input` right before the current element, the same
+    // way `insert_before` does with a single one.
+    pub fn splice_before(&mut self, input: CircularList<T>) {
+        match self.cur {
+            Some(cur) => unsafe {
+                let input_len = input.len;
+                let first = self.list.splice_before(cur, input);
+                if self.index == 0 && first.is_some() {
+                    self.list.front = first;
+                }
+                self.index += input_len;
+            },
+            None => {
+                *self.list = input;
+                self.cur = self.list.front;
+                self.index = 0;
+            }
+        }
+    }
+}
+
+// +--------+
+// | Traits |
+// +--------+
+
+// as a stack the front of the list is its top
+impl<T> Stack<T> for CircularList<T> {
+    fn push(&mut self, elem: T) {
+        CircularList::push_front(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        CircularList::pop_front(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        CircularList::front(self)
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut T> {
+        CircularList::front_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        CircularList::len(self)
+    }
+}
+
+impl<T> Queue<T> for CircularList<T> {
+    fn push_back(&mut self, elem: T) {
+        CircularList::push_back(self, elem)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        CircularList::pop_front(self)
+    }
+
+    fn front(&self) -> Option<&T> {
+        CircularList::front(self)
+    }
+
+    fn front_mut(&mut self) -> Option<&mut T> {
+        CircularList::front_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        CircularList::len(self)
+    }
+}
+
+impl<T> Deque<T> for CircularList<T> {
+    fn push_front(&mut self, elem: T) {
+        CircularList::push_front(self, elem)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        CircularList::pop_back(self)
+    }
+
+    fn back(&self) -> Option<&T> {
+        CircularList::back(self)
+    }
+
+    fn back_mut(&mut self) -> Option<&mut T> {
+        CircularList::back_mut(self)
+    }
+}
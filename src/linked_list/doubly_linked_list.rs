@@ -3,12 +3,25 @@ use std::fmt::{self, Debug};
 use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 use std::marker::PhantomData;
+use std::ops::{Bound, Index, IndexMut, RangeBounds};
 use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::Arc;
 
-pub struct LinkedList<T> {
+use super::allocator::{self, Allocator, Global, TryReserveError};
+use super::{Deque, Queue, Stack};
+
+pub struct LinkedList<T, A: Allocator = Global> {
     front: Link<T>,
     back: Link<T>,
     len: usize,
+    // unique id of this list, such that handles can tell whether their node
+    // belongs to it
+    id: u64,
+    // number of nodes that have a `Ticket` (i.e. handles may point to them)
+    tracked: usize,
+    // all nodes are allocated and freed through this one
+    alloc: A,
     // add Ghost to protect pointer
     // we semantically store values of T by-value
     _boo: PhantomData<T>,
@@ -23,30 +36,72 @@ type Link<T> = Option<NonNull<Node<T>>>;
 struct Node<T> {
     front: Link<T>,
     back: Link<T>,
+    // only allocated once a handle to this node has been requested
+    ticket: Option<Arc<Ticket>>,
     elem: T,
 }
 
+// Shared between a node and all handles to it. `owner` is the id of the list the
+// node currently belongs to, or `DEAD` once the node has been freed. As the
+// handles keep the ticket alive, they never have to touch a freed node to find
+// out that they are stale.
+struct Ticket {
+    owner: AtomicU64,
+}
+
+const DEAD: u64 = 0;
+
+// 0 is reserved for `DEAD`
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, AtomicOrdering::Relaxed)
+}
+
 impl<T> LinkedList<T> {
     pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    // creates an empty list whose nodes will be allocated by `alloc`
+    pub fn new_in(alloc: A) -> Self {
         Self {
             front: None,
             back: None,
             len: 0,
+            id: next_id(),
+            tracked: 0,
+            alloc,
             _boo: PhantomData,
         }
     }
 
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
     pub fn len(&self) -> usize {
         self.len
     }
 
     pub fn push_front(&mut self, elem: T) {
+        if let Err(err) = self.try_push_front(elem) {
+            err.handle();
+        }
+    }
+
+    // like `push_front`, but hands the element back instead of aborting when
+    // the allocator is out of memory
+    pub fn try_push_front(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
         unsafe {
-            let new = NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+            let new = allocator::try_allocate_node(&self.alloc, Node {
                 front: None,
                 back: None,
+                ticket: None,
                 elem,
-            })));
+            }, |node| node.elem)?;
 
             if let Some(old) = self.front {
                 // Put the new front before the old one
@@ -61,15 +116,25 @@ impl<T> LinkedList<T> {
             self.front = Some(new);
             self.len += 1;
         }
+        Ok(())
     }
 
     pub fn push_back(&mut self, elem: T) {
+        if let Err(err) = self.try_push_back(elem) {
+            err.handle();
+        }
+    }
+
+    // like `push_back`, but hands the element back instead of aborting when
+    // the allocator is out of memory
+    pub fn try_push_back(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
         unsafe {
-            let new = NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+            let new = allocator::try_allocate_node(&self.alloc, Node {
                 front: None,
                 back: None,
+                ticket: None,
                 elem,
-            })));
+            }, |node| node.elem)?;
 
             if let Some(old) = self.back {
                 // Put the new back before the old one
@@ -83,6 +148,7 @@ impl<T> LinkedList<T> {
             self.back = Some(new);
             self.len += 1;
         }
+        Ok(())
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
@@ -92,13 +158,14 @@ impl<T> LinkedList<T> {
             // everything is Copy and there are no dtors that will run if we
             // mess up
             self.front.map(|node| {
-                // Bring the Box back to life so we can move out its value and
-                // Drop it (Box continues to magically understand this for us)
-                let boxed = Box::from_raw(node.as_ptr());
-                let result = boxed.elem;
+                // Move the node out of its allocation, which is freed right
+                // away, the node itself is dropped at the end of the closure
+                let mut removed = allocator::deallocate(&self.alloc, node);
+                self.untrack(&mut removed);
+                let result = removed.elem;
 
                 // Make the next node into the new front
-                self.front = boxed.back;
+                self.front = removed.back;
                 if let Some(new) = self.front {
                     // Cleanup its reference to the removed node
                     (*new.as_ptr()).front = None;
@@ -115,7 +182,6 @@ impl<T> LinkedList<T> {
                 }
                 self.len -= 1;
                 result
-                // Box gets implicitly freed here, knows there is no T
             })
         }
     }
@@ -124,11 +190,12 @@ impl<T> LinkedList<T> {
         unsafe {
             // only have to do stuff if tere is a back node to pop.
             self.back.map(|node| {
-                let boxed = Box::from_raw(node.as_ptr());
-                let result = boxed.elem;
+                let mut removed = allocator::deallocate(&self.alloc, node);
+                self.untrack(&mut removed);
+                let result = removed.elem;
 
                 // Make the next node the new back
-                self.back = boxed.front;
+                self.back = removed.front;
                 if let Some(new) = self.back {
                     // Cleanup its reference to the removed node
                     (*new.as_ptr()).back = None;
@@ -138,7 +205,6 @@ impl<T> LinkedList<T> {
                 }
                 self.len -= 1;
                 result
-                // Box gets implicitly freed here, knows there is no T
             })
         }
     }
@@ -166,11 +232,23 @@ impl<T> LinkedList<T> {
     pub fn clear(&mut self) {
         while self.pop_front().is_some() {}
     }
+
+    // pushes the elements to the back until one of them can't be allocated,
+    // that one is handed back and the rest of the iterator is dropped
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryReserveError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.try_push_back(elem)?;
+        }
+        Ok(())
+    }
 }
 
 // Drop Trait
 
-impl<T> Drop for LinkedList<T> {
+impl<T, A: Allocator> Drop for LinkedList<T, A> {
     fn drop(&mut self) {
         // pop until we have to stop
         while self.pop_front().is_some() {}
@@ -188,8 +266,8 @@ pub struct Iter<'a, T> {
     _boo: PhantomData<&'a T>,
 }
 
-impl<T> LinkedList<T> {
-    pub fn iter(&self) -> Iter<T> {
+impl<T, A: Allocator> LinkedList<T, A> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             front: self.front,
             back: self.back,
@@ -199,7 +277,7 @@ impl<T> LinkedList<T> {
     }
 }
 
-impl<'a, T> IntoIterator for &'a LinkedList<T> {
+impl<'a, T, A: Allocator> IntoIterator for &'a LinkedList<T, A> {
     type Item = &'a T;
 
     type IntoIter = Iter<'a, T>;
@@ -256,8 +334,8 @@ pub struct IterMut<'a, T> {
     _boo: PhantomData<&'a mut T>,
 }
 
-impl<T> LinkedList<T> {
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+impl<T, A: Allocator> LinkedList<T, A> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             front: self.front,
             back: self.back,
@@ -267,7 +345,7 @@ impl<T> LinkedList<T> {
     }
 }
 
-impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+impl<'a, T, A: Allocator> IntoIterator for &'a mut LinkedList<T, A> {
     type Item = &'a mut T;
 
     type IntoIter = IterMut<'a, T>;
@@ -317,21 +395,21 @@ impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
     }
 }
 
-pub struct IntoIter<T> {
-    list: LinkedList<T>,
+pub struct IntoIter<T, A: Allocator = Global> {
+    list: LinkedList<T, A>,
 }
 
-impl<T> IntoIterator for LinkedList<T> {
+impl<T, A: Allocator> IntoIterator for LinkedList<T, A> {
     type Item = T;
 
-    type IntoIter = IntoIter<T>;
+    type IntoIter = IntoIter<T, A>;
 
     fn into_iter(self) -> Self::IntoIter {
         Self::IntoIter { list: self }
     }
 }
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -343,13 +421,13 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.list.pop_back()
     }
 }
 
-impl<T> ExactSizeIterator for IntoIter<T> {
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
     fn len(&self) -> usize {
         self.list.len
     }
@@ -365,9 +443,9 @@ impl<T> Default for LinkedList<T> {
     }
 }
 
-impl<T: Clone> Clone for LinkedList<T> {
+impl<T: Clone, A: Allocator + Clone> Clone for LinkedList<T, A> {
     fn clone(&self) -> Self {
-        let mut new_list = Self::new();
+        let mut new_list = Self::new_in(self.alloc.clone());
         for item in self {
             new_list.push_back(item.clone());
         }
@@ -375,7 +453,7 @@ impl<T: Clone> Clone for LinkedList<T> {
     }
 }
 
-impl<T> Extend<T> for LinkedList<T> {
+impl<T, A: Allocator> Extend<T> for LinkedList<T, A> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         for item in iter {
             self.push_back(item);
@@ -391,33 +469,33 @@ impl<T> FromIterator<T> for LinkedList<T> {
     }
 }
 
-impl<T: Debug> Debug for LinkedList<T> {
+impl<T: Debug, A: Allocator> Debug for LinkedList<T, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self).finish()
     }
 }
 
-impl<T: PartialEq> PartialEq for LinkedList<T> {
+impl<T: PartialEq, A: Allocator> PartialEq for LinkedList<T, A> {
     fn eq(&self, other: &Self) -> bool {
         self.len() == other.len() && self.iter().eq(other)
     }
 }
 
-impl<T: Eq> Eq for LinkedList<T> {}
+impl<T: Eq, A: Allocator> Eq for LinkedList<T, A> {}
 
-impl<T: PartialOrd> PartialOrd for LinkedList<T> {
+impl<T: PartialOrd, A: Allocator> PartialOrd for LinkedList<T, A> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.iter().partial_cmp(other)
     }
 }
 
-impl<T: Ord> Ord for LinkedList<T> {
+impl<T: Ord, A: Allocator> Ord for LinkedList<T, A> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.iter().cmp(other)
     }
 }
 
-impl<T: Hash> Hash for LinkedList<T> {
+impl<T: Hash, A: Allocator> Hash for LinkedList<T, A> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.len().hash(state);
         for item in self {
@@ -429,8 +507,8 @@ impl<T: Hash> Hash for LinkedList<T> {
 // +----------------------+
 // | Send and Sync Traits |
 // +----------------------+
-unsafe impl<T: Send> Send for LinkedList<T> {}
-unsafe impl<T: Sync> Sync for LinkedList<T> {}
+unsafe impl<T: Send, A: Allocator + Send> Send for LinkedList<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for LinkedList<T, A> {}
 
 // IntoIter does not need to implement Send and Sync as it auto derives from
 // LinkedList as it just contains a LinkedList which we just declared Send and
@@ -445,14 +523,14 @@ unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
 // +-----------------------+
 // | Cursor Implementation |
 // +-----------------------+
-pub struct CursorMut<'a, T> {
+pub struct CursorMut<'a, T, A: Allocator = Global> {
     cur: Link<T>,
-    list: &'a mut LinkedList<T>,
+    list: &'a mut LinkedList<T, A>,
     index: Option<usize>,
 }
 
-impl<T> LinkedList<T> {
-    pub fn cursor_mut(&mut self) -> CursorMut<T> {
+impl<T, A: Allocator> LinkedList<T, A> {
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, A> {
         // because we are using a "ghost" element we start at None
         CursorMut {
             cur: None,
@@ -462,7 +540,7 @@ impl<T> LinkedList<T> {
     }
 }
 
-impl<'a, T> CursorMut<'a, T> {
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
     pub fn index(&self) -> Option<usize> {
         self.index
     }
@@ -541,7 +619,10 @@ impl<'a, T> CursorMut<'a, T> {
         }
     }
 
-    pub fn split_before(&mut self) -> LinkedList<T> {
+    pub fn split_before(&mut self) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
         if let Some(cur) = self.cur {
             // we are pointing at a real element, so the list is non-empty
             debug_assert!(!self.list.is_empty());
@@ -572,21 +653,35 @@ impl<'a, T> CursorMut<'a, T> {
                 self.list.front = new_front;
                 self.index = new_idx;
 
-                LinkedList {
+                let mut output = LinkedList {
                     front: output_front,
                     back: output_back,
                     len: output_len,
+                    id: next_id(),
+                    tracked: 0,
+                    alloc: self.list.alloc.clone(),
                     _boo: PhantomData,
+                };
+
+                // handles have to follow their nodes into the output
+                if self.list.tracked > 0 {
+                    let moved = LinkedList::<T, A>::retag(output.front, output.len, output.id);
+                    output.tracked = moved;
+                    self.list.tracked -= moved;
                 }
+                output
             }
         } else {
             // we are the "ghost", just replace our list with an empty one.
             // No other state needs to be changed
-            std::mem::replace(self.list, LinkedList::new())
+            self.list.take_all()
         }
     }
 
-    pub fn split_after(&mut self) -> LinkedList<T> {
+    pub fn split_after(&mut self) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
         if let Some(cur) = self.cur {
             // we are pointing at a real element, so the list is non-empty
             debug_assert!(!self.list.is_empty());
@@ -617,21 +712,41 @@ impl<'a, T> CursorMut<'a, T> {
                 self.list.back = new_back;
                 self.index = new_idx;
 
-                LinkedList {
+                let mut output = LinkedList {
                     front: output_front,
                     back: output_back,
                     len: output_len,
+                    id: next_id(),
+                    tracked: 0,
+                    alloc: self.list.alloc.clone(),
                     _boo: PhantomData,
+                };
+
+                // handles have to follow their nodes into the output
+                if self.list.tracked > 0 {
+                    let moved = LinkedList::<T, A>::retag(output.front, output.len, output.id);
+                    output.tracked = moved;
+                    self.list.tracked -= moved;
                 }
+                output
             }
         } else {
             // we are the "ghost", just replace our list with an empty one.
             // No other state needs to be changed
-            std::mem::replace(self.list, LinkedList::new())
+            self.list.take_all()
         }
     }
 
-    pub fn splice_before(&mut self, mut input: LinkedList<T>) {
+    pub fn splice_before(&mut self, mut input: LinkedList<T, A>) {
+        self.splice_list_before(&mut input);
+        // input dropped here
+    }
+
+    // like `splice_before`, but `input` is only emptied and not consumed, so
+    // `append` can use it on a list it borrows
+    fn splice_list_before(&mut self, input: &mut LinkedList<T, A>) {
+        // handles have to follow their nodes into our list
+        self.list.adopt(input);
         unsafe {
             if input.is_empty() {
                 // Input is empty do nothing.
@@ -664,17 +779,28 @@ impl<'a, T> CursorMut<'a, T> {
                 self.list.back = Some(in_back);
             } else {
                 // we are empty, become the input, remain on the ghost
-                std::mem::swap(self.list, &mut input);
+                // NOTE: swapping the lists would also swap their ids
+                self.list.front = input.front.take();
+                self.list.back = input.back.take();
             }
         }
 
         self.list.len += input.len;
-        // Not necessary but polite to do
+        // input is empty now and starts over with a fresh id like a new list
         input.len = 0;
+        input.id = next_id();
+    }
+
+    pub fn splice_after(&mut self, mut input: LinkedList<T, A>) {
+        self.splice_list_after(&mut input);
         // input dropped here
     }
 
-    pub fn splice_after(&mut self, mut input: LinkedList<T>) {
+    // like `splice_after`, but `input` is only emptied and not consumed, so
+    // `prepend` can use it on a list it borrows
+    fn splice_list_after(&mut self, input: &mut LinkedList<T, A>) {
+        // handles have to follow their nodes into our list
+        self.list.adopt(input);
         unsafe {
             if input.is_empty() {
                 // Input is empty do nothing.
@@ -706,23 +832,34 @@ impl<'a, T> CursorMut<'a, T> {
                 self.list.front = Some(in_front);
             } else {
                 // we are empty, become the input, remain on the ghost
-                std::mem::swap(self.list, &mut input);
+                // NOTE: swapping the lists would also swap their ids
+                self.list.front = input.front.take();
+                self.list.back = input.back.take();
             }
         }
 
         self.list.len += input.len;
-        // Not necessary but polite to do
+        // input is empty now and starts over with a fresh id like a new list
         input.len = 0;
-        // input dropped here
+        input.id = next_id();
     }
 
     pub fn insert_after(&mut self, elem: T) {
+        if let Err(err) = self.try_insert_after(elem) {
+            err.handle();
+        }
+    }
+
+    // like `insert_after`, but hands the element back instead of aborting
+    // when the allocator is out of memory
+    pub fn try_insert_after(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
         unsafe {
-            let new = NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+            let new = allocator::try_allocate_node(&self.list.alloc, Node {
                 front: None,
                 back: None,
+                ticket: None,
                 elem,
-            })));
+            }, |node| node.elem)?;
 
             if let Some(cur) = self.cur {
                 // we have elements in the list
@@ -738,6 +875,11 @@ impl<'a, T> CursorMut<'a, T> {
                     (*new.as_ptr()).front = Some(cur);
                     self.list.back = Some(new);
                 }
+            } else if let Some(front) = self.list.front {
+                // we are the ghost, so after us is the front of the list
+                (*front.as_ptr()).front = Some(new);
+                (*new.as_ptr()).back = Some(front);
+                self.list.front = Some(new);
             } else {
                 // we don't have elements in the list, such that `elem` will be the first entry
                 self.list.front = Some(new);
@@ -746,15 +888,25 @@ impl<'a, T> CursorMut<'a, T> {
             // increase length
             self.list.len += 1;
         }
+        Ok(())
     }
 
     pub fn insert_before(&mut self, elem: T) {
+        if let Err(err) = self.try_insert_before(elem) {
+            err.handle();
+        }
+    }
+
+    // like `insert_before`, but hands the element back instead of aborting
+    // when the allocator is out of memory
+    pub fn try_insert_before(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
         unsafe {
-            let new = NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+            let new = allocator::try_allocate_node(&self.list.alloc, Node {
                 front: None,
                 back: None,
+                ticket: None,
                 elem,
-            })));
+            }, |node| node.elem)?;
 
             if let Some(cur) = self.cur {
                 // we have elements in the list
@@ -770,6 +922,13 @@ impl<'a, T> CursorMut<'a, T> {
                     (*new.as_ptr()).back = Some(cur);
                     self.list.front = Some(new);
                 }
+                // the cursor does not move, but its element is one further back
+                *self.index.as_mut().unwrap() += 1;
+            } else if let Some(back) = self.list.back {
+                // we are the ghost, so before us is the back of the list
+                (*back.as_ptr()).back = Some(new);
+                (*new.as_ptr()).front = Some(back);
+                self.list.back = Some(new);
             } else {
                 // we don't have elements in the list, such that `elem` will be the first entry
                 self.list.front = Some(new);
@@ -778,6 +937,7 @@ impl<'a, T> CursorMut<'a, T> {
             // increase length
             self.list.len += 1;
         }
+        Ok(())
     }
 
     pub fn remove_after(&mut self) -> Option<T> {
@@ -786,11 +946,12 @@ impl<'a, T> CursorMut<'a, T> {
                 // current list has elements
                 if let Some(next) = (*cur.as_ptr()).back {
                     // there is an element to remove
-                    // bring back the Box in order to drop it in the end
-                    let boxed = Box::from_raw(next.as_ptr());
-                    let result = Some(boxed.elem);
+                    // move the node out of its allocation in order to drop it in the end
+                    let mut removed = allocator::deallocate(&self.list.alloc, next);
+                    self.list.untrack(&mut removed);
+                    let result = Some(removed.elem);
 
-                    if let Some(box_next) = boxed.back {
+                    if let Some(box_next) = removed.back {
                         // there is an element after the one to remove
                         (*cur.as_ptr()).back = Some(box_next);
                         (*box_next.as_ptr()).front = Some(cur);
@@ -803,7 +964,7 @@ impl<'a, T> CursorMut<'a, T> {
                     // decrease length
                     self.list.len -= 1;
                     result
-                    // drop boxed afterwards
+                    // drop removed afterwards
                 } else {
                     // we cannot remove the ghost element so we do nothing
                     None
@@ -811,10 +972,11 @@ impl<'a, T> CursorMut<'a, T> {
             } else {
                 // we are the ghost
                 if let Some(front) = self.list.front {
-                    let boxed = Box::from_raw(front.as_ptr());
-                    let result = Some(boxed.elem);
+                    let mut removed = allocator::deallocate(&self.list.alloc, front);
+                    self.list.untrack(&mut removed);
+                    let result = Some(removed.elem);
 
-                    if let Some(box_next) = boxed.back {
+                    if let Some(box_next) = removed.back {
                         // there is a next element which can be the new front of the list
                         self.list.front = Some(box_next);
                         (*box_next.as_ptr()).front = None;
@@ -827,7 +989,7 @@ impl<'a, T> CursorMut<'a, T> {
                     // decrease length
                     self.list.len -= 1;
                     result
-                    // drop boxed afterwards
+                    // drop removed afterwards
                 } else {
                     // empty list.. nothing to do
                     None
@@ -842,11 +1004,12 @@ impl<'a, T> CursorMut<'a, T> {
                 // current list has elements
                 if let Some(prev) = (*cur.as_ptr()).front {
                     // there is an element to remove
-                    // bring back the Box in order to drop it in the end
-                    let boxed = Box::from_raw(prev.as_ptr());
-                    let result = Some(boxed.elem);
+                    // move the node out of its allocation in order to drop it in the end
+                    let mut removed = allocator::deallocate(&self.list.alloc, prev);
+                    self.list.untrack(&mut removed);
+                    let result = Some(removed.elem);
 
-                    if let Some(box_prev) = boxed.front {
+                    if let Some(box_prev) = removed.front {
                         // there is an element before the one to remove
                         (*cur.as_ptr()).front = Some(box_prev);
                         (*box_prev.as_ptr()).back = Some(cur);
@@ -856,10 +1019,13 @@ impl<'a, T> CursorMut<'a, T> {
                         self.list.front = Some(cur);
                     }
 
+                    // the cursor does not move, but its element is one further
+                    // to the front
+                    *self.index.as_mut().unwrap() -= 1;
                     // decrease length
                     self.list.len -= 1;
                     result
-                    // drop boxed afterwards
+                    // drop removed afterwards
                 } else {
                     // we cannot remove the ghost element so we do nothing
                     None
@@ -867,10 +1033,11 @@ impl<'a, T> CursorMut<'a, T> {
             } else {
                 // we are the ghost
                 if let Some(back) = self.list.back {
-                    let boxed = Box::from_raw(back.as_ptr());
-                    let result = Some(boxed.elem);
+                    let mut removed = allocator::deallocate(&self.list.alloc, back);
+                    self.list.untrack(&mut removed);
+                    let result = Some(removed.elem);
 
-                    if let Some(box_prev) = boxed.front {
+                    if let Some(box_prev) = removed.front {
                         // there is a prev element which can be the new front of the list
                         self.list.back = Some(box_prev);
                         (*box_prev.as_ptr()).back = None;
@@ -883,7 +1050,7 @@ impl<'a, T> CursorMut<'a, T> {
                     // decrease length
                     self.list.len -= 1;
                     result
-                    // drop boxed afterwards
+                    // drop removed afterwards
                 } else {
                     // empty list.. nothing to do
                     None
@@ -891,4 +1058,1219 @@ impl<'a, T> CursorMut<'a, T> {
             }
         }
     }
+
+    pub fn remove_current(&mut self) -> Option<T> {
+        // the ghost cannot be removed
+        let cur = self.cur?;
+        unsafe {
+            // move onto the next element, which takes over our index, unless
+            // it is the ghost
+            self.cur = (*cur.as_ptr()).back;
+            if self.cur.is_none() {
+                self.index = None;
+            }
+            self.list.unlink_node(cur);
+            // move the node out of its allocation in order to drop it in the end
+            let mut removed = allocator::deallocate(&self.list.alloc, cur);
+            self.list.untrack(&mut removed);
+            Some(removed.elem)
+        }
+    }
+
+    pub fn remove_current_as_list(&mut self) -> Option<LinkedList<T, A>>
+    where
+        A: Clone,
+    {
+        // the ghost cannot be removed
+        let cur = self.cur?;
+        unsafe {
+            self.cur = (*cur.as_ptr()).back;
+            if self.cur.is_none() {
+                self.index = None;
+            }
+            self.list.unlink_node(cur);
+            // reuse the node instead of reallocating it
+            let mut output = LinkedList {
+                front: Some(cur),
+                back: Some(cur),
+                len: 1,
+                id: next_id(),
+                tracked: 0,
+                alloc: self.list.alloc.clone(),
+                _boo: PhantomData,
+            };
+
+            // handles have to follow their node into the output
+            let moved = LinkedList::<T, A>::retag(output.front, output.len, output.id);
+            output.tracked = moved;
+            self.list.tracked -= moved;
+            Some(output)
+        }
+    }
+
+    // replaces the element the cursor points to and returns the old one, on the
+    // ghost nothing can be replaced so `elem` is handed back as an error
+    pub fn replace_current(&mut self, elem: T) -> Result<T, T> {
+        match self.cur {
+            Some(cur) => unsafe { Ok(std::mem::replace(&mut (*cur.as_ptr()).elem, elem)) },
+            None => Err(elem),
+        }
+    }
+
+    // swaps the current node with its next node by relinking them, the cursor
+    // stays on its element and therefore moves one further back
+    pub fn swap_with_next(&mut self) -> bool {
+        unsafe {
+            if let Some(cur) = self.cur {
+                if let Some(next) = (*cur.as_ptr()).back {
+                    self.list.swap_adjacent(cur, next);
+                    *self.index.as_mut().unwrap() += 1;
+                    return true;
+                }
+            }
+        }
+        // either we are the ghost or there is no next element to swap with
+        false
+    }
+
+    // swaps the current node with its previous node by relinking them, the
+    // cursor stays on its element and therefore moves one further to the front
+    pub fn swap_with_prev(&mut self) -> bool {
+        unsafe {
+            if let Some(cur) = self.cur {
+                if let Some(prev) = (*cur.as_ptr()).front {
+                    self.list.swap_adjacent(prev, cur);
+                    *self.index.as_mut().unwrap() -= 1;
+                    return true;
+                }
+            }
+        }
+        // either we are the ghost or there is no previous element to swap with
+        false
+    }
+}
+
+// +-----------------------+
+// | Joining and Splitting |
+// +-----------------------+
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    // Moves all elements of `other` to the back of this list, leaving `other`
+    // empty. This is O(1) unless `other` has handles: every node of `other`
+    // is visited then to hand its handles over, which makes it O(n).
+    pub fn append(&mut self, other: &mut LinkedList<T, A>) {
+        // on the ghost the back of the list is right before us
+        self.cursor_mut().splice_list_before(other);
+    }
+
+    // Moves all elements of `other` to the front of this list, leaving `other`
+    // empty. This is O(1) unless `other` has handles: every node of `other`
+    // is visited then to hand its handles over, which makes it O(n).
+    pub fn prepend(&mut self, other: &mut LinkedList<T, A>) {
+        // on the ghost the front of the list is right after us
+        self.cursor_mut().splice_list_after(other);
+    }
+
+    // Splits the list into two at the given index. Returns everything from
+    // `at` onwards, while this list keeps everything in front of it. Finding
+    // `at` walks from the nearer end, and if the list has handles the split
+    // off nodes are visited as well to hand their handles over.
+    //
+    // The returned list needs an allocator of its own, hence `A: Clone`.
+    //
+    // Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+        if at == 0 {
+            return self.take_all();
+        }
+
+        // the node in front of the split, `at - 1 < len` so it exists
+        let node = self.node_at(at - 1);
+        CursorMut {
+            cur: node,
+            list: self,
+            index: Some(at - 1),
+        }
+        .split_after()
+    }
+}
+
+// +-----------+
+// | Filtering |
+// +-----------+
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    // Keeps only the elements for which `f` returns true. The removal is done
+    // through a cursor, which keeps the list consistent at every step, so
+    // a panicking `f` leaves a valid list behind.
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        while let Some(elem) = cursor.current() {
+            if f(elem) {
+                cursor.move_next();
+            } else {
+                // moves the cursor onto the next element
+                cursor.remove_current();
+            }
+        }
+    }
+
+    // Lazily removes and yields the elements for which `pred` returns true.
+    // Elements that are not visited, because the iterator is dropped early,
+    // remain in the list.
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F, A>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        ExtractIf { cursor, pred }
+    }
+
+    // Removes the elements in `range` from the list and returns them as an
+    // iterator. The range is unlinked right away and not only while iterating.
+    //
+    // Panics if the range is out of bounds or its start is after its end.
+    pub fn drain<R>(&mut self, range: R) -> IntoIter<T, A>
+    where
+        R: RangeBounds<usize>,
+        A: Clone,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len,
+        };
+        assert!(start <= end, "drain start is after its end");
+        assert!(end <= self.len, "drain end is out of bounds");
+
+        let mut rest = self.split_off(end);
+        let drained = self.split_off(start);
+        self.append(&mut rest);
+        drained.into_iter()
+    }
+}
+
+pub struct ExtractIf<'a, T, F, A: Allocator = Global>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    cursor: CursorMut<'a, T, A>,
+    pred: F,
+}
+
+impl<'a, T, F, A: Allocator> Iterator for ExtractIf<'a, T, F, A>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // once we reach the ghost we are done
+        while let Some(elem) = self.cursor.current() {
+            if (self.pred)(elem) {
+                return self.cursor.remove_current();
+            }
+            self.cursor.move_next();
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // at most all remaining elements are extracted
+        let remaining = match self.cursor.index() {
+            Some(index) => self.cursor.list.len - index,
+            None => 0,
+        };
+        (0, Some(remaining))
+    }
+}
+
+// +------------------------+
+// | Searching and Ordering |
+// +------------------------+
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|elem| elem == x)
+    }
+
+    pub fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    // searches from the back, but the index is still counted from the front
+    pub fn rposition<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().rposition(predicate)
+    }
+
+    pub fn find<P>(&self, mut predicate: P) -> Option<&T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|elem| predicate(elem))
+    }
+
+    // Removes consecutive repeated elements, keeping the first of each run.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    // Removes consecutive elements for which `same_bucket(next, kept)` returns
+    // true, where `kept` is the last element that stays in the list.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        // the cursor always stays on the last kept element
+        while let Some(cur) = cursor.cur {
+            unsafe {
+                let next = match (*cur.as_ptr()).back {
+                    Some(next) => next,
+                    None => break,
+                };
+                if same_bucket(&mut (*next.as_ptr()).elem, &mut (*cur.as_ptr()).elem) {
+                    cursor.remove_after();
+                } else {
+                    cursor.move_next();
+                }
+            }
+        }
+    }
+
+    // Reverses the list in place by swapping the links of every node.
+    pub fn reverse(&mut self) {
+        let mut node = self.front;
+        while let Some(cur) = node {
+            unsafe {
+                let cur = &mut *cur.as_ptr();
+                std::mem::swap(&mut cur.front, &mut cur.back);
+                // `front` is the old `back` now
+                node = cur.front;
+            }
+        }
+        std::mem::swap(&mut self.front, &mut self.back);
+    }
+
+    // Rotates the list `n` places to the left, such that the element at `n`
+    // becomes the front. Only the links at the ends of the list are changed.
+    //
+    // Panics if `n > len`.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len, "cannot rotate by more than the length");
+        if n == 0 || n == self.len {
+            return;
+        }
+
+        unsafe {
+            // 0 < n < len, so all of these exist
+            let new_front = self.node_at(n).unwrap();
+            let new_back = (*new_front.as_ptr()).front.unwrap();
+            let old_front = self.front.unwrap();
+            let old_back = self.back.unwrap();
+
+            // close the ring
+            (*old_back.as_ptr()).back = Some(old_front);
+            (*old_front.as_ptr()).front = Some(old_back);
+
+            // and open it up again in front of `new_front`
+            (*new_back.as_ptr()).back = None;
+            (*new_front.as_ptr()).front = None;
+            self.front = Some(new_front);
+            self.back = Some(new_back);
+        }
+    }
+
+    // Rotates the list `n` places to the right, such that the element at
+    // `len - n` becomes the front.
+    //
+    // Panics if `n > len`.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len, "cannot rotate by more than the length");
+        self.rotate_left(self.len - n);
+    }
+}
+
+// +------------------+
+// | Internal Helpers |
+// +------------------+
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    // Finds the node at `index` by walking from whichever end is closer.
+    fn node_at(&self, index: usize) -> Link<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        unsafe {
+            if index < self.len / 2 {
+                let mut node = self.front;
+                for _ in 0..index {
+                    node = (*node.unwrap().as_ptr()).back;
+                }
+                node
+            } else {
+                let mut node = self.back;
+                for _ in index + 1..self.len {
+                    node = (*node.unwrap().as_ptr()).front;
+                }
+                node
+            }
+        }
+    }
+
+    // Unlinks `node` from the list and fixes up its neighbours as well as the
+    // front and back of the list. The node itself is not freed.
+    //
+    // SAFETY: `node` has to be a node of this list.
+    unsafe fn unlink_node(&mut self, node: NonNull<Node<T>>) {
+        let front = (*node.as_ptr()).front.take();
+        let back = (*node.as_ptr()).back.take();
+
+        if let Some(prev) = front {
+            (*prev.as_ptr()).back = back;
+        } else {
+            // node was the front of the list
+            self.front = back;
+        }
+
+        if let Some(next) = back {
+            (*next.as_ptr()).front = front;
+        } else {
+            // node was the back of the list
+            self.back = front;
+        }
+
+        self.len -= 1;
+    }
+
+    // Marks the ticket of a node that is about to be freed as dead, such that
+    // all handles to it become stale.
+    fn untrack(&mut self, node: &mut Node<T>) {
+        if let Some(ticket) = node.ticket.take() {
+            ticket.owner.store(DEAD, AtomicOrdering::Relaxed);
+            self.tracked -= 1;
+        }
+    }
+
+    // Hands out a handle to `node`, allocating its ticket on first use.
+    //
+    // SAFETY: `node` has to be a node of this list.
+    unsafe fn track(&mut self, node: NonNull<Node<T>>) -> NodeHandle<T> {
+        let id = self.id;
+        let ticket = (*node.as_ptr()).ticket.get_or_insert_with(|| {
+            self.tracked += 1;
+            Arc::new(Ticket {
+                owner: AtomicU64::new(id),
+            })
+        });
+
+        NodeHandle {
+            node,
+            ticket: Arc::clone(ticket),
+        }
+    }
+
+    // Moves the tickets of the first `len` nodes starting at `node` over to the
+    // list with the id `owner` and returns how many of them had a ticket.
+    //
+    // SAFETY: `node` has to be followed by at least `len - 1` nodes.
+    unsafe fn retag(mut node: Link<T>, len: usize, owner: u64) -> usize {
+        let mut moved = 0;
+        for _ in 0..len {
+            let cur = node.unwrap();
+            if let Some(ticket) = &(*cur.as_ptr()).ticket {
+                ticket.owner.store(owner, AtomicOrdering::Relaxed);
+                moved += 1;
+            }
+            node = (*cur.as_ptr()).back;
+        }
+        moved
+    }
+
+    // Moves all nodes into a new list, which is handed back, and leaves this
+    // one empty with a fresh id just like `mem::take` would. Unlike
+    // `mem::take` this only needs a clone of the allocator and no `Default`.
+    fn take_all(&mut self) -> LinkedList<T, A>
+    where
+        A: Clone,
+    {
+        let empty = LinkedList::new_in(self.alloc.clone());
+        std::mem::replace(self, empty)
+    }
+
+    // Retags all tracked nodes of `other` to this list before they are linked
+    // into it. This is O(1) for lists without handles and O(n) otherwise.
+    fn adopt(&mut self, other: &mut LinkedList<T, A>) {
+        if other.tracked > 0 {
+            unsafe { Self::retag(other.front, other.len, self.id) };
+            self.tracked += other.tracked;
+            other.tracked = 0;
+        }
+    }
+
+    // Swaps the position of the two nodes `a` and `b` by relinking them.
+    //
+    // SAFETY: `a` and `b` have to be distinct nodes of this list with `a` in
+    // front of `b`.
+    unsafe fn swap_nodes(&mut self, a: NonNull<Node<T>>, b: NonNull<Node<T>>) {
+        if (*a.as_ptr()).back == Some(b) {
+            self.swap_adjacent(a, b);
+            return;
+        }
+
+        let a_prev = (*a.as_ptr()).front;
+        let a_next = (*a.as_ptr()).back;
+        let b_prev = (*b.as_ptr()).front;
+        let b_next = (*b.as_ptr()).back;
+
+        // there is at least one node between them, so both neighbours of `a`
+        // and `b` are distinct from each other
+        if let Some(prev) = a_prev {
+            (*prev.as_ptr()).back = Some(b);
+        } else {
+            self.front = Some(b);
+        }
+        (*a_next.unwrap().as_ptr()).front = Some(b);
+        (*b_prev.unwrap().as_ptr()).back = Some(a);
+        if let Some(next) = b_next {
+            (*next.as_ptr()).front = Some(a);
+        } else {
+            self.back = Some(a);
+        }
+
+        (*a.as_ptr()).front = b_prev;
+        (*a.as_ptr()).back = b_next;
+        (*b.as_ptr()).front = a_prev;
+        (*b.as_ptr()).back = a_next;
+    }
+
+    // Swaps the position of `a` and its next node `b` by relinking them.
+    //
+    // SAFETY: `a` and `b` have to be nodes of this list with `a.back == b`.
+    unsafe fn swap_adjacent(&mut self, a: NonNull<Node<T>>, b: NonNull<Node<T>>) {
+        let prev = (*a.as_ptr()).front;
+        let next = (*b.as_ptr()).back;
+
+        // prev <-> b
+        if let Some(prev) = prev {
+            (*prev.as_ptr()).back = Some(b);
+        } else {
+            self.front = Some(b);
+        }
+        (*b.as_ptr()).front = prev;
+
+        // b <-> a
+        (*b.as_ptr()).back = Some(a);
+        (*a.as_ptr()).front = Some(b);
+
+        // a <-> next
+        (*a.as_ptr()).back = next;
+        if let Some(next) = next {
+            (*next.as_ptr()).front = Some(a);
+        } else {
+            self.back = Some(a);
+        }
+    }
+}
+
+// +---------------------------------+
+// | Read-only Cursor Implementation |
+// +---------------------------------+
+
+// same "ghost" element model as `CursorMut`, but only borrows the list shared
+// such that multiple cursors can walk the same list at the same time
+pub struct Cursor<'a, T, A: Allocator = Global> {
+    cur: Link<T>,
+    list: &'a LinkedList<T, A>,
+    index: Option<usize>,
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    pub fn cursor(&self) -> Cursor<'_, T, A> {
+        // because we are using a "ghost" element we start at None
+        Cursor {
+            cur: None,
+            list: self,
+            index: None,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
+    // a read-only view at the same position, borrowing this cursor for as long
+    // as it is used
+    pub fn as_cursor(&self) -> Cursor<'_, T, A> {
+        Cursor {
+            cur: self.cur,
+            list: self.list,
+            index: self.index,
+        }
+    }
+}
+
+// derive would require `T: Clone`, which is not necessary to copy a cursor
+impl<'a, T, A: Allocator> Clone for Cursor<'a, T, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T, A: Allocator> Copy for Cursor<'a, T, A> {}
+
+impl<'a, T, A: Allocator> Cursor<'a, T, A> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                // we are on a real element, go to its next (back)
+                self.cur = (*cur.as_ptr()).back;
+                if self.cur.is_some() {
+                    *self.index.as_mut().unwrap() += 1;
+                } else {
+                    // We just walked to the ghost, no more index
+                    self.index = None;
+                }
+            }
+        } else if !self.list.is_empty() {
+            // we are the ghost, and there is a real front, so move to it
+            self.cur = self.list.front;
+            self.index = Some(0);
+        } else {
+            // we are the ghost, but that's the only element.. nothing to do
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                // we are on a real element, go to its previous (front)
+                self.cur = (*cur.as_ptr()).front;
+                if self.cur.is_some() {
+                    *self.index.as_mut().unwrap() -= 1;
+                } else {
+                    // We just walked to the ghost, no more index
+                    self.index = None;
+                }
+            }
+        } else if !self.list.is_empty() {
+            // we are the ghost, and there is a real back, so move to it
+            self.cur = self.list.back;
+            self.index = Some(self.list.len - 1);
+        } else {
+            // we are the ghost, but that's the only element.. nothing to do
+        }
+    }
+
+    // the returned references are bound to the list and not to the cursor, as
+    // nobody can mutate the list while it is borrowed shared
+    pub fn current(&self) -> Option<&'a T> {
+        unsafe { self.cur.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        unsafe {
+            let next = if let Some(cur) = self.cur {
+                // try to follow the cur node's back pointer
+                (*cur.as_ptr()).back
+            } else {
+                // ghost -> try to follow the list's front pointer
+                self.list.front
+            };
+
+            next.map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        unsafe {
+            let prev = if let Some(cur) = self.cur {
+                // try to follow the cur node's front pointer
+                (*cur.as_ptr()).front
+            } else {
+                // ghost -> try to use the list's back pointer
+                self.list.back
+            };
+
+            prev.map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+}
+
+// Cursor only hands out shared references just like Iter does
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Send for Cursor<'a, T, A> {}
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Sync for Cursor<'a, T, A> {}
+
+// +--------------+
+// | Node Handles |
+// +--------------+
+
+// A stable reference to a single node of a list, which allows to access or
+// remove that node in O(1) without walking the list. A handle stays valid while
+// its node is part of a list (even if the node is moved to another list through
+// splitting or splicing) and becomes stale once the node is removed. Stale
+// handles and handles used with a list the node does not belong to are
+// detected and rejected.
+pub struct NodeHandle<T> {
+    node: NonNull<Node<T>>,
+    ticket: Arc<Ticket>,
+}
+
+// derive would require `T: Clone`, which is not necessary to clone a handle
+impl<T> Clone for NodeHandle<T> {
+    fn clone(&self) -> Self {
+        NodeHandle {
+            node: self.node,
+            ticket: Arc::clone(&self.ticket),
+        }
+    }
+}
+
+impl<T> PartialEq for NodeHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.ticket, &other.ticket)
+    }
+}
+
+impl<T> Eq for NodeHandle<T> {}
+
+impl<T> Debug for NodeHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeHandle")
+            .field("node", &self.node)
+            .field("stale", &self.is_stale())
+            .finish()
+    }
+}
+
+impl<T> NodeHandle<T> {
+    // whether the node of this handle has been removed from its list
+    pub fn is_stale(&self) -> bool {
+        self.ticket.owner.load(AtomicOrdering::Relaxed) == DEAD
+    }
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    pub fn push_front_handle(&mut self, elem: T) -> NodeHandle<T> {
+        self.push_front(elem);
+        unsafe { self.track(self.front.unwrap()) }
+    }
+
+    pub fn push_back_handle(&mut self, elem: T) -> NodeHandle<T> {
+        self.push_back(elem);
+        unsafe { self.track(self.back.unwrap()) }
+    }
+
+    // whether `handle` points to a node of this list, only then the node is
+    // known to be alive and can be dereferenced
+    pub fn contains_handle(&self, handle: &NodeHandle<T>) -> bool {
+        handle.ticket.owner.load(AtomicOrdering::Relaxed) == self.id
+    }
+}
+
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
+    pub fn insert_after_handle(&mut self, elem: T) -> NodeHandle<T> {
+        self.insert_after(elem);
+        unsafe {
+            let new = match self.cur {
+                Some(cur) => (*cur.as_ptr()).back,
+                // on the ghost we inserted at the front
+                None => self.list.front,
+            };
+            self.list.track(new.unwrap())
+        }
+    }
+
+    pub fn insert_before_handle(&mut self, elem: T) -> NodeHandle<T> {
+        self.insert_before(elem);
+        unsafe {
+            let new = match self.cur {
+                Some(cur) => (*cur.as_ptr()).front,
+                // on the ghost we inserted at the back
+                None => self.list.back,
+            };
+            self.list.track(new.unwrap())
+        }
+    }
+}
+
+// A handle is only dereferenced through the list it belongs to, which already
+// enforces the required bounds on T
+unsafe impl<T> Send for NodeHandle<T> {}
+unsafe impl<T> Sync for NodeHandle<T> {}
+
+// +---------+
+// | Sorting |
+// +---------+
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    // Stable bottom-up merge sort, which only relinks the nodes. This takes
+    // O(n log n) time, O(1) extra memory and never moves an element.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.len < 2 {
+            return;
+        }
+        unsafe { MergeSort::new(self).sort(&mut compare) };
+        // the list is restored when the `MergeSort` is dropped
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    // A linked list cannot be sorted any faster when giving up stability, so
+    // this is the same as `sort_by`.
+    pub fn sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.sort_by(compare);
+    }
+
+    // Merges the already sorted `other` into this already sorted list in O(n).
+    // For equal elements the ones of this list come first.
+    pub fn merge<F>(&mut self, mut other: LinkedList<T, A>, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if other.is_empty() {
+            return;
+        }
+
+        let (left_len, right_len) = (self.len, other.len);
+        // handles have to follow their nodes into our list
+        self.adopt(&mut other);
+
+        unsafe {
+            // chain both lists together, the front pointers are fixed up by the
+            // `MergeSort` afterwards
+            if let Some(back) = self.back {
+                (*back.as_ptr()).back = other.front.take();
+            } else {
+                self.front = other.front.take();
+            }
+            self.back = other.back.take();
+            self.len += other.len;
+            other.len = 0;
+
+            if left_len > 0 {
+                MergeSort::new(self).merge(left_len, right_len, &mut compare);
+            }
+        }
+    }
+}
+
+// State of a bottom-up merge sort. While sorting, the nodes are only chained
+// through their `back` pointers. All nodes are either part of the already
+// merged chain (`head` to `tail`), the rest of the left run that is currently
+// merged (`left_len` nodes from `left`) or the properly terminated chain
+// starting at `right`. This way the list can be restored when dropped, even if
+// the comparison panicked midway.
+struct MergeSort<'a, T, A: Allocator> {
+    list: &'a mut LinkedList<T, A>,
+    head: Link<T>,
+    tail: Link<T>,
+    left: Link<T>,
+    left_len: usize,
+    right: Link<T>,
+}
+
+impl<'a, T, A: Allocator> MergeSort<'a, T, A> {
+    fn new(list: &'a mut LinkedList<T, A>) -> Self {
+        let right = list.front;
+        MergeSort {
+            list,
+            head: None,
+            tail: None,
+            left: None,
+            left_len: 0,
+            right,
+        }
+    }
+
+    // appends `node` to the merged chain
+    unsafe fn push(&mut self, node: NonNull<Node<T>>) {
+        if let Some(tail) = self.tail {
+            (*tail.as_ptr()).back = Some(node);
+        } else {
+            self.head = Some(node);
+        }
+        self.tail = Some(node);
+    }
+
+    unsafe fn sort<F>(&mut self, compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut run = 1;
+        loop {
+            // one pass merges all pairs of neighbouring runs
+            let mut merges = 0;
+            while self.right.is_some() {
+                self.merge(run, run, compare);
+                merges += 1;
+            }
+
+            if merges <= 1 {
+                // everything has been merged into a single run
+                break;
+            }
+
+            // start the next pass on the merged chain
+            (*self.tail.unwrap().as_ptr()).back = None;
+            self.right = self.head.take();
+            self.tail = None;
+            run *= 2;
+        }
+    }
+
+    // merges the next `left_len` nodes with up to `right_len` nodes after them
+    unsafe fn merge<F>(&mut self, left_len: usize, mut right_len: usize, compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // split off the left run
+        self.left = self.right;
+        self.left_len = 0;
+        while self.left_len < left_len {
+            match self.right {
+                Some(node) => self.right = (*node.as_ptr()).back,
+                None => break,
+            }
+            self.left_len += 1;
+        }
+
+        loop {
+            let right = if right_len > 0 { self.right } else { None };
+            let node = match (self.left_len > 0, right) {
+                (false, None) => break,
+                (true, Some(right)) => {
+                    let left = self.left.unwrap();
+                    // take from the left on equality to stay stable
+                    if compare(&(*left.as_ptr()).elem, &(*right.as_ptr()).elem)
+                        != Ordering::Greater
+                    {
+                        self.take_left()
+                    } else {
+                        right_len -= 1;
+                        self.take_right()
+                    }
+                }
+                (true, None) => self.take_left(),
+                (false, Some(_)) => {
+                    right_len -= 1;
+                    self.take_right()
+                }
+            };
+            self.push(node);
+        }
+    }
+
+    unsafe fn take_left(&mut self) -> NonNull<Node<T>> {
+        let node = self.left.unwrap();
+        self.left = (*node.as_ptr()).back;
+        self.left_len -= 1;
+        node
+    }
+
+    unsafe fn take_right(&mut self) -> NonNull<Node<T>> {
+        let node = self.right.unwrap();
+        self.right = (*node.as_ptr()).back;
+        node
+    }
+}
+
+impl<'a, T, A: Allocator> Drop for MergeSort<'a, T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            // put everything that is not merged yet behind the merged chain
+            while self.left_len > 0 {
+                let node = self.take_left();
+                self.push(node);
+            }
+            if let Some(tail) = self.tail {
+                (*tail.as_ptr()).back = self.right;
+            } else {
+                self.head = self.right;
+            }
+
+            // and restore the front pointers of the whole chain
+            let mut prev = None;
+            let mut node = self.head;
+            while let Some(cur) = node {
+                (*cur.as_ptr()).front = prev;
+                prev = Some(cur);
+                node = (*cur.as_ptr()).back;
+            }
+            self.list.front = self.head;
+            self.list.back = prev;
+        }
+    }
+}
+
+// +-------------------+
+// | Positional Access |
+// +-------------------+
+
+// Something that locates a single node of a list, either an index (walking
+// from whichever end of the list is closer) or a `NodeHandle` (O(1)). It's
+// sealed, only the list itself knows how to locate its nodes.
+pub trait Position<T>: sealed::Locate<T> {}
+
+impl<T, P: sealed::Locate<T>> Position<T> for P {}
+
+mod sealed {
+    use super::{Allocator, LinkedList, Node, NodeHandle, NonNull};
+
+    pub trait Locate<T> {
+        fn locate<A: Allocator>(self, list: &LinkedList<T, A>) -> Option<Located<T>>;
+    }
+
+    // a node of a list and its index if that is known already
+    pub struct Located<T> {
+        pub(super) node: NonNull<Node<T>>,
+        pub(super) index: Option<usize>,
+    }
+
+    impl<T> Locate<T> for usize {
+        fn locate<A: Allocator>(self, list: &LinkedList<T, A>) -> Option<Located<T>> {
+            list.node_at(self).map(|node| Located {
+                node,
+                index: Some(self),
+            })
+        }
+    }
+
+    impl<T> Locate<T> for &NodeHandle<T> {
+        fn locate<A: Allocator>(self, list: &LinkedList<T, A>) -> Option<Located<T>> {
+            if list.contains_handle(self) {
+                Some(Located {
+                    node: self.node,
+                    index: None,
+                })
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    pub fn get<P: Position<T>>(&self, at: P) -> Option<&T> {
+        at.locate(self)
+            .map(|located| unsafe { &(*located.node.as_ptr()).elem })
+    }
+
+    pub fn get_mut<P: Position<T>>(&mut self, at: P) -> Option<&mut T> {
+        at.locate(self)
+            .map(|located| unsafe { &mut (*located.node.as_ptr()).elem })
+    }
+
+    pub fn remove<P: Position<T>>(&mut self, at: P) -> Option<T> {
+        let node = at.locate(self)?.node;
+        unsafe {
+            self.unlink_node(node);
+            let mut removed = allocator::deallocate(&self.alloc, node);
+            self.untrack(&mut removed);
+            Some(removed.elem)
+        }
+    }
+
+    // Places a cursor on the located node. Nodes do not know their position, so
+    // finding the index of the cursor for a handle is O(n).
+    pub fn cursor_at<P: Position<T>>(&mut self, at: P) -> Option<CursorMut<'_, T, A>> {
+        let located = at.locate(self)?;
+        let index = located.index.unwrap_or_else(|| {
+            // count the nodes in front of ours
+            let mut index = 0;
+            let mut prev = unsafe { (*located.node.as_ptr()).front };
+            while let Some(node) = prev {
+                index += 1;
+                prev = unsafe { (*node.as_ptr()).front };
+            }
+            index
+        });
+
+        Some(CursorMut {
+            cur: Some(located.node),
+            list: self,
+            index: Some(index),
+        })
+    }
+
+    // Inserts `elem` such that it ends up at `index`.
+    //
+    // Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, elem: T) {
+        if let Err(err) = self.try_insert(index, elem) {
+            err.handle();
+        }
+    }
+
+    // like `insert`, but hands the element back instead of aborting when the
+    // allocator is out of memory
+    //
+    // Panics if `index > len`.
+    pub fn try_insert(&mut self, index: usize, elem: T) -> Result<(), TryReserveError<T>> {
+        assert!(index <= self.len, "insertion index is out of bounds");
+        if index == self.len {
+            self.try_push_back(elem)
+        } else {
+            self.cursor_at(index).unwrap().try_insert_before(elem)
+        }
+    }
+
+    // Swaps the elements at `i` and `j` by relinking their nodes, such that
+    // handles stay with their elements.
+    //
+    // Panics if `i` or `j` are out of bounds.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let a = self.node_at(i).expect("swap index is out of bounds");
+        let b = self.node_at(j).expect("swap index is out of bounds");
+        match i.cmp(&j) {
+            Ordering::Less => unsafe { self.swap_nodes(a, b) },
+            Ordering::Greater => unsafe { self.swap_nodes(b, a) },
+            Ordering::Equal => {}
+        }
+    }
+}
+
+impl<T, A: Allocator> Index<usize> for LinkedList<T, A> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index is out of bounds")
+    }
+}
+
+impl<T, A: Allocator> IndexMut<usize> for LinkedList<T, A> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index is out of bounds")
+    }
+}
+
+// +--------+
+// | Traits |
+// +--------+
+
+// as a stack the front of the list is its top
+impl<T, A: Allocator> Stack<T> for LinkedList<T, A> {
+    fn push(&mut self, elem: T) {
+        LinkedList::push_front(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        LinkedList::pop_front(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        LinkedList::front(self)
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut T> {
+        LinkedList::front_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+}
+
+impl<T, A: Allocator> Queue<T> for LinkedList<T, A> {
+    fn push_back(&mut self, elem: T) {
+        LinkedList::push_back(self, elem)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        LinkedList::pop_front(self)
+    }
+
+    fn front(&self) -> Option<&T> {
+        LinkedList::front(self)
+    }
+
+    fn front_mut(&mut self) -> Option<&mut T> {
+        LinkedList::front_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+}
+
+impl<T, A: Allocator> Deque<T> for LinkedList<T, A> {
+    fn push_front(&mut self, elem: T) {
+        LinkedList::push_front(self, elem)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        LinkedList::pop_back(self)
+    }
+
+    fn back(&self) -> Option<&T> {
+        LinkedList::back(self)
+    }
+
+    fn back_mut(&mut self) -> Option<&mut T> {
+        LinkedList::back_mut(self)
+    }
 }
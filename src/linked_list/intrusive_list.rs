@@ -0,0 +1,608 @@
+// Doubly linked list, which does not own its elements and never allocates.
+//
+// The links live inside the elements: users embed a `Links` field in their own
+// struct, implement `Linked` to tell the list where it is and then link
+// elements they already own. An element must not move while it is linked, so
+// the list only takes pinned references, which also keep the elements alive
+// for as long as the list can reach them.
+//
+// Every element remembers the id of the list it is linked into. That is enough
+// to reject elements which are already in a list, and to unlink an element in
+// O(1) without trusting the caller that it is in this very list. The price is
+// paid when elements move to another list: `append`, `prepend` and the
+// splitting and splicing of the cursor relink in O(1), but every moved element
+// has to be told about its new list, which makes them O(n) in the number of
+// moved elements.
+
+use std::cell::Cell;
+use std::fmt::{self, Debug};
+use std::iter::FromIterator;
+use std::marker::{PhantomData, PhantomPinned};
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub struct IntrusiveList<'a, T: Linked> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    // unique id of this list, such that elements can tell whether they belong
+    // to it
+    id: u64,
+    _boo: PhantomData<Pin<&'a T>>,
+}
+
+type Link<T> = Option<NonNull<T>>;
+
+// The field to embed into the elements. Only shared references to the elements
+// exist while they are linked, hence the cells.
+pub struct Links<T> {
+    front: Cell<Link<T>>,
+    back: Cell<Link<T>>,
+    owner: Cell<u64>,
+    // the list points to the element, so it must stay where it is
+    _pin: PhantomPinned,
+}
+
+// Tells the list where the `Links` of an element are.
+//
+// Safety: implementations have to return the same `Links` field of `self`
+// every time, otherwise the list would follow links it never set.
+#[allow(clippy::missing_safety_doc)]
+pub unsafe trait Linked: Sized {
+    fn links(&self) -> &Links<Self>;
+}
+
+const UNLINKED: u64 = 0;
+
+// 0 is reserved for `UNLINKED`
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+impl<T> Links<T> {
+    pub const fn new() -> Self {
+        Links {
+            front: Cell::new(None),
+            back: Cell::new(None),
+            owner: Cell::new(UNLINKED),
+            _pin: PhantomPinned,
+        }
+    }
+
+    pub fn is_linked(&self) -> bool {
+        self.owner.get() != UNLINKED
+    }
+}
+
+impl<T> Default for Links<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for Links<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Links")
+            .field("linked", &self.is_linked())
+            .finish()
+    }
+}
+
+// only ever called with elements of a list, which outlive it
+fn links<'b, T: Linked>(node: NonNull<T>) -> &'b Links<T> {
+    unsafe { (*node.as_ptr()).links() }
+}
+
+// the elements were pinned when they were linked
+unsafe fn pinned<'a, T>(node: NonNull<T>) -> Pin<&'a T> {
+    Pin::new_unchecked(&*node.as_ptr())
+}
+
+impl<'a, T: Linked> IntrusiveList<'a, T> {
+    pub fn new() -> Self {
+        IntrusiveList {
+            front: None,
+            back: None,
+            len: 0,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // panics if `elem` is already in a list
+    pub fn push_front(&mut self, elem: Pin<&'a T>) {
+        self.link_between(None, self.front, elem);
+    }
+
+    // panics if `elem` is already in a list
+    pub fn push_back(&mut self, elem: Pin<&'a T>) {
+        self.link_between(self.back, None, elem);
+    }
+
+    pub fn pop_front(&mut self) -> Option<Pin<&'a T>> {
+        let node = self.front?;
+        Some(self.unlink(node))
+    }
+
+    pub fn pop_back(&mut self) -> Option<Pin<&'a T>> {
+        let node = self.back?;
+        Some(self.unlink(node))
+    }
+
+    pub fn front(&self) -> Option<Pin<&'a T>> {
+        self.front.map(|node| unsafe { pinned(node) })
+    }
+
+    pub fn back(&self) -> Option<Pin<&'a T>> {
+        self.back.map(|node| unsafe { pinned(node) })
+    }
+
+    // unlinks all elements, such that they can be linked again
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn contains(&self, elem: &T) -> bool {
+        elem.links().owner.get() == self.id
+    }
+
+    // O(1), returns false if `elem` is not in this list
+    pub fn remove(&mut self, elem: &T) -> bool {
+        if !self.contains(elem) {
+            return false;
+        }
+        self.unlink(NonNull::from(elem));
+        true
+    }
+
+    fn link_between(&mut self, front: Link<T>, back: Link<T>, elem: Pin<&'a T>) -> NonNull<T> {
+        let new_links = elem.links();
+        assert!(!new_links.is_linked(), "element is already in a list");
+        let new = NonNull::from(elem.get_ref());
+        new_links.owner.set(self.id);
+        new_links.front.set(front);
+        new_links.back.set(back);
+        match front {
+            Some(front) => links(front).back.set(Some(new)),
+            None => self.front = Some(new),
+        }
+        match back {
+            Some(back) => links(back).front.set(Some(new)),
+            None => self.back = Some(new),
+        }
+        self.len += 1;
+        new
+    }
+
+    // `node` has to be in this list
+    fn unlink(&mut self, node: NonNull<T>) -> Pin<&'a T> {
+        let node_links = links(node);
+        let front = node_links.front.take();
+        let back = node_links.back.take();
+        node_links.owner.set(UNLINKED);
+        match front {
+            Some(front) => links(front).back.set(back),
+            None => self.front = back,
+        }
+        match back {
+            Some(back) => links(back).front.set(front),
+            None => self.back = front,
+        }
+        self.len -= 1;
+        unsafe { pinned(node) }
+    }
+
+    // Links all elements of `chain` in between `front` and `back`, which have
+    // to be neighbours (or the ghost), and leaves `chain` empty.
+    fn link_chain_between(&mut self, front: Link<T>, back: Link<T>, chain: &mut Self) {
+        let (Some(first), Some(last)) = (chain.front.take(), chain.back.take()) else {
+            return;
+        };
+        set_owner(Some(first), self.id);
+        links(first).front.set(front);
+        links(last).back.set(back);
+        match front {
+            Some(front) => links(front).back.set(Some(first)),
+            None => self.front = Some(first),
+        }
+        match back {
+            Some(back) => links(back).front.set(Some(last)),
+            None => self.back = Some(last),
+        }
+        self.len += chain.len;
+        chain.len = 0;
+    }
+
+    // Unlinks the `len` elements from `first` to `last` into a list of their
+    // own.
+    fn unlink_chain(&mut self, first: NonNull<T>, last: NonNull<T>, len: usize) -> Self {
+        let front = links(first).front.take();
+        let back = links(last).back.take();
+        match front {
+            Some(front) => links(front).back.set(back),
+            None => self.front = back,
+        }
+        match back {
+            Some(back) => links(back).front.set(front),
+            None => self.back = front,
+        }
+        self.len -= len;
+        let mut output = Self::new();
+        output.front = Some(first);
+        output.back = Some(last);
+        output.len = len;
+        set_owner(output.front, output.id);
+        output
+    }
+}
+
+// tells every element from `node` to the end of its chain that it belongs to
+// the list `owner` now
+fn set_owner<T: Linked>(mut node: Link<T>, owner: u64) {
+    while let Some(cur) = node {
+        let cur_links = links(cur);
+        cur_links.owner.set(owner);
+        node = cur_links.back.get();
+    }
+}
+
+// +---------+
+// | Joining |
+// +---------+
+
+impl<'a, T: Linked> IntrusiveList<'a, T> {
+    // Moves all elements of `other` to the back of this list, leaving `other`
+    // empty. O(n) in the length of `other`, see the top of the file.
+    pub fn append(&mut self, other: &mut Self) {
+        self.link_chain_between(self.back, None, other);
+    }
+
+    // Moves all elements of `other` to the front of this list, leaving `other`
+    // empty. O(n) in the length of `other`, see the top of the file.
+    pub fn prepend(&mut self, other: &mut Self) {
+        self.link_chain_between(None, self.front, other);
+    }
+}
+
+impl<'a, T: Linked> Drop for IntrusiveList<'a, T> {
+    fn drop(&mut self) {
+        // the elements outlive the list, so they have to forget about it
+        self.clear();
+    }
+}
+
+// +-----------------+
+// | Iterator Traits |
+// +-----------------+
+
+// There is no `IterMut`, the list only holds shared references. Elements which
+// should be changed in place need cells of their own.
+pub struct Iter<'l, 'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'l Pin<&'a T>>,
+}
+
+impl<'a, T: Linked> IntrusiveList<'a, T> {
+    pub fn iter(&self) -> Iter<'_, 'a, T> {
+        Iter {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'l, 'a, T: Linked> IntoIterator for &'l IntrusiveList<'a, T> {
+    type Item = Pin<&'a T>;
+
+    type IntoIter = Iter<'l, 'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'l, 'a, T: Linked> Iterator for Iter<'l, 'a, T> {
+    type Item = Pin<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.front.map(|node| {
+            self.front = links(node).back.get();
+            unsafe { pinned(node) }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'l, 'a, T: Linked> DoubleEndedIterator for Iter<'l, 'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.back.map(|node| {
+            self.back = links(node).front.get();
+            unsafe { pinned(node) }
+        })
+    }
+}
+
+impl<'l, 'a, T: Linked> ExactSizeIterator for Iter<'l, 'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+// unlinks the elements one by one
+pub struct IntoIter<'a, T: Linked> {
+    list: IntrusiveList<'a, T>,
+}
+
+impl<'a, T: Linked> IntoIterator for IntrusiveList<'a, T> {
+    type Item = Pin<&'a T>;
+
+    type IntoIter = IntoIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T: Linked> Iterator for IntoIter<'a, T> {
+    type Item = Pin<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<'a, T: Linked> DoubleEndedIterator for IntoIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<'a, T: Linked> ExactSizeIterator for IntoIter<'a, T> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<'a, T: Linked> Default for IntrusiveList<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Linked> Extend<Pin<&'a T>> for IntrusiveList<'a, T> {
+    fn extend<I: IntoIterator<Item = Pin<&'a T>>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+impl<'a, T: Linked> FromIterator<Pin<&'a T>> for IntrusiveList<'a, T> {
+    fn from_iter<I: IntoIterator<Item = Pin<&'a T>>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<'a, T: Linked + Debug> Debug for IntrusiveList<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+// +-----------------------+
+// | Cursor Implementation |
+// +-----------------------+
+pub struct CursorMut<'l, 'a, T: Linked> {
+    cur: Link<T>,
+    list: &'l mut IntrusiveList<'a, T>,
+    index: Option<usize>,
+}
+
+impl<'a, T: Linked> IntrusiveList<'a, T> {
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, 'a, T> {
+        // because we are using a "ghost" element we start at None
+        CursorMut {
+            cur: None,
+            list: self,
+            index: None,
+        }
+    }
+}
+
+impl<'l, 'a, T: Linked> CursorMut<'l, 'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        self.cur = match self.cur {
+            Some(cur) => links(cur).back.get(),
+            // the ghost is followed by the front
+            None => self.list.front,
+        };
+        self.index = match (self.cur, self.index) {
+            (None, _) => None,
+            (Some(_), Some(index)) => Some(index + 1),
+            (Some(_), None) => Some(0),
+        };
+    }
+
+    pub fn move_prev(&mut self) {
+        self.cur = match self.cur {
+            Some(cur) => links(cur).front.get(),
+            // the ghost is preceded by the back
+            None => self.list.back,
+        };
+        self.index = match (self.cur, self.index) {
+            (None, _) => None,
+            (Some(_), Some(index)) => Some(index - 1),
+            (Some(_), None) => Some(self.list.len - 1),
+        };
+    }
+
+    pub fn current(&self) -> Option<Pin<&'a T>> {
+        self.cur.map(|node| unsafe { pinned(node) })
+    }
+
+    pub fn peek_next(&self) -> Option<Pin<&'a T>> {
+        let next = match self.cur {
+            Some(cur) => links(cur).back.get(),
+            None => self.list.front,
+        };
+        next.map(|node| unsafe { pinned(node) })
+    }
+
+    pub fn peek_prev(&self) -> Option<Pin<&'a T>> {
+        let prev = match self.cur {
+            Some(cur) => links(cur).front.get(),
+            None => self.list.back,
+        };
+        prev.map(|node| unsafe { pinned(node) })
+    }
+
+    // panics if `elem` is already in a list
+    pub fn insert_after(&mut self, elem: Pin<&'a T>) {
+        let next = match self.cur {
+            Some(cur) => links(cur).back.get(),
+            None => self.list.front,
+        };
+        self.list.link_between(self.cur, next, elem);
+    }
+
+    // panics if `elem` is already in a list
+    pub fn insert_before(&mut self, elem: Pin<&'a T>) {
+        let prev = match self.cur {
+            Some(cur) => links(cur).front.get(),
+            None => self.list.back,
+        };
+        self.list.link_between(prev, self.cur, elem);
+        // the cursor does not move, but its element is one further back
+        if let Some(index) = self.index.as_mut() {
+            *index += 1;
+        }
+    }
+
+    // unlinks the current element and moves onto the next one
+    pub fn remove_current(&mut self) -> Option<Pin<&'a T>> {
+        // the ghost cannot be removed
+        let cur = self.cur?;
+        self.cur = links(cur).back.get();
+        if self.cur.is_none() {
+            self.index = None;
+        }
+        Some(self.list.unlink(cur))
+    }
+
+    // unlinks the element after the cursor, the front when on the ghost
+    pub fn remove_after(&mut self) -> Option<Pin<&'a T>> {
+        let next = match self.cur {
+            Some(cur) => links(cur).back.get(),
+            None => self.list.front,
+        }?;
+        Some(self.list.unlink(next))
+    }
+
+    // unlinks the element before the cursor, the back when on the ghost
+    pub fn remove_before(&mut self) -> Option<Pin<&'a T>> {
+        let prev = match self.cur {
+            Some(cur) => links(cur).front.get(),
+            None => self.list.back,
+        }?;
+        // the cursor does not move, but its element is one further to the front
+        if let Some(index) = self.index.as_mut() {
+            *index -= 1;
+        }
+        Some(self.list.unlink(prev))
+    }
+
+    // Everything after the cursor is returned as a new list, on the ghost that
+    // is the whole list.
+    pub fn split_after(&mut self) -> IntrusiveList<'a, T> {
+        let next = match self.cur {
+            Some(cur) => links(cur).back.get(),
+            None => self.list.front,
+        };
+        match (next, self.list.back) {
+            (Some(first), Some(last)) => {
+                let len = self.list.len - self.index.map_or(0, |index| index + 1);
+                self.list.unlink_chain(first, last, len)
+            },
+            _ => IntrusiveList::new(),
+        }
+    }
+
+    // Everything before the cursor is returned as a new list, on the ghost that
+    // is the whole list.
+    pub fn split_before(&mut self) -> IntrusiveList<'a, T> {
+        let prev = match self.cur {
+            Some(cur) => links(cur).front.get(),
+            None => self.list.back,
+        };
+        match (self.list.front, prev) {
+            (Some(first), Some(last)) => {
+                let len = self.index.unwrap_or(self.list.len);
+                if self.index.is_some() {
+                    // the current element is the front now
+                    self.index = Some(0);
+                }
+                self.list.unlink_chain(first, last, len)
+            },
+            _ => IntrusiveList::new(),
+        }
+    }
+
+    // moves the elements of `input` in between the cursor and the element after
+    // it, the cursor stays where it is
+    pub fn splice_after(&mut self, mut input: IntrusiveList<'a, T>) {
+        let next = match self.cur {
+            Some(cur) => links(cur).back.get(),
+            None => self.list.front,
+        };
+        self.list.link_chain_between(self.cur, next, &mut input);
+    }
+
+    // moves the elements of `input` in between the element before the cursor
+    // and the cursor, whose index grows by their number
+    pub fn splice_before(&mut self, mut input: IntrusiveList<'a, T>) {
+        let prev = match self.cur {
+            Some(cur) => links(cur).front.get(),
+            None => self.list.back,
+        };
+        if let Some(index) = self.index.as_mut() {
+            *index += input.len;
+        }
+        self.list.link_chain_between(prev, self.cur, &mut input);
+    }
+}
@@ -0,0 +1,404 @@
+// Inmutable first in, first out queue, built from two persistent stacks: the
+// front, from which elements are taken, and the reversed rear, onto which new
+// elements are put.
+//
+// A simple two stack queue reverses the whole rear once the front runs empty,
+// which is only cheap on average as long as every version of the queue is used
+// once. Reusing an old version would reverse the same rear again and again.
+// Instead this is the real-time queue of Hood and Melville: as soon as the rear
+// gets longer than the front a rotation (front ++ reverse(rear)) is started and
+// a few steps of it are executed with every following operation. The rotation
+// is done before the front runs out, so every operation is O(1) in the worst
+// case, no matter which versions are reused.
+
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::Deref;
+
+use super::persistent_stack::{self, ArcFamily, PointerFamily, RcFamily};
+
+pub struct SharedList<T, P: PointerFamily> {
+    // number of elements in front of the rear, including the ones of a running
+    // rotation that are not yet part of `front`
+    front_len: usize,
+    front: Stack<T, P>,
+    rotation: Rotation<T, P>,
+    rear_len: usize,
+    rear: Stack<T, P>,
+}
+
+// single threaded queue
+pub type List<T> = SharedList<T, RcFamily>;
+
+// thread-safe queue, which can share its nodes between threads
+pub type SyncList<T> = SharedList<T, ArcFamily>;
+
+// The elements are shared as well, such that moving them between the stacks
+// during a rotation only copies a pointer and never the element.
+type Stack<T, P> = persistent_stack::SharedList<<P as PointerFamily>::Pointer<T>, P>;
+
+// State of the incremental `front ++ reverse(rear)`.
+enum Rotation<T, P: PointerFamily> {
+    Idle,
+    // copies the front onto `front_rev` while reversing the rear onto
+    // `rear_rev`, one element of each per step
+    Reversing {
+	// elements of `front_rev` which were not taken from the queue since
+	valid: usize,
+	front: Stack<T, P>,
+	front_rev: Stack<T, P>,
+	rear: Stack<T, P>,
+	rear_rev: Stack<T, P>,
+    },
+    // puts the valid elements of `front_rev` back on top of `rear_rev`
+    Appending {
+	valid: usize,
+	front_rev: Stack<T, P>,
+	rear_rev: Stack<T, P>,
+	// length of the reversed rear at the bottom of `rear_rev`
+	reversed: usize,
+    },
+    // the new front
+    Done(Stack<T, P>),
+}
+
+impl<T, P: PointerFamily> Rotation<T, P> {
+    // executes a single step of the rotation
+    fn exec(&self) -> Self {
+	match self {
+	    Rotation::Reversing {
+		valid,
+		front,
+		front_rev,
+		rear,
+		rear_rev,
+	    } => match (front.head(), rear.head()) {
+		(Some(x), Some(y)) => Rotation::Reversing {
+		    valid: valid + 1,
+		    front: front.tail(),
+		    front_rev: front_rev.prepend(x.clone()),
+		    rear: rear.tail(),
+		    rear_rev: rear_rev.prepend(y.clone()),
+		},
+		(None, Some(y)) => {
+		    // a rotation starts with the rear being one element longer
+		    // than the front, so this is the last one
+		    debug_assert_eq!(rear.len(), 1);
+		    let rear_rev = rear_rev.prepend(y.clone());
+		    Rotation::Appending {
+			valid: *valid,
+			front_rev: front_rev.clone(),
+			reversed: rear_rev.len(),
+			rear_rev,
+		    }
+		},
+		_ => unreachable!("rotation with a rear not longer than the front"),
+	    },
+	    Rotation::Appending {
+		valid: 0, rear_rev, ..
+	    } => Rotation::Done(rear_rev.clone()),
+	    Rotation::Appending {
+		valid,
+		front_rev,
+		rear_rev,
+		reversed,
+	    } => Rotation::Appending {
+		valid: valid - 1,
+		front_rev: front_rev.tail(),
+		rear_rev: rear_rev.prepend(front_rev.head().expect("valid element").clone()),
+		reversed: *reversed,
+	    },
+	    other => other.clone(),
+	}
+    }
+
+    // the front element of the queue was taken, so the rotation has one
+    // element less to copy back
+    fn invalidate(&self) -> Self {
+	match self {
+	    Rotation::Reversing {
+		valid,
+		front,
+		front_rev,
+		rear,
+		rear_rev,
+	    } => Rotation::Reversing {
+		valid: valid - 1,
+		front: front.clone(),
+		front_rev: front_rev.clone(),
+		rear: rear.clone(),
+		rear_rev: rear_rev.clone(),
+	    },
+	    Rotation::Appending {
+		valid: 0, rear_rev, ..
+	    } => Rotation::Done(rear_rev.tail()),
+	    Rotation::Appending {
+		valid,
+		front_rev,
+		rear_rev,
+		reversed,
+	    } => Rotation::Appending {
+		valid: valid - 1,
+		front_rev: front_rev.clone(),
+		rear_rev: rear_rev.clone(),
+		reversed: *reversed,
+	    },
+	    other => other.clone(),
+	}
+    }
+}
+
+// only clones the stacks, which shares their nodes
+impl<T, P: PointerFamily> Clone for Rotation<T, P> {
+    fn clone(&self) -> Self {
+	match self {
+	    Rotation::Idle => Rotation::Idle,
+	    Rotation::Reversing {
+		valid,
+		front,
+		front_rev,
+		rear,
+		rear_rev,
+	    } => Rotation::Reversing {
+		valid: *valid,
+		front: front.clone(),
+		front_rev: front_rev.clone(),
+		rear: rear.clone(),
+		rear_rev: rear_rev.clone(),
+	    },
+	    Rotation::Appending {
+		valid,
+		front_rev,
+		rear_rev,
+		reversed,
+	    } => Rotation::Appending {
+		valid: *valid,
+		front_rev: front_rev.clone(),
+		rear_rev: rear_rev.clone(),
+		reversed: *reversed,
+	    },
+	    Rotation::Done(front) => Rotation::Done(front.clone()),
+	}
+    }
+}
+
+impl<T, P: PointerFamily> SharedList<T, P> {
+    pub fn new() -> Self {
+	SharedList {
+	    front_len: 0,
+	    front: Stack::new(),
+	    rotation: Rotation::Idle,
+	    rear_len: 0,
+	    rear: Stack::new(),
+	}
+    }
+
+    pub fn push_back(&self, elem: T) -> Self {
+	Self::check(
+	    self.front_len,
+	    self.front.clone(),
+	    self.rotation.clone(),
+	    self.rear_len + 1,
+	    self.rear.prepend(P::new(elem)),
+	)
+    }
+
+    // returns the queue without its front element, an empty queue stays empty
+    // (just like `tail` of the persistent stack)
+    pub fn pop_front(&self) -> Self {
+	if self.front_len == 0 {
+	    return self.clone();
+	}
+	Self::check(
+	    self.front_len - 1,
+	    self.front.tail(),
+	    self.rotation.invalidate(),
+	    self.rear_len,
+	    self.rear.clone(),
+	)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+	self.front.head().map(Deref::deref)
+    }
+
+    pub fn len(&self) -> usize {
+	self.front_len + self.rear_len
+    }
+
+    pub fn is_empty(&self) -> bool {
+	self.len() == 0
+    }
+
+    // starts a new rotation once the rear gets longer than the front and
+    // executes two steps of the running one
+    fn check(
+	front_len: usize,
+	front: Stack<T, P>,
+	rotation: Rotation<T, P>,
+	rear_len: usize,
+	rear: Stack<T, P>,
+    ) -> Self {
+	let (front_len, rotation, rear_len, rear) = if rear_len <= front_len {
+	    (front_len, rotation, rear_len, rear)
+	} else {
+	    let rotation = Rotation::Reversing {
+		valid: 0,
+		front: front.clone(),
+		front_rev: Stack::new(),
+		rear,
+		rear_rev: Stack::new(),
+	    };
+	    (front_len + rear_len, rotation, 0, Stack::new())
+	};
+	match rotation.exec().exec() {
+	    Rotation::Done(front) => SharedList {
+		front_len,
+		front,
+		rotation: Rotation::Idle,
+		rear_len,
+		rear,
+	    },
+	    rotation => SharedList {
+		front_len,
+		front,
+		rotation,
+		rear_len,
+		rear,
+	    },
+	}
+    }
+}
+
+// iter
+// The queue consists of the front, the reversed rear of a running rotation and
+// the reversed rear. Both reversed parts have to be buffered, as the stacks can
+// only be walked from their head.
+pub struct Iter<'a, T, P: PointerFamily> {
+    front: persistent_stack::Iter<'a, P::Pointer<T>, P>,
+    // part of the rear of a running rotation, which was not reversed yet
+    rotating: Vec<&'a T>,
+    // already reversed part of the rear of a running rotation
+    rotated: Option<persistent_stack::Iter<'a, P::Pointer<T>, P>>,
+    rear: Vec<&'a T>,
+    len: usize,
+}
+
+impl<T, P: PointerFamily> SharedList<T, P> {
+    pub fn iter(&self) -> Iter<'_, T, P> {
+	let (rotating, rotated) = match &self.rotation {
+	    Rotation::Reversing { rear, rear_rev, .. } => (
+		rear.iter().map(Deref::deref).collect(),
+		Some(rear_rev.iter()),
+	    ),
+	    Rotation::Appending {
+		rear_rev, reversed, ..
+	    } => {
+		// the valid elements of the front on top are already part of `front`
+		let mut rotated = rear_rev.iter();
+		for _ in *reversed..rear_rev.len() {
+		    rotated.next();
+		}
+		(Vec::new(), Some(rotated))
+	    },
+	    Rotation::Idle | Rotation::Done(_) => (Vec::new(), None),
+	};
+	Iter {
+	    front: self.front.iter(),
+	    rotating,
+	    rotated,
+	    rear: self.rear.iter().map(Deref::deref).collect(),
+	    len: self.len(),
+	}
+    }
+}
+
+impl<'a, T, P: PointerFamily> IntoIterator for &'a SharedList<T, P> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+	self.iter()
+    }
+}
+
+impl<'a, T, P: PointerFamily> Iterator for Iter<'a, T, P> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+	let elem = self
+	    .front
+	    .next()
+	    .map(Deref::deref)
+	    .or_else(|| self.rotating.pop())
+	    .or_else(|| self.rotated.as_mut()?.next().map(Deref::deref))
+	    .or_else(|| self.rear.pop())?;
+	self.len -= 1;
+	Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+	(self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, P: PointerFamily> ExactSizeIterator for Iter<'a, T, P> {
+    fn len(&self) -> usize {
+	self.len
+    }
+}
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<T, P: PointerFamily> Default for SharedList<T, P> {
+    fn default() -> Self {
+	Self::new()
+    }
+}
+
+// cloning only shares the nodes, no element is cloned
+impl<T, P: PointerFamily> Clone for SharedList<T, P> {
+    fn clone(&self) -> Self {
+	SharedList {
+	    front_len: self.front_len,
+	    front: self.front.clone(),
+	    rotation: self.rotation.clone(),
+	    rear_len: self.rear_len,
+	    rear: self.rear.clone(),
+	}
+    }
+}
+
+impl<T, P: PointerFamily> FromIterator<T> for SharedList<T, P> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+	iter.into_iter()
+	    .fold(SharedList::new(), |queue, elem| queue.push_back(elem))
+    }
+}
+
+impl<T: Debug, P: PointerFamily> Debug for SharedList<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, P: PointerFamily> PartialEq for SharedList<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+	self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, P: PointerFamily> Eq for SharedList<T, P> {}
+
+impl<T: Hash, P: PointerFamily> Hash for SharedList<T, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+	self.len().hash(state);
+	for elem in self {
+	    elem.hash(state);
+	}
+    }
+}
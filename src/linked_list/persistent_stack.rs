@@ -1,53 +1,229 @@
 // Most important feature:
 // Manipulate the tails of lists basically for free
 
-// use Arc instead of Rc to make this inmutable list thread-safe!
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::Deref;
 use std::rc::Rc;
-// use std::sync::Arc;
+use std::sync::Arc;
 
-pub struct List<T> {
-    head: Link<T>,
+use super::PersistentStack;
+
+// The kind of shared pointer the nodes are stored in. With `Rc` the list can
+// only be used by a single thread, with `Arc` it is `Send` and `Sync` (as long
+// as T is), at the cost of atomic reference counting.
+pub trait PointerFamily {
+    type Pointer<U>: Deref<Target = U> + Clone;
+
+    fn new<U>(value: U) -> Self::Pointer<U>;
+
+    // gives back the value if `this` was the last pointer to it
+    fn try_unwrap<U>(this: Self::Pointer<U>) -> Result<U, Self::Pointer<U>>;
+
+    // Like `try_unwrap`, but if several pointers to the same value are dropped
+    // concurrently exactly one of them is guaranteed to get the value back.
+    // With `try_unwrap` all of them could fail and the value would be dropped
+    // by whoever lets go of it last, recursively dropping the rest of the list.
+    fn into_inner<U>(this: Self::Pointer<U>) -> Option<U>;
+}
+
+pub struct RcFamily;
+
+impl PointerFamily for RcFamily {
+    type Pointer<U> = Rc<U>;
+
+    fn new<U>(value: U) -> Rc<U> {
+	Rc::new(value)
+    }
+
+    fn try_unwrap<U>(this: Rc<U>) -> Result<U, Rc<U>> {
+	Rc::try_unwrap(this)
+    }
+
+    fn into_inner<U>(this: Rc<U>) -> Option<U> {
+	Rc::into_inner(this)
+    }
+}
+
+pub struct ArcFamily;
+
+impl PointerFamily for ArcFamily {
+    type Pointer<U> = Arc<U>;
+
+    fn new<U>(value: U) -> Arc<U> {
+	Arc::new(value)
+    }
+
+    fn try_unwrap<U>(this: Arc<U>) -> Result<U, Arc<U>> {
+	Arc::try_unwrap(this)
+    }
+
+    fn into_inner<U>(this: Arc<U>) -> Option<U> {
+	Arc::into_inner(this)
+    }
 }
 
-type Link<T> = Option<Rc<Node<T>>>;
+pub struct SharedList<T, P: PointerFamily> {
+    head: Link<T, P>,
+    // every version of the list knows its own length
+    len: usize,
+}
+
+// single threaded list
+pub type List<T> = SharedList<T, RcFamily>;
+
+// thread-safe list, which can share its nodes between threads
+pub type SyncList<T> = SharedList<T, ArcFamily>;
 
-struct Node<T> {
+type Link<T, P> = Option<<P as PointerFamily>::Pointer<Node<T, P>>>;
+
+struct Node<T, P: PointerFamily> {
     elem: T,
-    next: Link<T>,
+    next: Link<T, P>,
 }
 
-impl<T> List<T> {
+impl<T, P: PointerFamily> SharedList<T, P> {
     pub fn new() -> Self {
-	List { head: None }
+	SharedList { head: None, len: 0 }
     }
 
-    pub fn prepend(&self, elem: T) -> List<T> {
-	List {
-	    head: Some(Rc::new(Node {
+    pub fn prepend(&self, elem: T) -> Self {
+	SharedList {
+	    head: Some(P::new(Node {
 		elem,
 		next: self.head.clone(),
-	    }))
+	    })),
+	    len: self.len + 1,
 	}
     }
 
-    pub fn tail(&self) -> List<T> {
-	List {
-	    head: self.head.as_ref().and_then(|node| node.next.clone())
+    pub fn tail(&self) -> Self {
+	SharedList {
+	    head: self.head.as_ref().and_then(|node| node.next.clone()),
+	    len: self.len.saturating_sub(1),
 	}
     }
 
     pub fn head(&self) -> Option<&T> {
 	self.head.as_ref().map(|node| &node.elem)
     }
+
+    // same as `head`, named like the accessors of the other lists
+    pub fn peek(&self) -> Option<&T> {
+	self.head()
+    }
+
+    pub fn len(&self) -> usize {
+	self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+	self.len == 0
+    }
 }
 
-// because Rc only provides shared access to the containing object (as it could
+// +-----------------------+
+// | Functional Operations |
+// +-----------------------+
+
+// Nodes can never be changed once they are created, so only the suffix of a
+// list can be shared. Every operation which changes something in front of it
+// has to copy the elements up to there, which requires `T: Clone`.
+impl<T, P: PointerFamily> SharedList<T, P> {
+    pub fn nth(&self, n: usize) -> Option<&T> {
+	self.iter().nth(n)
+    }
+
+    // the list without its first `n` elements, sharing all of its nodes
+    pub fn drop(&self, n: usize) -> Self {
+	let mut head = &self.head;
+	for _ in 0..n {
+	    match head {
+		Some(node) => head = &node.next,
+		None => break,
+	    }
+	}
+	SharedList {
+	    head: head.clone(),
+	    len: self.len.saturating_sub(n),
+	}
+    }
+
+    // true if both lists start with the same node, i.e. one is a cheap clone
+    // of the other or both were taken from the same suffix
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+	match (&self.head, &other.head) {
+	    (Some(node), Some(other)) => std::ptr::eq::<Node<T, P>>(&**node, &**other),
+	    (None, None) => true,
+	    _ => false,
+	}
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> SharedList<U, P> {
+	self.iter().map(f).collect()
+    }
+
+    // builds `elems ++ suffix`, as a list can only be built from its end
+    fn with_prefix(elems: Vec<T>, suffix: Self) -> Self {
+	elems
+	    .into_iter()
+	    .rev()
+	    .fold(suffix, |list, elem| list.prepend(elem))
+    }
+}
+
+impl<T: Clone, P: PointerFamily> SharedList<T, P> {
+    // the first `n` elements, which have to be copied
+    pub fn take(&self, n: usize) -> Self {
+	if n >= self.len {
+	    return self.clone();
+	}
+	Self::with_prefix(self.iter().take(n).cloned().collect(), SharedList::new())
+    }
+
+    // copies `self` in front of `other`, all nodes of `other` are shared
+    pub fn append(&self, other: &Self) -> Self {
+	if self.is_empty() {
+	    return other.clone();
+	}
+	Self::with_prefix(self.iter().cloned().collect(), other.clone())
+    }
+
+    pub fn reverse(&self) -> Self {
+	self.iter()
+	    .fold(SharedList::new(), |list, elem| list.prepend(elem.clone()))
+    }
+
+    // Only the elements up to the last removed one are copied, the suffix
+    // after it is shared with `self`.
+    pub fn filter<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Self {
+	let mut kept = Vec::new();
+	// number of elements up to and including the last removed one
+	let mut removed_up_to = 0;
+	for (index, elem) in self.iter().enumerate() {
+	    if pred(elem) {
+		kept.push((index, elem));
+	    } else {
+		removed_up_to = index + 1;
+	    }
+	}
+	let prefix = kept
+	    .into_iter()
+	    .take_while(|(index, _)| *index < removed_up_to)
+	    .map(|(_, elem)| elem.clone())
+	    .collect();
+	Self::with_prefix(prefix, self.drop(removed_up_to))
+    }
+}
+
+// because Rc/Arc only provide shared access to the containing object (as it could
 // be pointed to by other Rc's) we can not mutate the Node inside of the Rc
-impl<T> Drop for List<T> {
+impl<T, P: PointerFamily> Drop for SharedList<T, P> {
     fn drop(&mut self) {
 	let mut head = self.head.take();
 	while let Some(node) = head {
-	    if let Ok(mut node) = Rc::try_unwrap(node) {
+	    if let Some(mut node) = P::into_inner(node) {
 		// we are the last one to hold a reference to this Rc so we safe
 		// to take node and let it go out of scope (and be free)
 		head = node.next.take();
@@ -61,21 +237,32 @@ impl<T> Drop for List<T> {
 }
 
 // as this list is a inmutable list
-// we cannot implement `Iterolter` or `IterMut` for this list
+// we cannot implement `IterMut` for this list and `IntoIter` can only move out
+// the elements nobody else shares with us
 
 // iter
 // identical to better_stack (a mutable list)
-pub struct Iter<'a, T> {
-    next: Option<&'a Node<T>>,
+pub struct Iter<'a, T, P: PointerFamily> {
+    next: Option<&'a Node<T, P>>,
 }
 
-impl<T> List<T> {
-    pub fn iter(&self) -> Iter<'_, T> {
+impl<T, P: PointerFamily> SharedList<T, P> {
+    pub fn iter(&self) -> Iter<'_, T, P> {
 	Iter { next: self.head.as_deref() }
     }
 }
 
-impl<'a, T> Iterator for Iter<'a, T> {
+impl<'a, T, P: PointerFamily> IntoIterator for &'a SharedList<T, P> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+	self.iter()
+    }
+}
+
+impl<'a, T, P: PointerFamily> Iterator for Iter<'a, T, P> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -85,3 +272,280 @@ impl<'a, T> Iterator for Iter<'a, T> {
 	})
     }
 }
+
+// into_iter
+pub struct IntoIter<T, P: PointerFamily>(SharedList<T, P>);
+
+// requires `T: Clone`, as elements of nodes that are shared with other lists
+// can only be cloned
+impl<T: Clone, P: PointerFamily> IntoIterator for SharedList<T, P> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+	IntoIter(self)
+    }
+}
+
+impl<T: Clone, P: PointerFamily> Iterator for IntoIter<T, P> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+	self.0.head.take().map(|node| {
+	    self.0.len -= 1;
+	    match P::try_unwrap(node) {
+		Ok(mut node) => {
+		    // we are the only owner, so we can move the element out
+		    self.0.head = node.next.take();
+		    node.elem
+		},
+		Err(node) => {
+		    // the node is shared, so the rest of the list is shared
+		    // as well
+		    self.0.head = node.next.clone();
+		    node.elem.clone()
+		},
+	    }
+	})
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+	(self.0.len, Some(self.0.len))
+    }
+}
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<T, P: PointerFamily> Default for SharedList<T, P> {
+    fn default() -> Self {
+	Self::new()
+    }
+}
+
+// cloning only shares the nodes, no element is cloned
+impl<T, P: PointerFamily> Clone for SharedList<T, P> {
+    fn clone(&self) -> Self {
+	SharedList {
+	    head: self.head.clone(),
+	    len: self.len,
+	}
+    }
+}
+
+// There is no `Extend`, as an inmutable list cannot be extended in place.
+// Collecting keeps the order of the iterator, such that the first element ends
+// up as the head of the list.
+impl<T, P: PointerFamily> FromIterator<T> for SharedList<T, P> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+	// nodes cannot be changed once they are created, so the list has to be
+	// built from its end
+	SharedList::with_prefix(iter.into_iter().collect(), SharedList::new())
+    }
+}
+
+impl<T: Debug, P: PointerFamily> Debug for SharedList<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, P: PointerFamily> PartialEq for SharedList<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+	self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, P: PointerFamily> Eq for SharedList<T, P> {}
+
+impl<T: Hash, P: PointerFamily> Hash for SharedList<T, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+	self.len.hash(state);
+	for elem in self {
+	    elem.hash(state);
+	}
+    }
+}
+
+// +--------+
+// | Zipper |
+// +--------+
+
+// Cursor over an inmutable list, which is split at the cursor into the
+// elements left of it (in reversed order, nearest first) and the ones right of
+// it. The current element is the first one of the right side, if the right
+// side is empty the zipper is at the end of the list (like the "ghost" of
+// `CursorMut`). Every operation only touches the heads of both sides and
+// returns a new zipper, the old one stays valid, which makes undoing an edit as
+// simple as keeping the old zipper around.
+pub struct SharedZipper<T, P: PointerFamily> {
+    left: SharedList<T, P>,
+    right: SharedList<T, P>,
+}
+
+pub type Zipper<T> = SharedZipper<T, RcFamily>;
+
+pub type SyncZipper<T> = SharedZipper<T, ArcFamily>;
+
+impl<T, P: PointerFamily> SharedZipper<T, P> {
+    pub fn new() -> Self {
+	SharedZipper {
+	    left: SharedList::new(),
+	    right: SharedList::new(),
+	}
+    }
+
+    // zipper at the start of `list`, which shares all of its nodes
+    pub fn from_list(list: &SharedList<T, P>) -> Self {
+	SharedZipper {
+	    left: SharedList::new(),
+	    right: list.clone(),
+	}
+    }
+
+    pub fn current(&self) -> Option<&T> {
+	self.right.head()
+    }
+
+    // element before the current one
+    pub fn peek_left(&self) -> Option<&T> {
+	self.left.head()
+    }
+
+    pub fn index(&self) -> usize {
+	self.left.len()
+    }
+
+    pub fn len(&self) -> usize {
+	self.left.len() + self.right.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+	self.len() == 0
+    }
+
+    pub fn is_at_start(&self) -> bool {
+	self.left.is_empty()
+    }
+
+    pub fn is_at_end(&self) -> bool {
+	self.right.is_empty()
+    }
+
+    // inserts `elem` at the cursor, it becomes the current element and the old
+    // current one follows it
+    pub fn insert(&self, elem: T) -> Self {
+	SharedZipper {
+	    left: self.left.clone(),
+	    right: self.right.prepend(elem),
+	}
+    }
+
+    // removes the current element, the next one becomes the current element
+    // returns `None` at the end of the list
+    pub fn delete(&self) -> Option<Self> {
+	self.right.head()?;
+	Some(SharedZipper {
+	    left: self.left.clone(),
+	    right: self.right.tail(),
+	})
+    }
+
+    // returns `None` at the end of the list
+    pub fn replace(&self, elem: T) -> Option<Self> {
+	self.right.head()?;
+	Some(SharedZipper {
+	    left: self.left.clone(),
+	    right: self.right.tail().prepend(elem),
+	})
+    }
+}
+
+// moving copies the element from one side to the other, as the nodes cannot
+// be relinked
+impl<T: Clone, P: PointerFamily> SharedZipper<T, P> {
+    // returns `None` at the start of the list
+    pub fn move_left(&self) -> Option<Self> {
+	let elem = self.left.head()?;
+	Some(SharedZipper {
+	    left: self.left.tail(),
+	    right: self.right.prepend(elem.clone()),
+	})
+    }
+
+    // returns `None` at the end of the list
+    pub fn move_right(&self) -> Option<Self> {
+	let elem = self.right.head()?;
+	Some(SharedZipper {
+	    left: self.left.prepend(elem.clone()),
+	    right: self.right.tail(),
+	})
+    }
+
+    // the whole list, copies the left side and shares the right side
+    pub fn to_list(&self) -> SharedList<T, P> {
+	self.left
+	    .iter()
+	    .fold(self.right.clone(), |list, elem| list.prepend(elem.clone()))
+    }
+}
+
+impl<T, P: PointerFamily> Default for SharedZipper<T, P> {
+    fn default() -> Self {
+	Self::new()
+    }
+}
+
+impl<T, P: PointerFamily> Clone for SharedZipper<T, P> {
+    fn clone(&self) -> Self {
+	SharedZipper {
+	    left: self.left.clone(),
+	    right: self.right.clone(),
+	}
+    }
+}
+
+impl<T, P: PointerFamily> From<SharedList<T, P>> for SharedZipper<T, P> {
+    fn from(list: SharedList<T, P>) -> Self {
+	SharedZipper {
+	    left: SharedList::new(),
+	    right: list,
+	}
+    }
+}
+
+impl<T: Debug, P: PointerFamily> Debug for SharedZipper<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	// the left side in the order of the list
+	let mut left: Vec<&T> = self.left.iter().collect();
+	left.reverse();
+	f.debug_struct("Zipper")
+	    .field("left", &left)
+	    .field("right", &self.right)
+	    .finish()
+    }
+}
+
+// +--------+
+// | Traits |
+// +--------+
+
+impl<T, P: PointerFamily> PersistentStack<T> for SharedList<T, P> {
+    fn prepend(&self, elem: T) -> Self {
+	SharedList::prepend(self, elem)
+    }
+
+    fn tail(&self) -> Self {
+	SharedList::tail(self)
+    }
+
+    fn head(&self) -> Option<&T> {
+	SharedList::head(self)
+    }
+
+    fn len(&self) -> usize {
+	SharedList::len(self)
+    }
+}
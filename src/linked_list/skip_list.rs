@@ -0,0 +1,1043 @@
+// Ordered map on top of a linked list, which keeps its nodes sorted by key.
+//
+// Every node has a random number of levels, the probability of a node having
+// `l + 1` levels is half of the one having `l` levels. On every level the nodes
+// having it are linked in order, so the higher levels skip over more and more
+// nodes. A search starts at the highest level and only goes down a level once
+// the next node on the current level is too far, which takes O(log n) steps
+// in expectation. The lowest level links all nodes (in both directions) just
+// like a `LinkedList` does.
+//
+// The levels are chosen by a small seeded random number generator, so a list
+// built by the same operations with the same seed always has the same shape.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+use std::ptr::NonNull;
+
+// enough levels for 2^32 nodes
+const MAX_LEVEL: usize = 32;
+
+const DEFAULT_SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+pub struct SkipList<K, V> {
+    // first node on every level
+    heads: [Link<K, V>; MAX_LEVEL],
+    back: Link<K, V>,
+    // number of levels used by at least one node
+    level: usize,
+    len: usize,
+    rng: XorShift,
+    _boo: PhantomData<(K, V)>,
+}
+
+type Link<K, V> = Option<NonNull<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    // previous node on the lowest level
+    back: Link<K, V>,
+    // next node on every level of this node
+    next: Box<[Link<K, V>]>,
+}
+
+// xorshift64*, good enough to pick the levels
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // the state must never be zero
+        XorShift(if seed == 0 { DEFAULT_SEED } else { seed })
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl<K, V> SkipList<K, V> {
+    pub fn new() -> Self {
+        Self::with_seed(DEFAULT_SEED)
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        SkipList {
+            heads: [None; MAX_LEVEL],
+            back: None,
+            level: 0,
+            len: 0,
+            rng: XorShift::new(seed),
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // number of levels in use
+    pub fn height(&self) -> usize {
+        self.level
+    }
+
+    pub fn clear(&mut self) {
+        let seed = self.rng.0;
+        *self = Self::with_seed(seed);
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.heads[0].map(|node| unsafe {
+            let node = &*node.as_ptr();
+            (&node.key, &node.value)
+        })
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.back.map(|node| unsafe {
+            let node = &*node.as_ptr();
+            (&node.key, &node.value)
+        })
+    }
+
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        // the first node is the first one on all of its levels
+        let node = self.heads[0]?;
+        Some(unsafe { self.unlink(node, &[None; MAX_LEVEL]) })
+    }
+
+    // next node on `level` after `link`, `None` stands for the heads
+    fn next_of(&self, link: Link<K, V>, level: usize) -> Link<K, V> {
+        match link {
+            Some(node) => unsafe { (&(*node.as_ptr()).next)[level] },
+            None => self.heads[level],
+        }
+    }
+
+    fn set_next(&mut self, link: Link<K, V>, level: usize, next: Link<K, V>) {
+        match link {
+            Some(node) => unsafe { (&mut (*node.as_ptr()).next)[level] = next },
+            None => self.heads[level] = next,
+        }
+    }
+
+    // For every level the last node for which `before` holds, or `None` if it
+    // holds for none of them. `before` has to hold for a prefix of the list.
+    fn predecessors<F>(&self, mut before: F) -> [Link<K, V>; MAX_LEVEL]
+    where
+        F: FnMut(&K) -> bool,
+    {
+        let mut preds = [None; MAX_LEVEL];
+        let mut cur = None;
+        for level in (0..self.level).rev() {
+            while let Some(next) = self.next_of(cur, level) {
+                if !before(unsafe { &(*next.as_ptr()).key }) {
+                    break;
+                }
+                cur = Some(next);
+            }
+            preds[level] = cur;
+        }
+        preds
+    }
+
+    // Unlinks and frees `node`, `preds` are its predecessors on all of its
+    // levels as far as the keys can tell. A buggy `Ord` can make the search
+    // for them overshoot, even onto `node` itself, so every predecessor is
+    // checked and otherwise looked up by walking the level from its head.
+    unsafe fn unlink(
+        &mut self,
+        node: NonNull<Node<K, V>>,
+        preds: &[Link<K, V>; MAX_LEVEL],
+    ) -> (K, V) {
+        let next = &(*node.as_ptr()).next;
+        let height = next.len();
+        for (level, &pred) in preds.iter().enumerate().take(height) {
+            let mut pred = pred;
+            if self.next_of(pred, level) != Some(node) {
+                pred = None;
+                loop {
+                    let cur = self
+                        .next_of(pred, level)
+                        .expect("a node is linked on all of its levels");
+                    if cur == node {
+                        break;
+                    }
+                    pred = Some(cur);
+                }
+            }
+            self.set_next(pred, level, next[level]);
+        }
+        let boxed = Box::from_raw(node.as_ptr());
+        match boxed.next[0] {
+            Some(next) => (*next.as_ptr()).back = boxed.back,
+            None => self.back = boxed.back,
+        }
+        while self.level > 0 && self.heads[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+        self.len -= 1;
+        let Node { key, value, .. } = *boxed;
+        (key, value)
+    }
+
+    fn random_level(&mut self) -> usize {
+        // every set bit is a coin flip that came up heads
+        (self.rng.next().trailing_ones() as usize + 1).min(MAX_LEVEL)
+    }
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    // returns the old value if `key` was already in the list, the key is not
+    // updated in that case
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut preds = self.predecessors(|k| *k < key);
+        if let Some(node) = self.next_of(preds[0], 0) {
+            let node = unsafe { &mut *node.as_ptr() };
+            if node.key == key {
+                return Some(std::mem::replace(&mut node.value, value));
+            }
+        }
+
+        let height = self.random_level();
+        // the new levels start at the heads
+        for pred in &mut preds[self.level..height.max(self.level)] {
+            *pred = None;
+        }
+        self.level = self.level.max(height);
+        let next: Box<[Link<K, V>]> = (0..height)
+            .map(|level| self.next_of(preds[level], level))
+            .collect();
+        let new = unsafe {
+            NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+                key,
+                value,
+                back: preds[0],
+                next,
+            })))
+        };
+        for (level, pred) in preds.iter().enumerate().take(height) {
+            self.set_next(*pred, level, Some(new));
+        }
+        match unsafe { (&(*new.as_ptr()).next)[0] } {
+            Some(next) => unsafe { (*next.as_ptr()).back = Some(new) },
+            None => self.back = Some(new),
+        }
+        self.len += 1;
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key)
+            .map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key)
+            .map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).map(|node| unsafe {
+            let node = &*node.as_ptr();
+            (&node.key, &node.value)
+        })
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let preds = self.predecessors(|k| k.borrow() < key);
+        let node = self.next_of(preds[0], 0)?;
+        if unsafe { (*node.as_ptr()).key.borrow() } != key {
+            return None;
+        }
+        Some(unsafe { self.unlink(node, &preds) })
+    }
+
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        let node = self.back?;
+        let preds = self.predecessors(|k| k < unsafe { &(*node.as_ptr()).key });
+        Some(unsafe { self.unlink(node, &preds) })
+    }
+
+    // iterates over all entries with a key in `range`, in order
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (front, back) = self.range_links(range);
+        Range {
+            front,
+            back,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn range_mut<Q, R>(&mut self, range: R) -> RangeMut<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (front, back) = self.range_links(range);
+        RangeMut {
+            front,
+            back,
+            _boo: PhantomData,
+        }
+    }
+
+    fn find<Q>(&self, key: &Q) -> Link<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let preds = self.predecessors(|k| k.borrow() < key);
+        self.next_of(preds[0], 0)
+            .filter(|node| unsafe { (*node.as_ptr()).key.borrow() } == key)
+    }
+
+    // first node after all keys for which `before` holds
+    fn first_after<F: FnMut(&K) -> bool>(&self, before: F) -> Link<K, V> {
+        let preds = self.predecessors(before);
+        self.next_of(preds[0], 0)
+    }
+
+    // first and last node of `range`, both `None` if it is empty
+    fn range_links<Q, R>(&self, range: R) -> (Link<K, V>, Link<K, V>)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let front = match range.start_bound() {
+            Bound::Included(start) => self.first_after(|k| k.borrow() < start),
+            Bound::Excluded(start) => self.first_after(|k| k.borrow() <= start),
+            Bound::Unbounded => self.heads[0],
+        };
+        let back = match range.end_bound() {
+            Bound::Included(end) => self.predecessors(|k| k.borrow() <= end)[0],
+            Bound::Excluded(end) => self.predecessors(|k| k.borrow() < end)[0],
+            Bound::Unbounded => self.back,
+        };
+        match (front, back) {
+            (Some(f), Some(b)) if unsafe { (*f.as_ptr()).key <= (*b.as_ptr()).key } => {
+                (front, back)
+            }
+            _ => (None, None),
+        }
+    }
+}
+
+impl<K, V> Drop for SkipList<K, V> {
+    fn drop(&mut self) {
+        // the lowest level links all nodes
+        let mut cur = self.heads[0].take();
+        while let Some(node) = cur {
+            let boxed = unsafe { Box::from_raw(node.as_ptr()) };
+            cur = boxed.next[0];
+        }
+    }
+}
+
+// +-----------------+
+// | Iterator Traits |
+// +-----------------+
+
+// Entries from `front` to `back` (both included), the iterator is exhausted
+// once they met.
+pub struct Range<'a, K, V> {
+    front: Link<K, V>,
+    back: Link<K, V>,
+    _boo: PhantomData<(&'a K, &'a V)>,
+}
+
+impl<'a, K, V> Range<'a, K, V> {
+    fn next_node(&mut self) -> Link<K, V> {
+        let node = self.front?;
+        if self.front == self.back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = unsafe { (&(*node.as_ptr()).next)[0] };
+        }
+        Some(node)
+    }
+
+    fn next_back_node(&mut self) -> Link<K, V> {
+        let node = self.back?;
+        if self.front == self.back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = unsafe { (*node.as_ptr()).back };
+        }
+        Some(node)
+    }
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_node().map(|node| unsafe {
+            let node = &*node.as_ptr();
+            (&node.key, &node.value)
+        })
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Range<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_node().map(|node| unsafe {
+            let node = &*node.as_ptr();
+            (&node.key, &node.value)
+        })
+    }
+}
+
+// the keys can not be changed, as that could break the order
+pub struct RangeMut<'a, K, V> {
+    front: Link<K, V>,
+    back: Link<K, V>,
+    _boo: PhantomData<(&'a K, &'a mut V)>,
+}
+
+impl<'a, K, V> Iterator for RangeMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut range = Range::<'a, K, V> {
+            front: self.front,
+            back: self.back,
+            _boo: PhantomData,
+        };
+        let node = range.next_node();
+        self.front = range.front;
+        self.back = range.back;
+        node.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            (&node.key, &mut node.value)
+        })
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for RangeMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let mut range = Range::<'a, K, V> {
+            front: self.front,
+            back: self.back,
+            _boo: PhantomData,
+        };
+        let node = range.next_back_node();
+        self.front = range.front;
+        self.back = range.back;
+        node.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            (&node.key, &mut node.value)
+        })
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    range: Range<'a, K, V>,
+    len: usize,
+}
+
+impl<K, V> SkipList<K, V> {
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            range: Range {
+                front: self.heads[0],
+                back: self.back,
+                _boo: PhantomData,
+            },
+            len: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            range: RangeMut {
+                front: self.heads[0],
+                back: self.back,
+                _boo: PhantomData,
+            },
+            len: self.len,
+        }
+    }
+
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipList<K, V> {
+    type Item = (&'a K, &'a V);
+
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.range.next()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.range.next_back()?;
+        self.len -= 1;
+        Some(item)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    range: RangeMut<'a, K, V>,
+    len: usize,
+}
+
+impl<'a, K, V> IntoIterator for &'a mut SkipList<K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.range.next()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for IterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.range.next_back()?;
+        self.len -= 1;
+        Some(item)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct IntoIter<K, V> {
+    list: SkipList<K, V>,
+}
+
+impl<K, V> IntoIterator for SkipList<K, V> {
+    type Item = (K, V);
+
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<K, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The clone is built with the default seed, so its shape can differ from the
+// original one. Afterwards it continues with the random numbers of the
+// original, so both choose the same levels for the same future inserts.
+impl<K: Ord + Clone, V: Clone> Clone for SkipList<K, V> {
+    fn clone(&self) -> Self {
+        let mut new_list = Self::new();
+        new_list.extend(self.iter().map(|(key, value)| (key.clone(), value.clone())));
+        new_list.rng = XorShift(self.rng.0);
+        new_list
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for SkipList<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SkipList<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for SkipList<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for SkipList<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for SkipList<K, V> {}
+
+impl<K: Hash, V: Hash> Hash for SkipList<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for entry in self {
+            entry.hash(state);
+        }
+    }
+}
+
+impl<K: PartialOrd, V: PartialOrd> PartialOrd for SkipList<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<K: Ord, V: Ord> Ord for SkipList<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+// +----------------------+
+// | Send and Sync Traits |
+// +----------------------+
+unsafe impl<K: Send, V: Send> Send for SkipList<K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for SkipList<K, V> {}
+
+unsafe impl<'a, K: Sync, V: Sync> Send for Range<'a, K, V> {}
+unsafe impl<'a, K: Sync, V: Sync> Sync for Range<'a, K, V> {}
+
+unsafe impl<'a, K: Sync, V: Send> Send for RangeMut<'a, K, V> {}
+unsafe impl<'a, K: Sync, V: Sync> Sync for RangeMut<'a, K, V> {}
+
+// +-----------------------+
+// | Cursor Implementation |
+// +-----------------------+
+
+// Same "ghost" element model as the cursor of `LinkedList`. The keys can not be
+// changed and there is no way to insert at the cursor, as both could break the
+// order of the list.
+pub struct CursorMut<'a, K, V> {
+    cur: Link<K, V>,
+    list: &'a mut SkipList<K, V>,
+}
+
+impl<K, V> SkipList<K, V> {
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, K, V> {
+        CursorMut {
+            cur: None,
+            list: self,
+        }
+    }
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    // cursor at the first entry with a key above `bound`, or at the ghost if
+    // there is none
+    pub fn lower_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let cur = match bound {
+            Bound::Included(start) => self.first_after(|k| k.borrow() < start),
+            Bound::Excluded(start) => self.first_after(|k| k.borrow() <= start),
+            Bound::Unbounded => self.heads[0],
+        };
+        CursorMut { cur, list: self }
+    }
+}
+
+impl<'a, K, V> CursorMut<'a, K, V> {
+    pub fn move_next(&mut self) {
+        self.cur = match self.cur {
+            Some(node) => unsafe { (&(*node.as_ptr()).next)[0] },
+            // the ghost is followed by the front
+            None => self.list.heads[0],
+        };
+    }
+
+    pub fn move_prev(&mut self) {
+        self.cur = match self.cur {
+            Some(node) => unsafe { (*node.as_ptr()).back },
+            // the ghost is preceded by the back
+            None => self.list.back,
+        };
+    }
+
+    pub fn key(&self) -> Option<&K> {
+        self.cur.map(|node| unsafe { &(*node.as_ptr()).key })
+    }
+
+    pub fn current(&mut self) -> Option<(&K, &mut V)> {
+        self.cur.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            (&node.key, &mut node.value)
+        })
+    }
+
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let next = match self.cur {
+            Some(node) => unsafe { (&(*node.as_ptr()).next)[0] },
+            None => self.list.heads[0],
+        };
+        next.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            (&node.key, &mut node.value)
+        })
+    }
+
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let prev = match self.cur {
+            Some(node) => unsafe { (*node.as_ptr()).back },
+            None => self.list.back,
+        };
+        prev.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            (&node.key, &mut node.value)
+        })
+    }
+}
+
+impl<'a, K: Ord, V> CursorMut<'a, K, V> {
+    // removes the current entry and moves onto the next one
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        let node = self.cur?;
+        unsafe {
+            self.cur = (&(*node.as_ptr()).next)[0];
+            let preds = self.list.predecessors(|k| k < &(*node.as_ptr()).key);
+            Some(self.list.unlink(node, &preds))
+        }
+    }
+}
+
+// +---------+
+// | SkipSet |
+// +---------+
+
+// Ordered set, which is a `SkipList` without values.
+pub struct SkipSet<K> {
+    map: SkipList<K, ()>,
+}
+
+impl<K> SkipSet<K> {
+    pub fn new() -> Self {
+        SkipSet {
+            map: SkipList::new(),
+        }
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        SkipSet {
+            map: SkipList::with_seed(seed),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    pub fn first(&self) -> Option<&K> {
+        self.map.first().map(|(key, _)| key)
+    }
+
+    pub fn last(&self) -> Option<&K> {
+        self.map.last().map(|(key, _)| key)
+    }
+
+    pub fn pop_first(&mut self) -> Option<K> {
+        self.map.pop_first().map(|(key, _)| key)
+    }
+
+    pub fn iter(&self) -> SetIter<'_, K> {
+        SetIter {
+            iter: self.map.iter(),
+        }
+    }
+}
+
+impl<K: Ord> SkipSet<K> {
+    // returns whether `key` was new
+    pub fn insert(&mut self, key: K) -> bool {
+        self.map.insert(key, ()).is_none()
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get_key_value(key).map(|(key, _)| key)
+    }
+
+    // returns whether `key` was in the set
+    pub fn remove<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(key).is_some()
+    }
+
+    pub fn take<Q>(&mut self, key: &Q) -> Option<K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove_entry(key).map(|(key, _)| key)
+    }
+
+    pub fn pop_last(&mut self) -> Option<K> {
+        self.map.pop_last().map(|(key, _)| key)
+    }
+
+    pub fn range<Q, R>(&self, range: R) -> impl DoubleEndedIterator<Item = &K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.map.range(range).map(|(key, _)| key)
+    }
+}
+
+pub struct SetIter<'a, K> {
+    iter: Iter<'a, K, ()>,
+}
+
+impl<'a, K> Iterator for SetIter<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K> DoubleEndedIterator for SetIter<'a, K> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K> ExactSizeIterator for SetIter<'a, K> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, K> IntoIterator for &'a SkipSet<K> {
+    type Item = &'a K;
+
+    type IntoIter = SetIter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct SetIntoIter<K> {
+    iter: IntoIter<K, ()>,
+}
+
+impl<K> IntoIterator for SkipSet<K> {
+    type Item = K;
+
+    type IntoIter = SetIntoIter<K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SetIntoIter {
+            iter: self.map.into_iter(),
+        }
+    }
+}
+
+impl<K> Iterator for SetIntoIter<K> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K> Default for SkipSet<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone> Clone for SkipSet<K> {
+    fn clone(&self) -> Self {
+        SkipSet {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<K: Ord> Extend<K> for SkipSet<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+impl<K: Ord> FromIterator<K> for SkipSet<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<K: Debug> Debug for SkipSet<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self).finish()
+    }
+}
+
+impl<K: PartialEq> PartialEq for SkipSet<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Eq> Eq for SkipSet<K> {}
+
+impl<K: Hash> Hash for SkipSet<K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.map.hash(state);
+    }
+}
@@ -1,63 +1,378 @@
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
 use std::mem;
+use std::ptr::NonNull;
+
+use super::allocator::{self, Allocator, Global, TryReserveError};
+use super::Stack;
 
 struct Node<T> {
     elem: T,
     next: Link<T>,
 }
 
+// the nodes are allocated through the allocator of the list, so they point to
+// each other with raw pointers instead of boxes
 enum Link<T> {
     Empty,
-    More(Box<Node<T>>),
+    More(NonNull<Node<T>>),
 }
 
 // wrapper around Link to hide the Link and Node enum/struct from the outside
-// because of zero cost abstractions this struct has the same size as that field
-pub struct List<T> {
+// because of zero cost abstractions the wrapper itself costs nothing, only the
+// length we keep track of takes additional space
+pub struct List<T, A: Allocator = Global> {
     head: Link<T>,
+    len: usize,
+    // all nodes are allocated and freed through this one
+    alloc: A,
+    // we own the nodes and with them values of type T
+    _boo: PhantomData<T>,
 }
 
 impl<T> List<T> {
     pub fn new() -> Self {
-	Self { head: Link::<T>::Empty }
+	Self::new_in(Global)
+    }
+}
+
+impl<T, A: Allocator> List<T, A> {
+    // creates an empty list whose nodes will be allocated by `alloc`
+    pub fn new_in(alloc: A) -> Self {
+	Self {
+	    head: Link::Empty,
+	    len: 0,
+	    alloc,
+	    _boo: PhantomData,
+	}
+    }
+
+    pub fn allocator(&self) -> &A {
+	&self.alloc
     }
 
     pub fn push(&mut self, elem: T) {
-	let new_node = Box::new(Node {
+	let new_node = allocator::allocate(&self.alloc, Node {
 	    elem,
 	    // replace the head with an link::Empty temporarly before replacing
 	    // it with the new head of the list
 	    next: mem::replace(&mut self.head, Link::Empty),
 	});
 	self.head = Link::More(new_node);
+	self.len += 1;
+    }
+
+    // like `push`, but hands the element back instead of aborting when the
+    // allocator is out of memory
+    pub fn try_push(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+	let node = Node {
+	    elem,
+	    next: mem::replace(&mut self.head, Link::Empty),
+	};
+	let new_node = allocator::try_allocate_node(&self.alloc, node, |node| {
+	    // the old head has to go back to where it came from
+	    self.head = node.next;
+	    node.elem
+	})?;
+	self.head = Link::More(new_node);
+	self.len += 1;
+	Ok(())
+    }
+
+    // pushes the elements until one of them can't be allocated, that one is
+    // handed back and the rest of the iterator is dropped
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryReserveError<T>>
+    where
+	I: IntoIterator<Item = T>,
+    {
+	for elem in iter {
+	    self.try_push(elem)?;
+	}
+	Ok(())
     }
 
     pub fn pop(&mut self) -> Option<T> {
 	match mem::replace(&mut self.head, Link::Empty) {
 	    Link::Empty => None,
 	    Link::More(node) => {
+		// every node of the list was allocated by `alloc`, moving it
+		// out of its block frees the block
+		let node = unsafe { allocator::deallocate(&self.alloc, node) };
 		// replace this node with the next one
 		self.head = node.next;
+		self.len -= 1;
 		// return the value of the current head
 		Some(node.elem)
 	    },
 	}
     }
+
+    pub fn peek(&self) -> Option<&T> {
+	match &self.head {
+	    Link::Empty => None,
+	    Link::More(node) => Some(unsafe { &node.as_ref().elem }),
+	}
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+	match &mut self.head {
+	    Link::Empty => None,
+	    Link::More(node) => Some(unsafe { &mut node.as_mut().elem }),
+	}
+    }
+
+    pub fn len(&self) -> usize {
+	self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+	self.len == 0
+    }
 }
 
 
-// as the list contains types which implement the Drop Trait the implementation
-// for Drop for the List is actually not required. But the automatic handling
-// can be bad as it will recursivly call drop on each element in the linked
-// list, which can cause a stack overflow.
-impl<T> Drop for List<T> {
+// the nodes are only linked by raw pointers, so they have to be freed by hand.
+// Doing that in a loop instead of letting every node free the next one also
+// means a long list can't overflow the stack while being dropped.
+impl<T, A: Allocator> Drop for List<T, A> {
     fn drop(&mut self) {
         let mut cur_link = mem::replace(&mut self.head, Link::Empty);
 
-	while let Link::More(mut boxed) = cur_link {
-	    cur_link = mem::replace(&mut boxed.next, Link::Empty);
-	    // boxed goes out of scope and gets dropped here;
-	    // but its Node's `next` field has been set to Link::Empty so no
-	    // unbounded recursion occurs
+	while let Link::More(node) = cur_link {
+	    let node = unsafe { allocator::deallocate(&self.alloc, node) };
+	    cur_link = node.next;
+	    // the element goes out of scope and gets dropped here
+	}
+    }
+}
+
+// into_iter
+pub struct IntoIter<T, A: Allocator = Global>(List<T, A>);
+
+impl<T, A: Allocator> IntoIterator for List<T, A> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+	IntoIter(self)
+    }
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+	self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+	(self.0.len, Some(self.0.len))
+    }
+}
+
+// iter
+pub struct Iter<'a, T> {
+    next: &'a Link<T>,
+    len: usize,
+}
+
+impl<T, A: Allocator> List<T, A> {
+    pub fn iter(&self) -> Iter<'_, T> {
+	Iter {
+	    next: &self.head,
+	    len: self.len,
+	}
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a List<T, A> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+	self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+	match self.next {
+	    Link::Empty => None,
+	    Link::More(node) => {
+		let node = unsafe { node.as_ref() };
+		self.next = &node.next;
+		self.len -= 1;
+		Some(&node.elem)
+	    },
+	}
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+	(self.len, Some(self.len))
+    }
+}
+
+// iter_mut
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+    len: usize,
+}
+
+impl<T, A: Allocator> List<T, A> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+	let next = match &self.head {
+	    Link::Empty => None,
+	    Link::More(node) => Some(unsafe { &mut *node.as_ptr() }),
+	};
+	IterMut {
+	    next,
+	    len: self.len,
+	}
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut List<T, A> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+	self.iter_mut()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+	self.next.take().map(|node| {
+	    self.next = match &node.next {
+		Link::Empty => None,
+		Link::More(next) => Some(unsafe { &mut *next.as_ptr() }),
+	    };
+	    self.len -= 1;
+	    &mut node.elem
+	})
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+	(self.len, Some(self.len))
+    }
+}
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+	Self::new()
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for List<T, A> {
+    fn clone(&self) -> Self {
+	let mut new_list = Self::new_in(self.alloc.clone());
+	// pushing would reverse the order, so we always append to the end of
+	// the new list instead
+	let mut tail = &mut new_list.head;
+	for elem in self {
+	    let node = allocator::allocate(&new_list.alloc, Node {
+		elem: elem.clone(),
+		next: Link::Empty,
+	    });
+	    *tail = Link::More(node);
+	    tail = unsafe { &mut (*node.as_ptr()).next };
+	}
+	new_list.len = self.len;
+	new_list
+    }
+}
+
+// elements are pushed in the order of the iterator, so the last one ends up on
+// top of the stack
+impl<T, A: Allocator> Extend<T> for List<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+	for elem in iter {
+	    self.push(elem);
 	}
     }
 }
+
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+	let mut list = Self::new();
+	list.extend(iter);
+	list
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for List<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, A: Allocator> PartialEq for List<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+	self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for List<T, A> {}
+
+impl<T: Hash, A: Allocator> Hash for List<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+	self.len.hash(state);
+	for elem in self {
+	    elem.hash(state);
+	}
+    }
+}
+
+// +----------------------+
+// | Send and Sync Traits |
+// +----------------------+
+
+// the list owns its nodes, the raw pointers to them just can't tell the
+// compiler about it
+unsafe impl<T: Send, A: Allocator + Send> Send for List<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for List<T, A> {}
+
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
+// +--------+
+// | Traits |
+// +--------+
+
+impl<T, A: Allocator> Stack<T> for List<T, A> {
+    fn push(&mut self, elem: T) {
+	List::push(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+	List::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+	List::peek(self)
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut T> {
+	List::peek_mut(self)
+    }
+
+    fn len(&self) -> usize {
+	List::len(self)
+    }
+}
@@ -0,0 +1,158 @@
+// Lists which can be shared between threads.
+
+use std::fmt::{self, Debug};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use super::unsafe_queue;
+
+pub(crate) mod epoch;
+mod lock_free_queue;
+mod lock_free_stack;
+
+pub use lock_free_queue::LockFreeQueue;
+pub use lock_free_stack::LockFreeStack;
+
+// +---------------+
+// | BlockingQueue |
+// +---------------+
+
+// `unsafe_queue::List` behind a lock. Popping from an empty queue waits for the
+// next element, pushing onto a full bounded queue waits for space, which slows
+// producers down to the pace of their consumers.
+pub struct BlockingQueue<T> {
+    state: Mutex<State<T>>,
+    // signaled when an element was pushed or the queue got closed
+    not_empty: Condvar,
+    // signaled when an element was popped or the queue got closed
+    not_full: Condvar,
+}
+
+struct State<T> {
+    queue: unsafe_queue::List<T>,
+    closed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopTimeoutError {
+    // no element arrived in time
+    Timeout,
+    // the queue is closed and all elements were taken
+    Closed,
+}
+
+impl<T> BlockingQueue<T> {
+    pub fn new() -> Self {
+        Self::with_queue(unsafe_queue::List::new())
+    }
+
+    // `push` blocks while the queue holds `capacity` elements. A queue without
+    // any capacity would block every push forever, so a capacity of 0 panics.
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(capacity > 0, "a blocking queue needs a capacity of at least 1");
+        Self::with_queue(unsafe_queue::List::bounded(capacity))
+    }
+
+    fn with_queue(queue: unsafe_queue::List<T>) -> Self {
+        BlockingQueue {
+            state: Mutex::new(State {
+                queue,
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    // waits until there is space for `elem`, gives the element back if the
+    // queue is closed
+    pub fn push(&self, elem: T) -> Result<(), T> {
+        let mut state = self
+            .not_full
+            .wait_while(self.lock(), |state| !state.closed && state.queue.is_full())
+            .unwrap_or_else(PoisonError::into_inner);
+        if state.closed {
+            return Err(elem);
+        }
+        state.queue.push(elem);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    // waits until there is an element, returns `None` once the queue is
+    // closed and all remaining elements were taken
+    pub fn pop(&self) -> Option<T> {
+        let mut state = self
+            .not_empty
+            .wait_while(self.lock(), |state| !state.closed && state.queue.is_empty())
+            .unwrap_or_else(PoisonError::into_inner);
+        self.take(&mut state)
+    }
+
+    // like `pop`, but waits at most `timeout` for an element
+    pub fn pop_timeout(&self, timeout: Duration) -> Result<T, PopTimeoutError> {
+        let (mut state, result) = self
+            .not_empty
+            .wait_timeout_while(self.lock(), timeout, |state| {
+                !state.closed && state.queue.is_empty()
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+        match self.take(&mut state) {
+            Some(elem) => Ok(elem),
+            None if result.timed_out() && !state.closed => Err(PopTimeoutError::Timeout),
+            None => Err(PopTimeoutError::Closed),
+        }
+    }
+
+    // Every waiting and future `push` fails, `pop` still hands out the
+    // remaining elements before it returns `None`.
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().queue.is_empty()
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.lock().queue.capacity()
+    }
+
+    fn take(&self, state: &mut State<T>) -> Option<T> {
+        let elem = state.queue.pop()?;
+        self.not_full.notify_one();
+        Some(elem)
+    }
+
+    // The queue is never left in an inconsistent state, even if a thread
+    // panics while holding the lock, so a poisoned lock can be used as well.
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Default for BlockingQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for BlockingQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("BlockingQueue")
+            .field("queue", &state.queue)
+            .field("closed", &state.closed)
+            .finish()
+    }
+}
@@ -0,0 +1,289 @@
+// Epoch based memory reclamation for the lock-free lists.
+//
+// A node which got unlinked from a lock-free list can still be read by other
+// threads, which loaded a pointer to it just before. So it can not be freed
+// right away, but is retired instead and only freed once no thread can hold a
+// pointer to it anymore.
+//
+// Every operation on a list pins itself to the current global epoch and unpins
+// itself again when it is done. The global epoch only advances if every pinned
+// operation is pinned to it. A node retired in epoch `e` can only be reached by
+// operations pinned to `e - 1` or `e`, so once the global epoch reached `e + 2`
+// all of them are done and the node can be freed.
+//
+// Freeing nodes only after no one can reach them anymore also rules out the
+// ABA problem, as the allocator can not hand out the address of a node to a new
+// one while an operation still compares against it.
+//
+// Records are reused by whichever operation needs one next, so a record can sit
+// idle for a long time. An operation therefore hands the garbage it could not
+// free yet over to a global list when it unpins, and the first operation which
+// pins after the epoch advanced frees whatever of it expired.
+
+use std::cell::UnsafeCell;
+use std::mem;
+use std::ptr;
+use std::sync::atomic::{fence, AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+// number of retired nodes after which an operation tries to free some of them
+const COLLECT_THRESHOLD: usize = 64;
+
+pub struct Collector {
+    epoch: AtomicUsize,
+    // Push-only list of participant records, they are reused by whichever
+    // operation needs one next, so there are never more records than
+    // operations running at the same time.
+    participants: AtomicPtr<Participant>,
+    // garbage handed over by operations which unpinned, see `Orphans`
+    orphans: AtomicPtr<Orphans>,
+    // epoch in which the orphans were collected the last time
+    collected: AtomicUsize,
+}
+
+struct Participant {
+    // record is in use by an operation
+    active: AtomicBool,
+    // epoch the operation is pinned to shifted to the left by one with the
+    // lowest bit set, or zero if it is not pinned
+    pinned: AtomicUsize,
+    // nodes retired by operations which used this record, only accessed by the
+    // operation which holds the record
+    garbage: UnsafeCell<Vec<Retired>>,
+    // never changes after the record was added to the list
+    next: *mut Participant,
+}
+
+// Retired nodes of an operation which already unpinned. The global list is
+// push-only as well, but collecting takes all of it at once.
+struct Orphans {
+    garbage: Vec<Retired>,
+    next: *mut Orphans,
+}
+
+struct Retired {
+    ptr: *mut (),
+    free: unsafe fn(*mut ()),
+    epoch: usize,
+}
+
+// Proof that the current operation is pinned, no node it can reach is freed
+// before the guard is dropped.
+pub struct Guard<'a> {
+    collector: &'a Collector,
+    participant: &'a Participant,
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        Collector {
+            epoch: AtomicUsize::new(0),
+            participants: AtomicPtr::new(ptr::null_mut()),
+            orphans: AtomicPtr::new(ptr::null_mut()),
+            collected: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn pin(&self) -> Guard<'_> {
+        let participant = self.acquire();
+        let epoch = self.epoch.load(Ordering::SeqCst);
+        participant.pinned.store(epoch << 1 | 1, Ordering::SeqCst);
+        // the pin has to be visible to everyone trying to advance the epoch,
+        // before this operation loads any pointer of the list
+        fence(Ordering::SeqCst);
+        // the first operation pinned to a new epoch frees the orphans which
+        // expired meanwhile
+        let collected = self.collected.load(Ordering::Relaxed);
+        if collected != epoch
+            && self
+                .collected
+                .compare_exchange(collected, epoch, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            self.collect_orphans(epoch);
+        }
+        Guard {
+            collector: self,
+            participant,
+        }
+    }
+
+    // takes an unused record or adds a new one
+    fn acquire(&self) -> &Participant {
+        let mut current = self.participants.load(Ordering::Acquire);
+        while let Some(participant) = unsafe { current.as_ref() } {
+            if participant
+                .active
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                return participant;
+            }
+            current = participant.next;
+        }
+
+        let participant = Box::into_raw(Box::new(Participant {
+            active: AtomicBool::new(true),
+            pinned: AtomicUsize::new(0),
+            garbage: UnsafeCell::new(Vec::new()),
+            next: ptr::null_mut(),
+        }));
+        let mut head = self.participants.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*participant).next = head };
+            match self.participants.compare_exchange_weak(
+                head,
+                participant,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return unsafe { &*participant },
+                Err(new_head) => head = new_head,
+            }
+        }
+    }
+
+    fn push_orphans(&self, garbage: Vec<Retired>) {
+        let orphans = Box::into_raw(Box::new(Orphans {
+            garbage,
+            next: ptr::null_mut(),
+        }));
+        let mut head = self.orphans.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*orphans).next = head };
+            match self.orphans.compare_exchange_weak(
+                head,
+                orphans,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(new_head) => head = new_head,
+            }
+        }
+    }
+
+    // Takes the whole global list, so no other operation touches the orphans
+    // while they are freed, and puts back what did not expire yet.
+    fn collect_orphans(&self, epoch: usize) {
+        let mut current = self.orphans.swap(ptr::null_mut(), Ordering::Acquire);
+        let mut remaining = Vec::new();
+        while !current.is_null() {
+            let orphans = unsafe { Box::from_raw(current) };
+            current = orphans.next;
+            remaining.extend(collect(orphans.garbage, epoch));
+        }
+        if !remaining.is_empty() {
+            self.push_orphans(remaining);
+        }
+    }
+
+    // advances the global epoch if every pinned operation is pinned to it
+    fn try_advance(&self) -> usize {
+        let epoch = self.epoch.load(Ordering::SeqCst);
+        fence(Ordering::SeqCst);
+        let mut current = self.participants.load(Ordering::Acquire);
+        while let Some(participant) = unsafe { current.as_ref() } {
+            let pinned = participant.pinned.load(Ordering::SeqCst);
+            if pinned & 1 == 1 && pinned >> 1 != epoch {
+                return epoch;
+            }
+            current = participant.next;
+        }
+        match self.epoch.compare_exchange(
+            epoch,
+            epoch.wrapping_add(1),
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Ok(_) => epoch.wrapping_add(1),
+            Err(epoch) => epoch,
+        }
+    }
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Frees the retired nodes which expired in `epoch` and returns the others.
+// `epoch` may be older than some of the nodes, if it was loaded before the
+// global epoch advanced, so the distance is compared signed to keep those.
+fn collect(mut garbage: Vec<Retired>, epoch: usize) -> Vec<Retired> {
+    garbage.retain(|retired| {
+        if epoch.wrapping_sub(retired.epoch) as isize >= 2 {
+            unsafe { (retired.free)(retired.ptr) };
+            false
+        } else {
+            true
+        }
+    });
+    garbage
+}
+
+// No operation can be running anymore, so all retired nodes can be freed.
+impl Drop for Collector {
+    fn drop(&mut self) {
+        let mut current = *self.participants.get_mut();
+        while !current.is_null() {
+            let participant = unsafe { Box::from_raw(current) };
+            for retired in participant.garbage.into_inner() {
+                unsafe { (retired.free)(retired.ptr) };
+            }
+            current = participant.next;
+        }
+        let mut current = *self.orphans.get_mut();
+        while !current.is_null() {
+            let orphans = unsafe { Box::from_raw(current) };
+            for retired in orphans.garbage {
+                unsafe { (retired.free)(retired.ptr) };
+            }
+            current = orphans.next;
+        }
+    }
+}
+
+impl Guard<'_> {
+    // Frees `ptr` once no operation can reach it anymore.
+    //
+    // Safety: `ptr` was created by `Box::into_raw`, is unlinked from the list,
+    // such that operations pinned after this one can not reach it, and is
+    // retired only once.
+    pub unsafe fn retire<N>(&self, ptr: *mut N) {
+        unsafe fn free<N>(ptr: *mut ()) {
+            drop(Box::from_raw(ptr as *mut N));
+        }
+
+        let epoch = self.collector.epoch.load(Ordering::SeqCst);
+        // we hold the record, so nobody else touches its garbage
+        let garbage = &mut *self.participant.garbage.get();
+        garbage.push(Retired {
+            ptr: ptr as *mut (),
+            free: free::<N>,
+            epoch,
+        });
+        if garbage.len() >= COLLECT_THRESHOLD {
+            let epoch = self.collector.try_advance();
+            *garbage = collect(mem::take(garbage), epoch);
+        }
+    }
+}
+
+// The record may not be used again for a long time, so the garbage which can
+// not be freed yet goes to the global list instead of staying in the record.
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        // we still hold the record
+        let garbage = unsafe { &mut *self.participant.garbage.get() };
+        if !garbage.is_empty() {
+            let epoch = self.collector.try_advance();
+            let garbage = collect(mem::take(garbage), epoch);
+            if !garbage.is_empty() {
+                self.collector.push_orphans(garbage);
+            }
+        }
+        self.participant.pinned.store(0, Ordering::SeqCst);
+        self.participant.active.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,169 @@
+// Lock-free multi-producer multi-consumer queue of Michael and Scott.
+//
+// Just like `unsafe_queue::List` it has a head to pop from and a tail to push
+// onto, but both are atomics which are only ever changed by compare-and-swap.
+// The head always points to a dummy node, the first element is the one after
+// it. Popping moves the head onto the node of the popped element, which becomes
+// the new dummy, such that head and tail never have to be changed together.
+//
+// The tail can lag one node behind, if a push linked its node but did not move
+// the tail yet. Every operation which notices this moves the tail on itself
+// instead of waiting for the push to finish.
+
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use super::epoch::Collector;
+
+pub struct LockFreeQueue<T> {
+    head: AtomicPtr<Node<T>>,
+    tail: AtomicPtr<Node<T>>,
+    collector: Collector,
+}
+
+struct Node<T> {
+    // uninitialized for the dummy node, its element was already popped
+    elem: MaybeUninit<T>,
+    next: AtomicPtr<Node<T>>,
+}
+
+impl<T> Node<T> {
+    fn new(elem: MaybeUninit<T>) -> *mut Self {
+        Box::into_raw(Box::new(Node {
+            elem,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }))
+    }
+}
+
+impl<T> LockFreeQueue<T> {
+    pub fn new() -> Self {
+        let dummy = Node::new(MaybeUninit::uninit());
+        LockFreeQueue {
+            head: AtomicPtr::new(dummy),
+            tail: AtomicPtr::new(dummy),
+            collector: Collector::new(),
+        }
+    }
+
+    pub fn push(&self, elem: T) {
+        let new_tail = Node::new(MaybeUninit::new(elem));
+        let _guard = self.collector.pin();
+        loop {
+            let tail = self.tail.load(Ordering::Acquire);
+            // the tail can not be freed while we are pinned
+            let next = unsafe { &(*tail).next };
+            let after_tail = next.load(Ordering::Acquire);
+            if !after_tail.is_null() {
+                // the tail is lagging behind, help the other push
+                let _ = self.tail.compare_exchange(
+                    tail,
+                    after_tail,
+                    Ordering::Release,
+                    Ordering::Relaxed,
+                );
+                continue;
+            }
+            if next
+                .compare_exchange(
+                    ptr::null_mut(),
+                    new_tail,
+                    Ordering::Release,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+            {
+                // if this fails someone else already moved the tail for us
+                let _ = self.tail.compare_exchange(
+                    tail,
+                    new_tail,
+                    Ordering::Release,
+                    Ordering::Relaxed,
+                );
+                return;
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard = self.collector.pin();
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            let first = unsafe { (*head).next.load(Ordering::Acquire) };
+            if first.is_null() {
+                return None;
+            }
+            let tail = self.tail.load(Ordering::Acquire);
+            if head == tail {
+                // the tail still points to the dummy we are about to retire,
+                // so move it on first
+                let _ = self
+                    .tail
+                    .compare_exchange(tail, first, Ordering::Release, Ordering::Relaxed);
+                continue;
+            }
+            if self
+                .head
+                .compare_exchange(head, first, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                unsafe {
+                    // `first` is the new dummy, so we are the only one to take
+                    // its element
+                    let elem = (*first).elem.assume_init_read();
+                    guard.retire(head);
+                    return Some(elem);
+                }
+            }
+        }
+    }
+
+    // might already be outdated when it returns, if other threads use the
+    // queue at the same time
+    pub fn is_empty(&self) -> bool {
+        let _guard = self.collector.pin();
+        let head = self.head.load(Ordering::Acquire);
+        unsafe { (*head).next.load(Ordering::Acquire).is_null() }
+    }
+}
+
+impl<T> Drop for LockFreeQueue<T> {
+    fn drop(&mut self) {
+        // no one else can access the queue anymore, so the nodes can be freed
+        // directly, the retired ones are freed by the collector
+        let dummy = unsafe { Box::from_raw(*self.head.get_mut()) };
+        let mut current = dummy.next.load(Ordering::Relaxed);
+        while !current.is_null() {
+            let mut node = unsafe { Box::from_raw(current) };
+            unsafe { node.elem.assume_init_drop() };
+            current = *node.next.get_mut();
+        }
+    }
+}
+
+impl<T> Default for LockFreeQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Extend<T> for LockFreeQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for LockFreeQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+// the elements are moved between threads through the queue
+unsafe impl<T: Send> Send for LockFreeQueue<T> {}
+unsafe impl<T: Send> Sync for LockFreeQueue<T> {}
@@ -0,0 +1,136 @@
+// Lock-free stack of Treiber, the concurrent counterpart of
+// `better_stack::List`.
+//
+// The head is an atomic pointer which is only ever changed by compare-and-swap
+// of the node the operation started with. Between loading the head and the
+// swap another thread could pop that node, free it and push a new node which
+// gets the same address, such that the swap succeeds with an outdated `next`
+// (the ABA problem). Popped nodes are therefore only freed by the epoch based
+// collector, once no running operation can hold a pointer to them anymore.
+
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use super::epoch::Collector;
+
+pub struct LockFreeStack<T> {
+    head: AtomicPtr<Node<T>>,
+    collector: Collector,
+}
+
+struct Node<T> {
+    // moved out by `pop`, so retired nodes must not drop it again
+    elem: ManuallyDrop<T>,
+    // never changes once the node is pushed
+    next: *mut Node<T>,
+}
+
+impl<T> LockFreeStack<T> {
+    pub fn new() -> Self {
+        LockFreeStack {
+            head: AtomicPtr::new(ptr::null_mut()),
+            collector: Collector::new(),
+        }
+    }
+
+    pub fn push(&self, elem: T) {
+        let new_head = Box::into_raw(Box::new(Node {
+            elem: ManuallyDrop::new(elem),
+            next: ptr::null_mut(),
+        }));
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            // the node is not shared yet, so we can still change it
+            unsafe { (*new_head).next = head };
+            match self.head.compare_exchange_weak(
+                head,
+                new_head,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(new) => head = new,
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let guard = self.collector.pin();
+        let mut head = self.head.load(Ordering::Acquire);
+        loop {
+            // the head can not be freed while we are pinned
+            let node = unsafe { head.as_ref() }?;
+            match self.head.compare_exchange_weak(
+                head,
+                node.next,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => unsafe {
+                    // we unlinked the node, so we are the only one to take its
+                    // element
+                    let elem = ptr::read(&*node.elem);
+                    guard.retire(head);
+                    return Some(elem);
+                },
+                Err(new) => head = new,
+            }
+        }
+    }
+
+    // Other threads could pop the head while we look at it, so the stack can
+    // only be peeked at with exclusive access.
+    pub fn peek(&mut self) -> Option<&T> {
+        unsafe { self.head.get_mut().as_ref().map(|node| &*node.elem) }
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.head.get_mut().as_mut().map(|node| &mut *node.elem) }
+    }
+
+    // might already be outdated when it returns, if other threads use the
+    // stack at the same time
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+}
+
+impl<T> Drop for LockFreeStack<T> {
+    fn drop(&mut self) {
+        // no one else can access the stack anymore, so the nodes can be freed
+        // directly, the popped ones are freed by the collector
+        let mut current = *self.head.get_mut();
+        while !current.is_null() {
+            let mut node = unsafe { Box::from_raw(current) };
+            unsafe { ManuallyDrop::drop(&mut node.elem) };
+            current = node.next;
+        }
+    }
+}
+
+impl<T> Default for LockFreeStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Extend<T> for LockFreeStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for LockFreeStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Self::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+// the elements are moved between threads through the stack
+unsafe impl<T: Send> Send for LockFreeStack<T> {}
+unsafe impl<T: Send> Sync for LockFreeStack<T> {}
@@ -0,0 +1,67 @@
+// Common interfaces of the different linked list implementations, such that
+// code can be written generic over the container it uses.
+//
+// The inherent methods of the lists are named after what they do for that
+// specific list (e.g. `push` of a queue appends at the back), the methods of
+// these traits are named after what they do for any container implementing it.
+
+// Last in, first out. All operations work on the same end of the container.
+pub trait Stack<T> {
+    fn push(&mut self, elem: T);
+
+    fn pop(&mut self) -> Option<T>;
+
+    fn peek(&self) -> Option<&T>;
+
+    fn peek_mut(&mut self) -> Option<&mut T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// First in, first out. Elements are added at the back and taken from the front.
+pub trait Queue<T> {
+    fn push_back(&mut self, elem: T);
+
+    fn pop_front(&mut self) -> Option<T>;
+
+    fn front(&self) -> Option<&T>;
+
+    fn front_mut(&mut self) -> Option<&mut T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// Double-ended queue, which can add and take elements at both ends.
+pub trait Deque<T>: Queue<T> {
+    fn push_front(&mut self, elem: T);
+
+    fn pop_back(&mut self) -> Option<T>;
+
+    fn back(&self) -> Option<&T>;
+
+    fn back_mut(&mut self) -> Option<&mut T>;
+}
+
+// Inmutable stack, where every operation returns a new version of the stack
+// and leaves the old one untouched.
+pub trait PersistentStack<T>: Sized {
+    fn prepend(&self, elem: T) -> Self;
+
+    fn tail(&self) -> Self;
+
+    fn head(&self) -> Option<&T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
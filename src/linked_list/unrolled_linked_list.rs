@@ -0,0 +1,1508 @@
+// Doubly linked list which stores up to `N` elements in every node instead of a
+// single one. Walking the list mostly stays inside of the element array of a
+// node, which is a lot friendlier to the cache for small element types, and
+// there is only one allocation for every `N` elements.
+//
+// Inside of a node the elements are kept at the start of the array, so putting
+// an element into the middle of a node moves the ones after it, which is cheap
+// as long as `N` is small. A full node is split in half when an element has to
+// be put into it, a node which gets less than half full by a removal is merged
+// with one of its neighbours if they fit into a single node.
+
+use std::alloc::{self, Layout};
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem::{self, MaybeUninit};
+use std::ops::{Index, IndexMut};
+use std::ptr::{self, NonNull};
+
+use super::allocator::{self, Global, TryReserveError};
+use super::{Deque, Queue, Stack};
+
+pub struct UnrolledList<T, const N: usize> {
+    front: Link<T, N>,
+    back: Link<T, N>,
+    len: usize,
+    _boo: PhantomData<T>,
+}
+
+type Link<T, const N: usize> = Option<NonNull<Node<T, N>>>;
+
+struct Node<T, const N: usize> {
+    front: Link<T, N>,
+    back: Link<T, N>,
+    // only the first `len` elements are initialized
+    len: usize,
+    elems: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> Node<T, N> {
+    // allocates an empty node, the error is the layout which did not fit
+    fn try_new() -> Result<NonNull<Self>, Layout> {
+        let node = Node {
+            front: None,
+            back: None,
+            len: 0,
+            elems: std::array::from_fn(|_| MaybeUninit::uninit()),
+        };
+        allocator::try_allocate(&Global, node).map_err(|_| Layout::new::<Self>())
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    fn get(&self, offset: usize) -> &T {
+        assert!(offset < self.len);
+        unsafe { self.elems[offset].assume_init_ref() }
+    }
+
+    fn get_mut(&mut self, offset: usize) -> &mut T {
+        assert!(offset < self.len);
+        unsafe { self.elems[offset].assume_init_mut() }
+    }
+
+    // pointer to an element which does not borrow the whole node, such that
+    // two elements of the same node can be borrowed at once
+    unsafe fn elem_ptr(node: NonNull<Self>, offset: usize) -> *mut T {
+        debug_assert!(offset < (*node.as_ptr()).len);
+        ptr::addr_of_mut!((*node.as_ptr()).elems)
+            .cast::<T>()
+            .add(offset)
+    }
+
+    // moves the elements from `offset` on one to the back to make room
+    fn insert(&mut self, offset: usize, elem: T) {
+        assert!(offset <= self.len && !self.is_full());
+        unsafe {
+            let elems = self.elems.as_mut_ptr();
+            ptr::copy(elems.add(offset), elems.add(offset + 1), self.len - offset);
+            (*elems.add(offset)).write(elem);
+        }
+        self.len += 1;
+    }
+
+    // moves the elements after `offset` one to the front to close the gap
+    fn remove(&mut self, offset: usize) -> T {
+        assert!(offset < self.len);
+        self.len -= 1;
+        unsafe {
+            let elems = self.elems.as_mut_ptr();
+            let elem = (*elems.add(offset)).assume_init_read();
+            ptr::copy(elems.add(offset + 1), elems.add(offset), self.len - offset);
+            elem
+        }
+    }
+
+    // moves all elements of `other` behind the ones of `self`
+    fn take_all(&mut self, other: &mut Self) {
+        assert!(self.len + other.len <= N);
+        unsafe {
+            ptr::copy_nonoverlapping(
+                other.elems.as_ptr(),
+                self.elems.as_mut_ptr().add(self.len),
+                other.len,
+            );
+        }
+        self.len += other.len;
+        other.len = 0;
+    }
+}
+
+impl<T, const N: usize> Drop for Node<T, N> {
+    fn drop(&mut self) {
+        for elem in &mut self.elems[..self.len] {
+            unsafe { elem.assume_init_drop() };
+        }
+    }
+}
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    pub fn new() -> Self {
+        const { assert!(N >= 2, "nodes have to hold at least two elements") };
+        Self {
+            front: None,
+            back: None,
+            len: 0,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // number of allocated nodes, each of them holds up to `N` elements
+    pub fn node_count(&self) -> usize {
+        let mut count = 0;
+        let mut cur = self.front;
+        while let Some(node) = cur {
+            count += 1;
+            cur = unsafe { (*node.as_ptr()).back };
+        }
+        count
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        if let Err(err) = self.try_push_front(elem) {
+            err.handle();
+        }
+    }
+
+    // like `push_front`, but hands the element back instead of aborting when
+    // there is no memory left for a new node
+    pub fn try_push_front(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        unsafe {
+            let front = match self.front {
+                Some(front) if !(*front.as_ptr()).is_full() => front,
+                // start a new node, instead of splitting the full one, such
+                // that pushing a lot of elements leaves full nodes behind
+                _ => match Node::try_new() {
+                    Ok(new) => self.link_after(None, new),
+                    Err(layout) => return Err(TryReserveError::alloc_error(elem, layout)),
+                },
+            };
+            (*front.as_ptr()).insert(0, elem);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        if let Err(err) = self.try_push_back(elem) {
+            err.handle();
+        }
+    }
+
+    pub fn try_push_back(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        unsafe {
+            let back = match self.back {
+                Some(back) if !(*back.as_ptr()).is_full() => back,
+                _ => match Node::try_new() {
+                    Ok(new) => self.link_after(self.back, new),
+                    Err(layout) => return Err(TryReserveError::alloc_error(elem, layout)),
+                },
+            };
+            let node = &mut *back.as_ptr();
+            node.insert(node.len, elem);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    // pushes the elements to the back until one of them doesn't fit, that one
+    // is handed back and the rest of the iterator is dropped
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryReserveError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.try_push_back(elem)?;
+        }
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.front.map(|front| unsafe {
+            let elem = (*front.as_ptr()).remove(0);
+            self.len -= 1;
+            self.rebalance(front, &mut (None, 0));
+            elem
+        })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.back.map(|back| unsafe {
+            let node = &mut *back.as_ptr();
+            let elem = node.remove(node.len - 1);
+            self.len -= 1;
+            self.rebalance(back, &mut (None, 0));
+            elem
+        })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        unsafe { self.front.map(|node| (*node.as_ptr()).get(0)) }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.front.map(|node| (*node.as_ptr()).get_mut(0)) }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe {
+            self.back.map(|node| {
+                let node = &*node.as_ptr();
+                node.get(node.len - 1)
+            })
+        }
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        unsafe {
+            self.back.map(|node| {
+                let node = &mut *node.as_ptr();
+                node.get_mut(node.len - 1)
+            })
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+// Drop Trait
+
+impl<T, const N: usize> Drop for UnrolledList<T, N> {
+    fn drop(&mut self) {
+        // every node drops its own elements
+        let mut cur = self.front.take();
+        while let Some(node) = cur {
+            let node = unsafe { allocator::deallocate(&Global, node) };
+            cur = node.back;
+        }
+    }
+}
+
+// +------------------+
+// | Internal Helpers |
+// +------------------+
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    // links the empty node `new` after `prev`, or at the front for `None`
+    unsafe fn link_after(
+        &mut self,
+        prev: Link<T, N>,
+        new: NonNull<Node<T, N>>,
+    ) -> NonNull<Node<T, N>> {
+        let next = match prev {
+            Some(prev) => (*prev.as_ptr()).back,
+            None => self.front,
+        };
+        (*new.as_ptr()).front = prev;
+        (*new.as_ptr()).back = next;
+        match prev {
+            Some(prev) => (*prev.as_ptr()).back = Some(new),
+            None => self.front = Some(new),
+        }
+        match next {
+            Some(next) => (*next.as_ptr()).front = Some(new),
+            None => self.back = Some(new),
+        }
+        new
+    }
+
+    // unlinks and frees an empty node
+    unsafe fn unlink(&mut self, node: NonNull<Node<T, N>>) {
+        let node = allocator::deallocate(&Global, node);
+        debug_assert_eq!(node.len, 0);
+        match node.front {
+            Some(prev) => (*prev.as_ptr()).back = node.back,
+            None => self.front = node.back,
+        }
+        match node.back {
+            Some(next) => (*next.as_ptr()).front = node.front,
+            None => self.back = node.front,
+        }
+    }
+
+    // Splits `node` in half if it is full, such that an element can be put at
+    // `offset`. Returns the node and offset where the element has to be put,
+    // or the layout of the node which could not be allocated.
+    unsafe fn make_room(
+        &mut self,
+        node: NonNull<Node<T, N>>,
+        offset: usize,
+    ) -> Result<(NonNull<Node<T, N>>, usize), Layout> {
+        if !(*node.as_ptr()).is_full() {
+            return Ok((node, offset));
+        }
+        let mid = N / 2;
+        let new = self.try_split_node(node, mid)?;
+        if offset <= mid {
+            Ok((node, offset))
+        } else {
+            Ok((new, offset - mid))
+        }
+    }
+
+    // moves the elements of `node` from `offset` on into a new node right
+    // behind it, aborting if there is no memory left for it
+    unsafe fn split_node(
+        &mut self,
+        node: NonNull<Node<T, N>>,
+        offset: usize,
+    ) -> NonNull<Node<T, N>> {
+        self.try_split_node(node, offset)
+            .unwrap_or_else(|layout| alloc::handle_alloc_error(layout))
+    }
+
+    // like `split_node`, but leaves `node` as it is if the new node can not be
+    // allocated
+    unsafe fn try_split_node(
+        &mut self,
+        node: NonNull<Node<T, N>>,
+        offset: usize,
+    ) -> Result<NonNull<Node<T, N>>, Layout> {
+        let new = self.link_after(Some(node), Node::try_new()?);
+        let old = &mut *node.as_ptr();
+        let split = &mut *new.as_ptr();
+        ptr::copy_nonoverlapping(
+            old.elems.as_ptr().add(offset),
+            split.elems.as_mut_ptr(),
+            old.len - offset,
+        );
+        split.len = old.len - offset;
+        old.len = offset;
+        Ok(new)
+    }
+
+    // Cuts the list behind `at` (in front of the first node for `None`) and
+    // returns the nodes behind the cut, which hold `len` elements.
+    unsafe fn detach_after(&mut self, at: Link<T, N>, len: usize) -> Self {
+        let first = match at {
+            Some(at) => (*at.as_ptr()).back.take(),
+            None => self.front.take(),
+        };
+        let mut detached = Self::new();
+        if let Some(first) = first {
+            (*first.as_ptr()).front = None;
+            detached.front = Some(first);
+            detached.back = mem::replace(&mut self.back, at);
+            detached.len = len;
+            self.len -= len;
+        }
+        detached
+    }
+
+    // Links all nodes of `other` behind `at` (at the front for `None`) and
+    // merges the nodes at the seams if they got too empty. `pos` is moved
+    // along with its element.
+    unsafe fn attach_after(
+        &mut self,
+        at: Link<T, N>,
+        mut other: Self,
+        pos: &mut (Link<T, N>, usize),
+    ) {
+        let (Some(first), Some(last)) = (other.front.take(), other.back.take()) else {
+            return;
+        };
+        let next = match at {
+            Some(at) => (*at.as_ptr()).back,
+            None => self.front,
+        };
+        (*first.as_ptr()).front = at;
+        (*last.as_ptr()).back = next;
+        match at {
+            Some(at) => (*at.as_ptr()).back = Some(first),
+            None => self.front = Some(first),
+        }
+        match next {
+            Some(next) => (*next.as_ptr()).front = Some(last),
+            None => self.back = Some(last),
+        }
+        self.len += mem::replace(&mut other.len, 0);
+        // `at` is never freed by merging `last`, it can only take over the
+        // elements of `last`
+        self.rebalance(last, pos);
+        if let Some(at) = at {
+            self.rebalance(at, pos);
+        }
+    }
+
+    // node and offset of the element at `index`, walking from whichever end of
+    // the list is closer
+    fn locate(&self, index: usize) -> Option<(NonNull<Node<T, N>>, usize)> {
+        if index >= self.len {
+            return None;
+        }
+        unsafe {
+            if index < self.len / 2 {
+                let mut index = index;
+                let mut cur = self.front;
+                while let Some(node) = cur {
+                    let len = (*node.as_ptr()).len;
+                    if index < len {
+                        return Some((node, index));
+                    }
+                    index -= len;
+                    cur = (*node.as_ptr()).back;
+                }
+            } else {
+                // how many elements are behind the one we are looking for
+                let mut behind = self.len - 1 - index;
+                let mut cur = self.back;
+                while let Some(node) = cur {
+                    let len = (*node.as_ptr()).len;
+                    if behind < len {
+                        return Some((node, len - 1 - behind));
+                    }
+                    behind -= len;
+                    cur = (*node.as_ptr()).front;
+                }
+            }
+        }
+        unreachable!("the nodes hold fewer elements than the list")
+    }
+
+    // Merges `node` with one of its neighbours, if it got less than half full
+    // and they fit into a single node. `pos` is moved along with its element.
+    unsafe fn rebalance(&mut self, node: NonNull<Node<T, N>>, pos: &mut (Link<T, N>, usize)) {
+        let len = (*node.as_ptr()).len;
+        if len == 0 {
+            self.unlink(node);
+            return;
+        }
+        if len >= N / 2 {
+            return;
+        }
+        if let Some(next) = (*node.as_ptr()).back {
+            if len + (*next.as_ptr()).len <= N {
+                // the elements of `next` follow the ones of `node`
+                if pos.0 == Some(next) {
+                    *pos = (Some(node), len + pos.1);
+                }
+                (*node.as_ptr()).take_all(&mut *next.as_ptr());
+                self.unlink(next);
+                return;
+            }
+        }
+        if let Some(prev) = (*node.as_ptr()).front {
+            let prev_len = (*prev.as_ptr()).len;
+            if prev_len + len <= N {
+                if pos.0 == Some(node) {
+                    *pos = (Some(prev), prev_len + pos.1);
+                }
+                (*prev.as_ptr()).take_all(&mut *node.as_ptr());
+                self.unlink(node);
+            }
+        }
+    }
+}
+
+// +-----------------+
+// | Iterator Traits |
+// +-----------------+
+
+pub struct Iter<'a, T, const N: usize> {
+    front: (Link<T, N>, usize),
+    back: (Link<T, N>, usize),
+    len: usize,
+    _boo: PhantomData<&'a T>,
+}
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter {
+            front: (self.front, 0),
+            back: (self.back, self.back_offset()),
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    fn back_offset(&self) -> usize {
+        self.back
+            .map_or(0, |node| unsafe { (*node.as_ptr()).len - 1 })
+    }
+}
+
+// position after `pos`, which is on a real element
+unsafe fn step_next<T, const N: usize>(pos: (Link<T, N>, usize)) -> (Link<T, N>, usize) {
+    match pos {
+        (Some(node), offset) if offset + 1 < (*node.as_ptr()).len => (Some(node), offset + 1),
+        (Some(node), _) => ((*node.as_ptr()).back, 0),
+        (None, _) => (None, 0),
+    }
+}
+
+// position before `pos`, which is on a real element
+unsafe fn step_prev<T, const N: usize>(pos: (Link<T, N>, usize)) -> (Link<T, N>, usize) {
+    match pos {
+        (Some(node), offset) if offset > 0 => (Some(node), offset - 1),
+        (Some(node), _) => {
+            let prev = (*node.as_ptr()).front;
+            (prev, prev.map_or(0, |prev| (*prev.as_ptr()).len - 1))
+        }
+        (None, _) => (None, 0),
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a UnrolledList<T, N> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.front.0.map(|node| unsafe {
+                let elem = (*node.as_ptr()).get(self.front.1);
+                self.len -= 1;
+                self.front = step_next(self.front);
+                elem
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for Iter<'a, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.0.map(|node| unsafe {
+                let elem = (*node.as_ptr()).get(self.back.1);
+                self.len -= 1;
+                self.back = step_prev(self.back);
+                elem
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for Iter<'a, T, N> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct IterMut<'a, T, const N: usize> {
+    front: (Link<T, N>, usize),
+    back: (Link<T, N>, usize),
+    len: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, N> {
+        IterMut {
+            front: (self.front, 0),
+            back: (self.back, self.back_offset()),
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut UnrolledList<T, N> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for IterMut<'a, T, N> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.front.0.map(|node| unsafe {
+                let elem = (*node.as_ptr()).get_mut(self.front.1);
+                self.len -= 1;
+                self.front = step_next(self.front);
+                elem
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for IterMut<'a, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.0.map(|node| unsafe {
+                let elem = (*node.as_ptr()).get_mut(self.back.1);
+                self.len -= 1;
+                self.back = step_prev(self.back);
+                elem
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for IterMut<'a, T, N> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct IntoIter<T, const N: usize> {
+    list: UnrolledList<T, N>,
+}
+
+impl<T, const N: usize> IntoIterator for UnrolledList<T, N> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter { list: self }
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<T, const N: usize> Default for UnrolledList<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for UnrolledList<T, N> {
+    fn clone(&self) -> Self {
+        let mut new_list = Self::new();
+        for item in self {
+            new_list.push_back(item.clone());
+        }
+        new_list
+    }
+}
+
+impl<T, const N: usize> Extend<T> for UnrolledList<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for UnrolledList<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for UnrolledList<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for UnrolledList<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for UnrolledList<T, N> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for UnrolledList<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for UnrolledList<T, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for UnrolledList<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for item in self {
+            item.hash(state);
+        }
+    }
+}
+
+// +----------------------+
+// | Send and Sync Traits |
+// +----------------------+
+unsafe impl<T: Send, const N: usize> Send for UnrolledList<T, N> {}
+unsafe impl<T: Sync, const N: usize> Sync for UnrolledList<T, N> {}
+
+unsafe impl<'a, T: Send, const N: usize> Send for Iter<'a, T, N> {}
+unsafe impl<'a, T: Sync, const N: usize> Sync for Iter<'a, T, N> {}
+
+unsafe impl<'a, T: Send, const N: usize> Send for IterMut<'a, T, N> {}
+unsafe impl<'a, T: Sync, const N: usize> Sync for IterMut<'a, T, N> {}
+
+// +-----------------------+
+// | Cursor Implementation |
+// +-----------------------+
+
+// Same "ghost" element model as the cursor of `LinkedList`, a real element is
+// addressed by its node and its offset inside of the node.
+pub struct CursorMut<'a, T, const N: usize> {
+    cur: (Link<T, N>, usize),
+    list: &'a mut UnrolledList<T, N>,
+    index: Option<usize>,
+}
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, N> {
+        CursorMut {
+            cur: (None, 0),
+            list: self,
+            index: None,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> CursorMut<'a, T, N> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        if self.cur.0.is_some() {
+            self.cur = unsafe { step_next(self.cur) };
+            if self.cur.0.is_some() {
+                *self.index.as_mut().unwrap() += 1;
+            } else {
+                // We just walked to the ghost, no more index
+                self.index = None;
+            }
+        } else if !self.list.is_empty() {
+            // we are the ghost, and there is a real front, so move to it
+            self.cur = (self.list.front, 0);
+            self.index = Some(0);
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if self.cur.0.is_some() {
+            self.cur = unsafe { step_prev(self.cur) };
+            if self.cur.0.is_some() {
+                *self.index.as_mut().unwrap() -= 1;
+            } else {
+                self.index = None;
+            }
+        } else if !self.list.is_empty() {
+            // we are the ghost, and there is a real back, so move to it
+            self.cur = (self.list.back, self.list.back_offset());
+            self.index = Some(self.list.len - 1);
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        let (node, offset) = self.cur;
+        unsafe { node.map(|node| (*node.as_ptr()).get_mut(offset)) }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let (node, offset) = match self.cur.0 {
+            Some(_) => unsafe { step_next(self.cur) },
+            None => (self.list.front, 0),
+        };
+        unsafe { node.map(|node| (*node.as_ptr()).get_mut(offset)) }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let (node, offset) = match self.cur.0 {
+            Some(_) => unsafe { step_prev(self.cur) },
+            None => (self.list.back, self.list.back_offset()),
+        };
+        unsafe { node.map(|node| (*node.as_ptr()).get_mut(offset)) }
+    }
+
+    pub fn insert_after(&mut self, elem: T) {
+        if let Err(err) = self.try_insert_after(elem) {
+            err.handle();
+        }
+    }
+
+    // like `insert_after`, but hands the element back instead of aborting when
+    // there is no memory left to split a full node
+    pub fn try_insert_after(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        let (Some(node), offset) = self.cur else {
+            // we are the ghost, so after us is the front of the list
+            return self.list.try_push_front(elem);
+        };
+        unsafe {
+            let (node, offset) = match self.list.make_room(node, offset + 1) {
+                Ok(room) => room,
+                Err(layout) => return Err(TryReserveError::alloc_error(elem, layout)),
+            };
+            (*node.as_ptr()).insert(offset, elem);
+            // our element is right before the new one, even if the node was
+            // split
+            self.cur = (Some(node), offset - 1);
+        }
+        self.list.len += 1;
+        Ok(())
+    }
+
+    pub fn insert_before(&mut self, elem: T) {
+        if let Err(err) = self.try_insert_before(elem) {
+            err.handle();
+        }
+    }
+
+    pub fn try_insert_before(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        let (Some(node), offset) = self.cur else {
+            // we are the ghost, so before us is the back of the list
+            return self.list.try_push_back(elem);
+        };
+        unsafe {
+            let (node, offset) = match self.list.make_room(node, offset) {
+                Ok(room) => room,
+                Err(layout) => return Err(TryReserveError::alloc_error(elem, layout)),
+            };
+            (*node.as_ptr()).insert(offset, elem);
+            // our element is right after the new one, which may be the start
+            // of the next node if the new one went to the end of the split
+            self.cur = step_next((Some(node), offset));
+        }
+        // the cursor does not move, but its element is one further back
+        *self.index.as_mut().unwrap() += 1;
+        self.list.len += 1;
+        Ok(())
+    }
+
+    // removes the current element and moves onto the next one, which takes
+    // over the index (unless it is the ghost)
+    pub fn remove_current(&mut self) -> Option<T> {
+        let (node, offset) = self.cur;
+        let node = node?;
+        unsafe {
+            let elem = (*node.as_ptr()).remove(offset);
+            self.list.len -= 1;
+            self.cur = if offset < (*node.as_ptr()).len {
+                (Some(node), offset)
+            } else {
+                ((*node.as_ptr()).back, 0)
+            };
+            if self.cur.0.is_none() {
+                self.index = None;
+            }
+            self.list.rebalance(node, &mut self.cur);
+            Some(elem)
+        }
+    }
+
+    pub fn remove_after(&mut self) -> Option<T> {
+        let (node, offset) = match self.cur.0 {
+            Some(_) => unsafe { step_next(self.cur) },
+            // we are the ghost, so after us is the front of the list
+            None => (self.list.front, 0),
+        };
+        let node = node?;
+        unsafe {
+            let elem = (*node.as_ptr()).remove(offset);
+            self.list.len -= 1;
+            self.list.rebalance(node, &mut self.cur);
+            Some(elem)
+        }
+    }
+
+    pub fn remove_before(&mut self) -> Option<T> {
+        let (node, offset) = match self.cur.0 {
+            Some(_) => unsafe { step_prev(self.cur) },
+            // we are the ghost, so before us is the back of the list
+            None => (self.list.back, self.list.back_offset()),
+        };
+        let node = node?;
+        unsafe {
+            let elem = (*node.as_ptr()).remove(offset);
+            self.list.len -= 1;
+            if self.cur.0 == Some(node) {
+                // our element moved one to the front along with the rest
+                self.cur.1 -= 1;
+            }
+            if let Some(index) = self.index.as_mut() {
+                *index -= 1;
+            }
+            self.list.rebalance(node, &mut self.cur);
+            Some(elem)
+        }
+    }
+
+    // Returns everything in front of the cursor, the node of the current
+    // element is split if necessary. On the ghost the whole list is returned.
+    pub fn split_before(&mut self) -> UnrolledList<T, N> {
+        let (Some(node), offset) = self.cur else {
+            return mem::take(self.list);
+        };
+        unsafe {
+            let node = if offset > 0 {
+                let new = self.list.split_node(node, offset);
+                self.cur = (Some(new), 0);
+                new
+            } else {
+                node
+            };
+            let before = self.index.unwrap();
+            let rest = self
+                .list
+                .detach_after((*node.as_ptr()).front, self.list.len - before);
+            let mut output = mem::replace(self.list, rest);
+            self.index = Some(0);
+            self.list.rebalance(node, &mut self.cur);
+            if let Some(back) = output.back {
+                output.rebalance(back, &mut (None, 0));
+            }
+            output
+        }
+    }
+
+    // Returns everything behind the cursor, the node of the current element is
+    // split if necessary. On the ghost the whole list is returned.
+    pub fn split_after(&mut self) -> UnrolledList<T, N> {
+        let (Some(node), offset) = self.cur else {
+            return mem::take(self.list);
+        };
+        unsafe {
+            if offset + 1 < (*node.as_ptr()).len {
+                self.list.split_node(node, offset + 1);
+            }
+            let behind = self.list.len - self.index.unwrap() - 1;
+            let mut output = self.list.detach_after(Some(node), behind);
+            self.list.rebalance(node, &mut self.cur);
+            if let Some(front) = output.front {
+                output.rebalance(front, &mut (None, 0));
+            }
+            output
+        }
+    }
+
+    // Moves all elements of `input` in front of the cursor, its nodes are
+    // linked in as they are.
+    pub fn splice_before(&mut self, input: UnrolledList<T, N>) {
+        if input.is_empty() {
+            return;
+        }
+        unsafe {
+            let at = match self.cur {
+                (Some(node), offset) => {
+                    let node = if offset > 0 {
+                        let new = self.list.split_node(node, offset);
+                        self.cur = (Some(new), 0);
+                        new
+                    } else {
+                        node
+                    };
+                    // index moves forward by input length
+                    *self.index.as_mut().unwrap() += input.len;
+                    (*node.as_ptr()).front
+                }
+                // we are the ghost, so before us is the back of the list
+                (None, _) => self.list.back,
+            };
+            self.list.attach_after(at, input, &mut self.cur);
+        }
+    }
+
+    // Moves all elements of `input` behind the cursor, its nodes are linked in
+    // as they are.
+    pub fn splice_after(&mut self, input: UnrolledList<T, N>) {
+        if input.is_empty() {
+            return;
+        }
+        unsafe {
+            let at = match self.cur {
+                (Some(node), offset) => {
+                    if offset + 1 < (*node.as_ptr()).len {
+                        self.list.split_node(node, offset + 1);
+                    }
+                    Some(node)
+                }
+                // we are the ghost, so after us is the front of the list
+                (None, _) => None,
+            };
+            self.list.attach_after(at, input, &mut self.cur);
+        }
+    }
+}
+
+// +---------------------------------+
+// | Read-only Cursor Implementation |
+// +---------------------------------+
+
+// same "ghost" element model as `CursorMut`, but only borrows the list shared
+// such that multiple cursors can walk the same list at the same time
+pub struct Cursor<'a, T, const N: usize> {
+    cur: (Link<T, N>, usize),
+    list: &'a UnrolledList<T, N>,
+    index: Option<usize>,
+}
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    pub fn cursor(&self) -> Cursor<'_, T, N> {
+        Cursor {
+            cur: (None, 0),
+            list: self,
+            index: None,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> CursorMut<'a, T, N> {
+    // a read-only view at the same position, borrowing this cursor for as long
+    // as it is used
+    pub fn as_cursor(&self) -> Cursor<'_, T, N> {
+        Cursor {
+            cur: self.cur,
+            list: self.list,
+            index: self.index,
+        }
+    }
+}
+
+// derive would require `T: Clone`, which is not necessary to copy a cursor
+impl<'a, T, const N: usize> Clone for Cursor<'a, T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T, const N: usize> Copy for Cursor<'a, T, N> {}
+
+impl<'a, T, const N: usize> Cursor<'a, T, N> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        if self.cur.0.is_some() {
+            self.cur = unsafe { step_next(self.cur) };
+            if self.cur.0.is_some() {
+                *self.index.as_mut().unwrap() += 1;
+            } else {
+                // We just walked to the ghost, no more index
+                self.index = None;
+            }
+        } else if !self.list.is_empty() {
+            // we are the ghost, and there is a real front, so move to it
+            self.cur = (self.list.front, 0);
+            self.index = Some(0);
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if self.cur.0.is_some() {
+            self.cur = unsafe { step_prev(self.cur) };
+            if self.cur.0.is_some() {
+                *self.index.as_mut().unwrap() -= 1;
+            } else {
+                self.index = None;
+            }
+        } else if !self.list.is_empty() {
+            // we are the ghost, and there is a real back, so move to it
+            self.cur = (self.list.back, self.list.back_offset());
+            self.index = Some(self.list.len - 1);
+        }
+    }
+
+    // the returned references are bound to the list and not to the cursor, as
+    // nobody can mutate the list while it is borrowed shared
+    pub fn current(&self) -> Option<&'a T> {
+        let (node, offset) = self.cur;
+        unsafe { node.map(|node| (*node.as_ptr()).get(offset)) }
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let (node, offset) = match self.cur.0 {
+            Some(_) => unsafe { step_next(self.cur) },
+            None => (self.list.front, 0),
+        };
+        unsafe { node.map(|node| (*node.as_ptr()).get(offset)) }
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let (node, offset) = match self.cur.0 {
+            Some(_) => unsafe { step_prev(self.cur) },
+            None => (self.list.back, self.list.back_offset()),
+        };
+        unsafe { node.map(|node| (*node.as_ptr()).get(offset)) }
+    }
+}
+
+// Cursor only hands out shared references just like Iter does
+unsafe impl<'a, T: Sync, const N: usize> Send for Cursor<'a, T, N> {}
+unsafe impl<'a, T: Sync, const N: usize> Sync for Cursor<'a, T, N> {}
+
+// +-------------------+
+// | Positional Access |
+// +-------------------+
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    // the element is found by walking whole nodes, so this is O(n / N)
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.locate(index)
+            .map(|(node, offset)| unsafe { (*node.as_ptr()).get(offset) })
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.locate(index)
+            .map(|(node, offset)| unsafe { (*node.as_ptr()).get_mut(offset) })
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        self.cursor_at(index)?.remove_current()
+    }
+
+    pub fn cursor_at(&mut self, index: usize) -> Option<CursorMut<'_, T, N>> {
+        let (node, offset) = self.locate(index)?;
+        Some(CursorMut {
+            cur: (Some(node), offset),
+            list: self,
+            index: Some(index),
+        })
+    }
+
+    // Inserts `elem` such that it ends up at `index`.
+    //
+    // Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, elem: T) {
+        if let Err(err) = self.try_insert(index, elem) {
+            err.handle();
+        }
+    }
+
+    // like `insert`, but hands the element back instead of aborting when there
+    // is no memory left to split a full node
+    //
+    // Panics if `index > len`.
+    pub fn try_insert(&mut self, index: usize, elem: T) -> Result<(), TryReserveError<T>> {
+        assert!(index <= self.len, "insertion index is out of bounds");
+        if index == self.len {
+            self.try_push_back(elem)
+        } else {
+            self.cursor_at(index).unwrap().try_insert_before(elem)
+        }
+    }
+}
+
+impl<T, const N: usize> Index<usize> for UnrolledList<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index is out of bounds")
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for UnrolledList<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index is out of bounds")
+    }
+}
+
+// +------------------------+
+// | Splitting and Splicing |
+// +------------------------+
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    // Moves all elements of `other` to the back of this list, leaving `other`
+    // empty. Only the nodes at the seam are merged, so this is O(N).
+    pub fn append(&mut self, other: &mut Self) {
+        // on the ghost the back of the list is right before us
+        self.cursor_mut().splice_before(mem::take(other));
+    }
+
+    // Moves all elements of `other` to the front of this list, leaving `other`
+    // empty.
+    pub fn prepend(&mut self, other: &mut Self) {
+        // on the ghost the front of the list is right after us
+        self.cursor_mut().splice_after(mem::take(other));
+    }
+
+    // Splits the list into two at the given index. Returns everything from
+    // `at` onwards, while this list keeps everything in front of it.
+    //
+    // Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+        if at == 0 {
+            return mem::take(self);
+        }
+        // `at - 1 < len` so the element in front of the split exists
+        self.cursor_at(at - 1).unwrap().split_after()
+    }
+}
+
+// +-----------+
+// | Filtering |
+// +-----------+
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        while let Some(elem) = cursor.current() {
+            if f(elem) {
+                cursor.move_next();
+            } else {
+                // the cursor moves onto the next element by itself
+                cursor.remove_current();
+            }
+        }
+    }
+}
+
+// +------------------------+
+// | Searching and Ordering |
+// +------------------------+
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|elem| elem == x)
+    }
+
+    pub fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    // searches from the back, but the index is still counted from the front
+    pub fn rposition<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().rposition(predicate)
+    }
+
+    pub fn find<P>(&self, mut predicate: P) -> Option<&T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|elem| predicate(elem))
+    }
+
+    // Removes consecutive repeated elements, keeping the first of each run.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    // Removes consecutive elements for which `same_bucket(next, kept)` returns
+    // true, where `kept` is the last element that stays in the list.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        // the cursor always stays on the last kept element
+        while let (Some(node), offset) = cursor.cur {
+            unsafe {
+                let (Some(next), next_offset) = step_next(cursor.cur) else {
+                    break;
+                };
+                // both may be in the same node, so only the elements are
+                // borrowed and not their nodes
+                let kept = &mut *Node::elem_ptr(node, offset);
+                if same_bucket(&mut *Node::elem_ptr(next, next_offset), kept) {
+                    cursor.remove_after();
+                } else {
+                    cursor.move_next();
+                }
+            }
+        }
+    }
+
+    // Reverses the order of the nodes and of the elements inside of them.
+    pub fn reverse(&mut self) {
+        let mut node = self.front;
+        while let Some(cur) = node {
+            unsafe {
+                let cur = &mut *cur.as_ptr();
+                let len = cur.len;
+                cur.elems[..len].reverse();
+                mem::swap(&mut cur.front, &mut cur.back);
+                // `front` is the old `back` now
+                node = cur.front;
+            }
+        }
+        mem::swap(&mut self.front, &mut self.back);
+    }
+
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b));
+    }
+
+    // Stable sort. Only the positions of the elements are sorted, while the
+    // elements stay in their nodes, so a panicking `compare` leaves the list as
+    // it was. Afterwards the elements are moved to their new positions, the
+    // nodes themselves are kept.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut slots = Vec::with_capacity(self.len);
+        let mut node = self.front;
+        while let Some(cur) = node {
+            unsafe {
+                for offset in 0..(*cur.as_ptr()).len {
+                    slots.push(Node::elem_ptr(cur, offset));
+                }
+                node = (*cur.as_ptr()).back;
+            }
+        }
+
+        let mut order: Vec<usize> = (0..slots.len()).collect();
+        order.sort_by(|&a, &b| unsafe { compare(&*slots[a], &*slots[b]) });
+
+        // nothing in here can panic, so no element is lost or read twice
+        let mut sorted = Vec::with_capacity(slots.len());
+        sorted.extend(order.iter().map(|&i| unsafe { slots[i].read() }));
+        for (slot, elem) in slots.into_iter().zip(sorted) {
+            unsafe { slot.write(elem) };
+        }
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+}
+
+// +--------+
+// | Traits |
+// +--------+
+
+// as a stack the front of the list is its top
+impl<T, const N: usize> Stack<T> for UnrolledList<T, N> {
+    fn push(&mut self, elem: T) {
+        UnrolledList::push_front(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        UnrolledList::pop_front(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        UnrolledList::front(self)
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut T> {
+        UnrolledList::front_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        UnrolledList::len(self)
+    }
+}
+
+impl<T, const N: usize> Queue<T> for UnrolledList<T, N> {
+    fn push_back(&mut self, elem: T) {
+        UnrolledList::push_back(self, elem)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        UnrolledList::pop_front(self)
+    }
+
+    fn front(&self) -> Option<&T> {
+        UnrolledList::front(self)
+    }
+
+    fn front_mut(&mut self) -> Option<&mut T> {
+        UnrolledList::front_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        UnrolledList::len(self)
+    }
+}
+
+impl<T, const N: usize> Deque<T> for UnrolledList<T, N> {
+    fn push_front(&mut self, elem: T) {
+        UnrolledList::push_front(self, elem)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        UnrolledList::pop_back(self)
+    }
+
+    fn back(&self) -> Option<&T> {
+        UnrolledList::back(self)
+    }
+
+    fn back_mut(&mut self) -> Option<&mut T> {
+        UnrolledList::back_mut(self)
+    }
+}
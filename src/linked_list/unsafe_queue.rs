@@ -1,8 +1,21 @@
-use std::ptr;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ptr::{self, NonNull};
 
-pub struct List<T> {
+use super::allocator::{self, Allocator, Global, TryReserveError};
+use super::Queue;
+
+pub struct List<T, A: Allocator = Global> {
     head: Link<T>,
     tail: Link<T>,
+    len: usize,
+    // maximum number of elements of a bounded queue
+    capacity: Option<usize>,
+    // all nodes are allocated and freed through this one
+    alloc: A,
 }
 
 type Link<T> = *mut Node<T>;
@@ -14,19 +27,57 @@ struct Node<T> {
 
 impl<T> List<T> {
     pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    // queue which never holds more than `capacity` elements
+    pub fn bounded(capacity: usize) -> Self {
+        Self::bounded_in(capacity, Global)
+    }
+}
+
+impl<T, A: Allocator> List<T, A> {
+    // creates an empty queue whose nodes will be allocated by `alloc`
+    pub fn new_in(alloc: A) -> Self {
         List {
             head: ptr::null_mut(),
             tail: ptr::null_mut(),
+            len: 0,
+            capacity: None,
+            alloc,
         }
     }
 
+    pub fn bounded_in(capacity: usize, alloc: A) -> Self {
+        let mut list = Self::new_in(alloc);
+        list.capacity = Some(capacity);
+        list
+    }
+
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    // panics if a bounded queue is full, use `try_push` for those instead
     pub fn push(&mut self, elem: T) {
+        assert!(!self.is_full(), "pushed onto a full bounded queue");
+        if let Err(err) = self.try_push(elem) {
+            err.handle();
+        }
+    }
+
+    // like `push`, but neither panics nor aborts, a full bounded queue and an
+    // allocator that is out of memory both hand the element back
+    pub fn try_push(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        if self.is_full() {
+            return Err(TryReserveError::capacity_overflow(elem));
+        }
         unsafe {
-            let new_tail = Box::into_raw(Box::new(Node {
+            let new_tail = allocator::try_allocate_node(&self.alloc, Node {
                 elem,
                 // When you push onto the tail, your next is always None
                 next: ptr::null_mut(),
-            }));
+            }, |node| node.elem)?.as_ptr();
 
             // .is_null checks for null, equivalent to checking for None
             if !self.tail.is_null() {
@@ -38,7 +89,21 @@ impl<T> List<T> {
             }
 
             self.tail = new_tail;
+            self.len += 1;
         }
+        Ok(())
+    }
+
+    // pushes the elements until one of them doesn't fit, that one is handed
+    // back and the rest of the iterator is dropped
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryReserveError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.try_push(elem)?;
+        }
+        Ok(())
     }
 
     pub fn pop(&mut self) -> Option<T> {
@@ -47,7 +112,8 @@ impl<T> List<T> {
                 None
             } else {
                 // rise from the grave
-                let head = Box::from_raw(self.head);
+                let head = NonNull::new_unchecked(self.head);
+                let head = allocator::deallocate(&self.alloc, head);
                 self.head = head.next;
 
                 if self.head.is_null() {
@@ -55,6 +121,7 @@ impl<T> List<T> {
                     // be empty
                     self.tail = ptr::null_mut();
                 }
+                self.len -= 1;
                 Some(head.elem)
             }
         }
@@ -67,18 +134,84 @@ impl<T> List<T> {
     pub fn peek_mut(&mut self) -> Option<&mut T> {
         unsafe { self.head.as_mut().map(|node| &mut node.elem) }
     }
+
+    // the element which was pushed last
+    pub fn peek_back(&self) -> Option<&T> {
+        unsafe { self.tail.as_ref().map(|node| &node.elem) }
+    }
+
+    pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.tail.as_mut().map(|node| &mut node.elem) }
+    }
+
+    // moves all elements of `other` behind the ones of `self`, which only
+    // links the tail of `self` to the head of `other`
+    // panics if a bounded queue would get more elements than its capacity
+    pub fn append(&mut self, other: &mut Self) {
+        assert!(
+            self.capacity
+                .is_none_or(|capacity| self.len + other.len <= capacity),
+            "appended more elements than a bounded queue can hold"
+        );
+        if other.is_empty() {
+            return;
+        }
+        unsafe {
+            if self.tail.is_null() {
+                self.head = other.head;
+            } else {
+                (*self.tail).next = other.head;
+            }
+        }
+        self.tail = other.tail;
+        self.len += other.len;
+        other.head = ptr::null_mut();
+        other.tail = ptr::null_mut();
+        other.len = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // `None` for queues without a limit
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.capacity.is_some_and(|capacity| self.len >= capacity)
+    }
 }
 
-impl<T> Drop for List<T> {
+impl<T, A: Allocator> Drop for List<T, A> {
     fn drop(&mut self) {
-        // just pop all elements one by one
-        // this is simple but can be inefficient
-        while self.pop().is_some() {}
+        // free the nodes one after the other, without keeping head, tail and
+        // len up to date like `pop` would
+        free_nodes(self.head, &self.alloc);
+    }
+}
+
+// frees `link` and all nodes after it
+fn free_nodes<T, A: Allocator>(mut link: Link<T>, alloc: &A) {
+    while let Some(node) = NonNull::new(link) {
+        let node = unsafe { allocator::deallocate(alloc, node) };
+        link = node.next;
     }
 }
 
 // IntoIter
-pub struct IntoIter<T>(List<T>);
+// owns the nodes which were not yielded yet, the tail is not needed anymore
+pub struct IntoIter<T, A: Allocator = Global> {
+    next: Link<T>,
+    len: usize,
+    alloc: A,
+    _boo: PhantomData<T>,
+}
 
 // Iter
 pub struct Iter<'a, T> {
@@ -90,11 +223,7 @@ pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
 }
 
-impl<T> List<T> {
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
-    }
-
+impl<T, A: Allocator> List<T, A> {
     pub fn iter(&self) -> Iter<'_, T> {
         unsafe {
             Iter {
@@ -112,11 +241,68 @@ impl<T> List<T> {
     }
 }
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator> IntoIterator for List<T, A> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // the list must not free the nodes it hands over, so it's never
+        // dropped and its allocator is moved out instead
+        let list = ManuallyDrop::new(self);
+        IntoIter {
+            next: list.head,
+            len: list.len,
+            alloc: unsafe { ptr::read(&list.alloc) },
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a List<T, A> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut List<T, A> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.pop()
+        let node = unsafe { allocator::deallocate(&self.alloc, NonNull::new(self.next)?) };
+        self.next = node.next;
+        self.len -= 1;
+        Some(node.elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<T, A: Allocator> Drop for IntoIter<T, A> {
+    fn drop(&mut self) {
+        free_nodes(self.next, &self.alloc);
     }
 }
 
@@ -145,3 +331,102 @@ impl<'a, T> Iterator for IterMut<'a, T> {
         }
     }
 }
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for List<T, A> {
+    fn clone(&self) -> Self {
+        let mut new_list = Self::new_in(self.alloc.clone());
+        new_list.capacity = self.capacity;
+        for elem in self {
+            new_list.push(elem.clone());
+        }
+        new_list
+    }
+}
+
+// panics if a bounded queue gets full
+impl<T, A: Allocator> Extend<T> for List<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for List<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, A: Allocator> PartialEq for List<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for List<T, A> {}
+
+impl<T: Hash, A: Allocator> Hash for List<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for elem in self {
+            elem.hash(state);
+        }
+    }
+}
+
+// +----------------------+
+// | Send and Sync Traits |
+// +----------------------+
+
+// the raw pointers opt us out of Send and Sync, but the list owns its nodes
+// just like a Box would
+unsafe impl<T: Send, A: Allocator + Send> Send for List<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for List<T, A> {}
+
+unsafe impl<T: Send, A: Allocator + Send> Send for IntoIter<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for IntoIter<T, A> {}
+
+// +--------+
+// | Traits |
+// +--------+
+
+impl<T, A: Allocator> Queue<T> for List<T, A> {
+    fn push_back(&mut self, elem: T) {
+        List::push(self, elem)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        List::pop(self)
+    }
+
+    fn front(&self) -> Option<&T> {
+        List::peek(self)
+    }
+
+    fn front_mut(&mut self) -> Option<&mut T> {
+        List::peek_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        List::len(self)
+    }
+}
@@ -0,0 +1,981 @@
+// Doubly linked list, where every node only stores a single link: the XOR of
+// the addresses of its previous and its next node.
+//
+// Knowing one neighbour of a node is enough to get the other one, so the list
+// can be walked in both directions as long as we remember where we came from.
+// The ends are linked to address 0, therefore the list behaves like a ring
+// through a "ghost" node at address 0, which is the same model the cursor of
+// `LinkedList` uses. As the links do not tell which neighbour is which, the
+// whole list is reversed by just swapping its front and back.
+//
+// The addresses are turned back into pointers with exposed provenance, every
+// node gets exposed when its address is mixed into a link.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr::{self, NonNull};
+
+use super::allocator::{self, Global, TryReserveError};
+use super::{Deque, Queue, Stack};
+
+pub struct XorLinkedList<T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<T>,
+}
+
+// null for the ghost
+type Link<T> = *mut Node<T>;
+
+struct Node<T> {
+    // address of the previous node XOR address of the next node
+    link: usize,
+    elem: T,
+}
+
+fn xor<T>(a: Link<T>, b: Link<T>) -> usize {
+    a.expose_provenance() ^ b.expose_provenance()
+}
+
+// the neighbour of `node` which is not `from`
+unsafe fn step<T>(from: Link<T>, node: Link<T>) -> Link<T> {
+    ptr::with_exposed_provenance_mut((*node).link ^ from.expose_provenance())
+}
+
+impl<T> XorLinkedList<T> {
+    pub fn new() -> Self {
+        XorLinkedList {
+            front: ptr::null_mut(),
+            back: ptr::null_mut(),
+            len: 0,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        unsafe { self.link_between(ptr::null_mut(), self.front, elem) };
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        unsafe { self.link_between(self.back, ptr::null_mut(), elem) };
+    }
+
+    // like `push_front`, but hands the element back instead of aborting when
+    // running out of memory
+    pub fn try_push_front(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        unsafe { self.try_link_between(ptr::null_mut(), self.front, elem)? };
+        Ok(())
+    }
+
+    pub fn try_push_back(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        unsafe { self.try_link_between(self.back, ptr::null_mut(), elem)? };
+        Ok(())
+    }
+
+    // pushes the elements to the back until one of them can't be allocated,
+    // that one is handed back and the rest of the iterator is dropped
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), TryReserveError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.try_push_back(elem)?;
+        }
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.front.is_null() {
+            return None;
+        }
+        unsafe { Some(self.unlink(ptr::null_mut(), self.front)) }
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.back.is_null() {
+            return None;
+        }
+        unsafe { Some(self.unlink(ptr::null_mut(), self.back)) }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        unsafe { self.front.as_ref().map(|node| &node.elem) }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.front.as_mut().map(|node| &mut node.elem) }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe { self.back.as_ref().map(|node| &node.elem) }
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.back.as_mut().map(|node| &mut node.elem) }
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    // O(1), the links are the same in both directions
+    pub fn reverse(&mut self) {
+        std::mem::swap(&mut self.front, &mut self.back);
+    }
+
+    // neighbour of `node` in the ring through the ghost
+    unsafe fn next_of(&self, from: Link<T>, node: Link<T>) -> Link<T> {
+        if !node.is_null() {
+            step(from, node)
+        } else if from == self.back {
+            // walking forwards through the ghost
+            self.front
+        } else {
+            self.back
+        }
+    }
+
+    // `prev` and `next` have to be neighbours (or the ghost), returns the new
+    // node
+    unsafe fn link_between(&mut self, prev: Link<T>, next: Link<T>, elem: T) -> Link<T> {
+        self.try_link_between(prev, next, elem).unwrap_or_else(|err| err.handle())
+    }
+
+    unsafe fn try_link_between(
+        &mut self,
+        prev: Link<T>,
+        next: Link<T>,
+        elem: T,
+    ) -> Result<Link<T>, TryReserveError<T>> {
+        let node = Node {
+            link: xor(prev, next),
+            elem,
+        };
+        let new = allocator::try_allocate_node(&Global, node, |node| node.elem)?.as_ptr();
+        if prev.is_null() {
+            self.front = new;
+        } else {
+            // replace `next` by `new` in the link of `prev`
+            (*prev).link ^= xor(next, new);
+        }
+        if next.is_null() {
+            self.back = new;
+        } else {
+            (*next).link ^= xor(prev, new);
+        }
+        self.len += 1;
+        Ok(new)
+    }
+
+    // unlinks and frees `node`, `from` is one of its neighbours
+    unsafe fn unlink(&mut self, from: Link<T>, node: Link<T>) -> T {
+        let other = step(from, node);
+        // the two neighbours become neighbours of each other
+        for (near, far) in [(from, other), (other, from)] {
+            if !near.is_null() {
+                (*near).link ^= xor(node, far);
+            } else if self.front == node {
+                self.front = far;
+            } else {
+                self.back = far;
+            }
+        }
+        self.len -= 1;
+        allocator::deallocate(&Global, NonNull::new_unchecked(node)).elem
+    }
+
+    // Links all nodes of `chain` in between `prev` and `next`, which have to be
+    // neighbours (or the ghost), and leaves `chain` empty.
+    unsafe fn link_chain_between(&mut self, prev: Link<T>, next: Link<T>, chain: &mut Self) {
+        if chain.is_empty() {
+            return;
+        }
+        let (first, last) = (chain.front, chain.back);
+        // the ends of the chain are linked to the ghost, which is replaced by
+        // their new neighbours
+        (*first).link ^= xor(ptr::null_mut(), prev);
+        (*last).link ^= xor(ptr::null_mut(), next);
+        if prev.is_null() {
+            self.front = first;
+        } else {
+            (*prev).link ^= xor(next, first);
+        }
+        if next.is_null() {
+            self.back = last;
+        } else {
+            (*next).link ^= xor(prev, last);
+        }
+        self.len += chain.len;
+        chain.front = ptr::null_mut();
+        chain.back = ptr::null_mut();
+        chain.len = 0;
+    }
+
+    // Unlinks the `len` nodes from `first` to `last` into a list of their own.
+    // `prev` is the neighbour in front of `first` and `next` the one after
+    // `last`, either of them can be the ghost.
+    unsafe fn unlink_chain(
+        &mut self,
+        prev: Link<T>,
+        first: Link<T>,
+        last: Link<T>,
+        next: Link<T>,
+        len: usize,
+    ) -> Self {
+        if prev.is_null() {
+            self.front = next;
+        } else {
+            (*prev).link ^= xor(first, next);
+        }
+        if next.is_null() {
+            self.back = prev;
+        } else {
+            (*next).link ^= xor(last, prev);
+        }
+        // the ends of the chain get the ghost as their neighbour
+        (*first).link ^= xor(prev, ptr::null_mut());
+        (*last).link ^= xor(next, ptr::null_mut());
+        self.len -= len;
+        XorLinkedList {
+            front: first,
+            back: last,
+            len,
+            _boo: PhantomData,
+        }
+    }
+
+    // the node at `index` together with the one in front of it, walking from
+    // the nearer end of the list
+    unsafe fn pair_at(&self, index: usize) -> (Link<T>, Link<T>) {
+        debug_assert!(index < self.len);
+        if index <= self.len / 2 {
+            let mut pair = (ptr::null_mut(), self.front);
+            for _ in 0..index {
+                advance(&mut pair);
+            }
+            pair
+        } else {
+            // walking backwards the pair is the other way around
+            let mut pair = (ptr::null_mut(), self.back);
+            for _ in index..self.len - 1 {
+                advance(&mut pair);
+            }
+            let (next, node) = pair;
+            (step(next, node), node)
+        }
+    }
+}
+
+impl<T> Drop for XorLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// +-----------------------+
+// | Joining and Splitting |
+// +-----------------------+
+
+// Moving nodes between lists only rewrites the links at the seams, the nodes
+// themselves stay where they are.
+impl<T> XorLinkedList<T> {
+    // moves all elements of `other` to the back of this list in O(1), leaving
+    // `other` empty
+    pub fn append(&mut self, other: &mut Self) {
+        unsafe { self.link_chain_between(self.back, ptr::null_mut(), other) };
+    }
+
+    // moves all elements of `other` to the front of this list in O(1), leaving
+    // `other` empty
+    pub fn prepend(&mut self, other: &mut Self) {
+        unsafe { self.link_chain_between(ptr::null_mut(), self.front, other) };
+    }
+
+    // Splits the list into two at the given index. Returns everything from
+    // `at` onwards, while this list keeps everything in front of it. Finding
+    // `at` walks from the nearer end, the split itself is O(1).
+    //
+    // Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+        if at == self.len {
+            return Self::new();
+        }
+        unsafe {
+            let (prev, first) = self.pair_at(at);
+            self.unlink_chain(prev, first, self.back, ptr::null_mut(), self.len - at)
+        }
+    }
+}
+
+// +-----------------+
+// | Iterator Traits |
+// +-----------------+
+
+// Both ends remember where they came from, `len` tells when they met.
+pub struct Iter<'a, T> {
+    front: (Link<T>, Link<T>),
+    back: (Link<T>, Link<T>),
+    len: usize,
+    _boo: PhantomData<&'a T>,
+}
+
+impl<T> XorLinkedList<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: (ptr::null_mut(), self.front),
+            back: (ptr::null_mut(), self.back),
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+}
+
+// advances the `(from, node)` pair and returns the node it was at
+unsafe fn advance<T>(pair: &mut (Link<T>, Link<T>)) -> Link<T> {
+    let (from, node) = *pair;
+    *pair = (node, step(from, node));
+    node
+}
+
+impl<'a, T> IntoIterator for &'a XorLinkedList<T> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe { Some(&(*advance(&mut self.front)).elem) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe { Some(&(*advance(&mut self.back)).elem) }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct IterMut<'a, T> {
+    front: (Link<T>, Link<T>),
+    back: (Link<T>, Link<T>),
+    len: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+impl<T> XorLinkedList<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: (ptr::null_mut(), self.front),
+            back: (ptr::null_mut(), self.back),
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut XorLinkedList<T> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe { Some(&mut (*advance(&mut self.front)).elem) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe { Some(&mut (*advance(&mut self.back)).elem) }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub struct IntoIter<T> {
+    list: XorLinkedList<T>,
+}
+
+impl<T> IntoIterator for XorLinkedList<T> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+// +---------------------------------------------+
+// | Default Traits, which should be expected... |
+// +---------------------------------------------+
+
+impl<T> Default for XorLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for XorLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Extend<T> for XorLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for XorLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Debug> Debug for XorLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for XorLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for XorLinkedList<T> {}
+
+impl<T: PartialOrd> PartialOrd for XorLinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T: Ord> Ord for XorLinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+impl<T: Hash> Hash for XorLinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for item in self {
+            item.hash(state);
+        }
+    }
+}
+
+// +----------------------+
+// | Send and Sync Traits |
+// +----------------------+
+unsafe impl<T: Send> Send for XorLinkedList<T> {}
+unsafe impl<T: Sync> Sync for XorLinkedList<T> {}
+
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
+// +-----------------------+
+// | Cursor Implementation |
+// +-----------------------+
+
+// Besides the current node the cursor has to remember the node before it, to
+// be able to follow the links. On the ghost that is the back of the list.
+pub struct CursorMut<'a, T> {
+    prev: Link<T>,
+    cur: Link<T>,
+    list: &'a mut XorLinkedList<T>,
+    index: Option<usize>,
+}
+
+impl<T> XorLinkedList<T> {
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            prev: self.back,
+            cur: ptr::null_mut(),
+            list: self,
+            index: None,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        unsafe {
+            let next = self.list.next_of(self.prev, self.cur);
+            self.prev = self.cur;
+            self.cur = next;
+        }
+        self.index = match (self.cur.is_null(), self.index) {
+            (true, _) => None,
+            (false, Some(index)) => Some(index + 1),
+            (false, None) => Some(0),
+        };
+    }
+
+    pub fn move_prev(&mut self) {
+        unsafe {
+            let prev = self.list.next_of(self.cur, self.prev);
+            self.cur = self.prev;
+            self.prev = prev;
+        }
+        self.index = match (self.cur.is_null(), self.index) {
+            (true, _) => None,
+            (false, Some(index)) => Some(index - 1),
+            (false, None) => Some(self.list.len - 1),
+        };
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.cur.as_mut().map(|node| &mut node.elem) }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        unsafe {
+            let next = self.list.next_of(self.prev, self.cur);
+            next.as_mut().map(|node| &mut node.elem)
+        }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        unsafe { self.prev.as_mut().map(|node| &mut node.elem) }
+    }
+
+    pub fn insert_after(&mut self, elem: T) {
+        if let Err(err) = self.try_insert_after(elem) {
+            err.handle();
+        }
+    }
+
+    pub fn insert_before(&mut self, elem: T) {
+        if let Err(err) = self.try_insert_before(elem) {
+            err.handle();
+        }
+    }
+
+    // like `insert_after`, but hands the element back instead of aborting
+    // when running out of memory
+    pub fn try_insert_after(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        unsafe {
+            let next = self.list.next_of(self.prev, self.cur);
+            self.list.try_link_between(self.cur, next, elem)?;
+        }
+        if self.cur.is_null() {
+            // the new node might be the back now, which is before the ghost
+            self.prev = self.list.back;
+        }
+        Ok(())
+    }
+
+    pub fn try_insert_before(&mut self, elem: T) -> Result<(), TryReserveError<T>> {
+        unsafe {
+            self.prev = self.list.try_link_between(self.prev, self.cur, elem)?;
+        }
+        // the cursor does not move, but its element is one further back
+        if let Some(index) = self.index.as_mut() {
+            *index += 1;
+        }
+        Ok(())
+    }
+
+    // removes the element after the cursor, the front when on the ghost
+    pub fn remove_after(&mut self) -> Option<T> {
+        unsafe {
+            let next = self.list.next_of(self.prev, self.cur);
+            if next.is_null() {
+                // the ghost cannot be removed
+                return None;
+            }
+            let elem = self.list.unlink(self.cur, next);
+            if self.cur.is_null() {
+                // the list might have lost its back as well
+                self.prev = self.list.back;
+            }
+            Some(elem)
+        }
+    }
+
+    // removes the element before the cursor, the back when on the ghost
+    pub fn remove_before(&mut self) -> Option<T> {
+        if self.prev.is_null() {
+            return None;
+        }
+        unsafe {
+            let before = step(self.cur, self.prev);
+            let elem = self.list.unlink(self.cur, self.prev);
+            self.prev = before;
+            // the cursor does not move, but its element is one further to the
+            // front
+            if let Some(index) = self.index.as_mut() {
+                *index -= 1;
+            }
+            Some(elem)
+        }
+    }
+
+    // removes the current element and moves onto the next one
+    pub fn remove_current(&mut self) -> Option<T> {
+        // the ghost cannot be removed
+        if self.cur.is_null() {
+            return None;
+        }
+        unsafe {
+            let next = step(self.prev, self.cur);
+            let elem = self.list.unlink(self.prev, self.cur);
+            self.cur = next;
+            if self.cur.is_null() {
+                self.index = None;
+                // the ghost comes after the new back
+                self.prev = self.list.back;
+            }
+            Some(elem)
+        }
+    }
+
+    // like `remove_current`, but hands the element back as a list of its own,
+    // without freeing and allocating its node
+    pub fn remove_current_as_list(&mut self) -> Option<XorLinkedList<T>> {
+        if self.cur.is_null() {
+            return None;
+        }
+        unsafe {
+            let next = step(self.prev, self.cur);
+            let removed = self.list.unlink_chain(self.prev, self.cur, self.cur, next, 1);
+            self.cur = next;
+            if self.cur.is_null() {
+                self.index = None;
+                self.prev = self.list.back;
+            }
+            Some(removed)
+        }
+    }
+
+    // Everything after the cursor is returned as a new list, on the ghost that
+    // is the whole list.
+    pub fn split_after(&mut self) -> XorLinkedList<T> {
+        unsafe {
+            let next = self.list.next_of(self.prev, self.cur);
+            if next.is_null() {
+                return XorLinkedList::new();
+            }
+            let len = self.list.len - self.index.map_or(0, |index| index + 1);
+            let back = self.list.back;
+            let output = self.list.unlink_chain(self.cur, next, back, ptr::null_mut(), len);
+            if self.cur.is_null() {
+                // the list is empty now
+                self.prev = ptr::null_mut();
+            }
+            output
+        }
+    }
+
+    // Everything before the cursor is returned as a new list, on the ghost that
+    // is the whole list.
+    pub fn split_before(&mut self) -> XorLinkedList<T> {
+        if self.prev.is_null() {
+            return XorLinkedList::new();
+        }
+        unsafe {
+            let len = self.index.unwrap_or(self.list.len);
+            let front = self.list.front;
+            let output = self.list.unlink_chain(ptr::null_mut(), front, self.prev, self.cur, len);
+            // the current element is the front now, on the ghost the list is
+            // empty
+            self.prev = ptr::null_mut();
+            if self.index.is_some() {
+                self.index = Some(0);
+            }
+            output
+        }
+    }
+
+    // Moves the elements of `input` in between the cursor and the element after
+    // it, the cursor stays where it is.
+    pub fn splice_after(&mut self, mut input: XorLinkedList<T>) {
+        unsafe {
+            let next = self.list.next_of(self.prev, self.cur);
+            self.list.link_chain_between(self.cur, next, &mut input);
+        }
+        if self.cur.is_null() {
+            // an empty list gets a new back
+            self.prev = self.list.back;
+        }
+    }
+
+    // Moves the elements of `input` in between the element before the cursor
+    // and the cursor, whose index grows by their number.
+    pub fn splice_before(&mut self, mut input: XorLinkedList<T>) {
+        if input.is_empty() {
+            return;
+        }
+        let (last, len) = (input.back, input.len);
+        unsafe { self.list.link_chain_between(self.prev, self.cur, &mut input) };
+        self.prev = last;
+        if let Some(index) = self.index.as_mut() {
+            *index += len;
+        }
+    }
+}
+
+// +---------------------------------+
+// | Read-only Cursor Implementation |
+// +---------------------------------+
+
+// Walks the list just like `CursorMut`, but only borrows it shared, so several
+// of them can be used at the same time.
+pub struct Cursor<'a, T> {
+    prev: Link<T>,
+    cur: Link<T>,
+    list: &'a XorLinkedList<T>,
+    index: Option<usize>,
+}
+
+impl<T> XorLinkedList<T> {
+    pub fn cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            prev: self.back,
+            cur: ptr::null_mut(),
+            list: self,
+            index: None,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    // a read-only cursor at the same position, which borrows this one
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            prev: self.prev,
+            cur: self.cur,
+            list: self.list,
+            index: self.index,
+        }
+    }
+}
+
+// derive would require `T: Clone`
+impl<'a, T> Clone for Cursor<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for Cursor<'a, T> {}
+
+impl<'a, T> Cursor<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        unsafe {
+            let next = self.list.next_of(self.prev, self.cur);
+            self.prev = self.cur;
+            self.cur = next;
+        }
+        self.index = match (self.cur.is_null(), self.index) {
+            (true, _) => None,
+            (false, Some(index)) => Some(index + 1),
+            (false, None) => Some(0),
+        };
+    }
+
+    pub fn move_prev(&mut self) {
+        unsafe {
+            let prev = self.list.next_of(self.cur, self.prev);
+            self.cur = self.prev;
+            self.prev = prev;
+        }
+        self.index = match (self.cur.is_null(), self.index) {
+            (true, _) => None,
+            (false, Some(index)) => Some(index - 1),
+            (false, None) => Some(self.list.len - 1),
+        };
+    }
+
+    // bound to the list and not to the cursor, nobody can change the list
+    // while it is borrowed shared
+    pub fn current(&self) -> Option<&'a T> {
+        unsafe { self.cur.as_ref().map(|node| &node.elem) }
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        unsafe {
+            let next = self.list.next_of(self.prev, self.cur);
+            next.as_ref().map(|node| &node.elem)
+        }
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        unsafe { self.prev.as_ref().map(|node| &node.elem) }
+    }
+}
+
+// the cursor only reads the list, just like `Iter`
+unsafe impl<'a, T: Sync> Send for Cursor<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Cursor<'a, T> {}
+
+// +--------+
+// | Traits |
+// +--------+
+
+// as a stack the front of the list is its top
+impl<T> Stack<T> for XorLinkedList<T> {
+    fn push(&mut self, elem: T) {
+        XorLinkedList::push_front(self, elem)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        XorLinkedList::pop_front(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        XorLinkedList::front(self)
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut T> {
+        XorLinkedList::front_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        XorLinkedList::len(self)
+    }
+}
+
+impl<T> Queue<T> for XorLinkedList<T> {
+    fn push_back(&mut self, elem: T) {
+        XorLinkedList::push_back(self, elem)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        XorLinkedList::pop_front(self)
+    }
+
+    fn front(&self) -> Option<&T> {
+        XorLinkedList::front(self)
+    }
+
+    fn front_mut(&mut self) -> Option<&mut T> {
+        XorLinkedList::front_mut(self)
+    }
+
+    fn len(&self) -> usize {
+        XorLinkedList::len(self)
+    }
+}
+
+impl<T> Deque<T> for XorLinkedList<T> {
+    fn push_front(&mut self, elem: T) {
+        XorLinkedList::push_front(self, elem)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        XorLinkedList::pop_back(self)
+    }
+
+    fn back(&self) -> Option<&T> {
+        XorLinkedList::back(self)
+    }
+
+    fn back_mut(&mut self) -> Option<&mut T> {
+        XorLinkedList::back_mut(self)
+    }
+}